@@ -0,0 +1,92 @@
+//! # Primitives
+//!
+//! Host-side benchmarks of each primitive, comparing the fixed-width limb arithmetic used by
+//! this crate against `dashu` and `k256` equivalents. Run with `cargo bench`.
+//!
+//! These measure native execution only. Compute unit costs on SVM depend on the syscalls and
+//! are not captured here.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use dashu::integer::{fast_div::ConstDivisor, modular::IntoRing, UBig};
+use k256::{
+    elliptic_curve::{ops::Reduce, sec1::FromEncodedPoint, Field},
+    AffinePoint, EncodedPoint, ProjectivePoint, Scalar, U256,
+};
+use solana_secp256k1::{eth::RecoverableSignature, CompressedPoint, Curve, Secp256k1Point, UncompressedPoint};
+
+const K: [u8; 32] = [
+    0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10,
+    0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20,
+];
+
+fn scalar(k: &[u8; 32]) -> Scalar {
+    <Scalar as Reduce<U256>>::reduce_bytes(k.into())
+}
+
+fn mod_n(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mod_n");
+    let ring = ConstDivisor::new(UBig::from_be_bytes(&Curve::N));
+    let n_sub_2 = UBig::from_be_bytes(&Curve::N_SUB_2);
+
+    group.bench_function("mul/limbs", |b| b.iter(|| Curve::mul_mod_n(black_box(&K), black_box(&Curve::G.x()))));
+    group.bench_function("mul/dashu", |b| {
+        b.iter(|| {
+            let a = UBig::from_be_bytes(black_box(&K)).into_ring(&ring);
+            let g = UBig::from_be_bytes(black_box(&Curve::G.x())).into_ring(&ring);
+            (a * g).residue()
+        })
+    });
+    group.bench_function("mul/k256", |b| b.iter(|| scalar(black_box(&K)) * scalar(black_box(&Curve::G.x()))));
+
+    group.bench_function("inv/limbs", |b| b.iter(|| Curve::mod_inv_n(black_box(&K))));
+    group.bench_function("inv/dashu", |b| {
+        b.iter(|| UBig::from_be_bytes(black_box(&K)).into_ring(&ring).pow(&n_sub_2).residue())
+    });
+    group.bench_function("inv/k256", |b| b.iter(|| scalar(black_box(&K)).invert()));
+    group.finish();
+}
+
+fn mod_p(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mod_p");
+    let p = UBig::from_be_bytes(&Curve::P);
+
+    group.bench_function("negate/limbs", |b| b.iter(|| Curve::negate_p(black_box(&K))));
+    group.bench_function("negate/dashu", |b| b.iter(|| &p - UBig::from_be_bytes(black_box(&K))));
+    group.finish();
+}
+
+fn points(c: &mut Criterion) {
+    let mut group = c.benchmark_group("points");
+    let p = Curve::mul_g(&K).unwrap();
+    let compressed = CompressedPoint::from(p);
+    let q = Curve::G_MULTIPLES[2];
+
+    group.bench_function("lift_x/limbs", |b| b.iter(|| Curve::lift_x(black_box(&p.x()))));
+    group.bench_function("lift_x/k256", |b| {
+        b.iter(|| {
+            let encoded = EncodedPoint::from_bytes(black_box(compressed.0)).unwrap();
+            AffinePoint::from_encoded_point(&encoded).unwrap()
+        })
+    });
+
+    let (p_k, q_k) = (ProjectivePoint::GENERATOR * scalar(&K), ProjectivePoint::GENERATOR * Scalar::ONE.double());
+    group.bench_function("add/limbs", |b| b.iter(|| black_box(p) + black_box(q)));
+    group.bench_function("add/k256", |b| b.iter(|| (black_box(p_k) + black_box(q_k)).to_affine()));
+
+    group.bench_function("mul_g", |b| b.iter(|| Curve::mul_g(black_box(&K))));
+    group.bench_function("ecmul", |b| b.iter(|| Curve::ecmul(black_box(&compressed), black_box(&K))));
+    group.bench_function("tweak", |b| b.iter(|| black_box(p).tweak(black_box(K))));
+    group.finish();
+}
+
+fn eth(c: &mut Criterion) {
+    let sig = RecoverableSignature { r: Curve::G.x(), s: K, recovery_id: 0 };
+    let p = UncompressedPoint(Curve::G.0);
+    c.bench_function("eth/recover_address", |b| b.iter(|| black_box(sig).recover_address(black_box(&[0u8; 32]))));
+    c.bench_function("eth/address", |b| b.iter(|| solana_secp256k1::eth::EthAddress::from(black_box(p))));
+}
+
+criterion_group!(benches, mod_n, mod_p, points, eth);
+criterion_main!(benches);
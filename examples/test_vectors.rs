@@ -0,0 +1,130 @@
+//! # Test Vectors
+//!
+//! Emits deterministic JSON test vectors for each primitive, so downstream programs and
+//! alternative implementations can cross-check against this crate's exact behavior. Every
+//! input is derived from a fixed seed, so the output is stable across runs and platforms. Run
+//! with `cargo run --example test_vectors > vectors.json`.
+//!
+//! Signatures are produced with `k256`'s RFC 6979 signer, and everything else is computed by
+//! this crate.
+
+use serde_json::{json, Value};
+use solana_secp256k1::{
+    bip340, eth::RecoverableSignature, eth::EthAddress, CompressedPoint, Curve, Secp256k1Point, SecretKey,
+    UncompressedPoint,
+};
+use sha2::{Digest, Sha256};
+
+const SEED: &[u8] = b"solana-secp256k1 test vectors";
+const COUNT: u32 = 8;
+
+/// Derives the `i`th input of a kind, reduced to a scalar in `1..𝑁`.
+fn scalar(kind: &str, i: u32) -> [u8; 32] {
+    let hash: [u8; 32] = Sha256::new()
+        .chain_update(SEED)
+        .chain_update(kind)
+        .chain_update(i.to_le_bytes())
+        .finalize()
+        .into();
+    Curve::add_mod_n(&hash, &[0u8; 32])
+}
+
+fn point(kind: &str, i: u32) -> UncompressedPoint {
+    Curve::mul_g(&scalar(kind, i)).expect("nonzero scalar")
+}
+
+fn result<T, E: std::fmt::Debug>(result: Result<T, E>, ok: impl FnOnce(T) -> Value) -> Value {
+    match result {
+        Ok(value) => ok(value),
+        Err(e) => json!({ "error": format!("{e:?}") }),
+    }
+}
+
+fn main() {
+    let mul_g: Vec<Value> = (0..COUNT)
+        .map(|i| {
+            let k = scalar("mul_g", i);
+            json!({ "k": hex::encode(k), "result": result(Curve::mul_g(&k), |p| json!(hex::encode(p.0))) })
+        })
+        .collect();
+
+    let ecmul: Vec<Value> = (0..COUNT)
+        .map(|i| {
+            let (p, k) = (point("ecmul.point", i).compress(), scalar("ecmul.k", i));
+            json!({
+                "point": hex::encode(p.0),
+                "k": hex::encode(k),
+                "result": result(Curve::ecmul(&p, &k), |p| json!(hex::encode(p.0))),
+            })
+        })
+        .collect();
+
+    let tweak: Vec<Value> = (0..COUNT)
+        .map(|i| {
+            let (p, t) = (point("tweak.point", i).compress(), scalar("tweak.t", i));
+            json!({
+                "point": hex::encode(p.0),
+                "tweak": hex::encode(t),
+                "add": result(p.tweak(t), |p| json!(hex::encode(p.0))),
+                "mul": result(p.tweak_mul(t), |p| json!(hex::encode(p.0))),
+            })
+        })
+        .collect();
+
+    // Half of the 𝑋-coordinates are arbitrary, so roughly half of them are off curve
+    let lift_x: Vec<Value> = (0..COUNT)
+        .map(|i| {
+            let x = if i % 2 == 0 { point("lift_x", i).x() } else { scalar("lift_x", i) };
+            json!({ "x": hex::encode(x), "result": result(Curve::lift_x(&x), |p| json!(hex::encode(p.0))) })
+        })
+        .collect();
+
+    let ecdsa: Vec<Value> = (0..COUNT)
+        .map(|i| {
+            let key = SecretKey::from_bytes(scalar("ecdsa.key", i)).expect("scalar in range");
+            let hash = scalar("ecdsa.hash", i);
+            let signer = ::k256::ecdsa::SigningKey::from_bytes(key.as_bytes().into()).expect("scalar in range");
+            let (signature, recovery_id) = signer.sign_prehash_recoverable(&hash).expect("valid prehash");
+            let bytes: [u8; 64] = signature.to_bytes().into();
+            let pubkey = key.public_key().expect("valid key");
+            let recoverable = RecoverableSignature {
+                r: bytes[..32].try_into().expect("32 bytes"),
+                s: bytes[32..].try_into().expect("32 bytes"),
+                recovery_id: recovery_id.to_byte(),
+            };
+            json!({
+                "key": hex::encode(key.as_bytes()),
+                "pubkey": hex::encode(CompressedPoint::from(pubkey).0),
+                "hash": hex::encode(hash),
+                "signature": hex::encode(recoverable.to_bytes()),
+                "verify": Curve::verify_ecdsa(&hash, &bytes, &pubkey.compress()).is_ok(),
+                "eth_address": hex::encode(EthAddress::from(pubkey).0),
+                "recovered_address": result(recoverable.recover_address(&hash), |a| json!(hex::encode(a.0))),
+            })
+        })
+        .collect();
+
+    let bip340_nonce: Vec<Value> = (0..COUNT)
+        .map(|i| {
+            let key = SecretKey::from_bytes(scalar("bip340.key", i)).expect("scalar in range");
+            let (message, aux_rand) = (scalar("bip340.message", i), scalar("bip340.aux", i));
+            json!({
+                "key": hex::encode(key.as_bytes()),
+                "message": hex::encode(message),
+                "aux_rand": hex::encode(aux_rand),
+                "nonce": result(bip340::nonce(&key, &message, &aux_rand), |k| json!(hex::encode(k))),
+            })
+        })
+        .collect();
+
+    let vectors = json!({
+        "seed": String::from_utf8_lossy(SEED),
+        "mul_g": mul_g,
+        "ecmul": ecmul,
+        "tweak": tweak,
+        "lift_x": lift_x,
+        "ecdsa": ecdsa,
+        "bip340_nonce": bip340_nonce,
+    });
+    println!("{}", serde_json::to_string_pretty(&vectors).expect("serializable"));
+}
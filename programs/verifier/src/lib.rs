@@ -0,0 +1,333 @@
+//! # Secp256k1 Verifier
+//!
+//! A small example program built on `solana-secp256k1`, serving both as living documentation
+//! and as a target for compute unit and integration testing. It takes no accounts, and the
+//! first byte of instruction data selects the instruction:
+//!
+//! | Tag | Instruction     | Data                                            | Return data  |
+//! |-----|-----------------|-------------------------------------------------|--------------|
+//! | 0   | `VerifyEcdsa`   | `hash[32] ‖ r[32] ‖ s[32] ‖ v[1] ‖ pubkey[64]`  | —            |
+//! | 1   | `VerifySchnorr` | `pubkey_x[32] ‖ message[32] ‖ r[32] ‖ s[32]`    | —            |
+//! | 2   | `Ecmul`         | `point[33] ‖ k[32]`                             | `point[64]`  |
+//! | 3   | `Tweak`         | `point[33] ‖ tweak[32]`                         | `point[64]`  |
+//! | 4   | `MulG`          | `k[32]`                                         | `point[64]`  |
+//! | 5   | `Decompress`    | `point[33]`                                     | `point[64]`  |
+//! | 6   | `DoubleMul`     | `a[32] ‖ point[33] ‖ b[32]`                     | `point[64]`  |
+//! | 7   | `ModInv`        | `k[32]`                                         | `k⁻¹[32]`    |
+//! | 8   | `PointAdd`      | `p[64] ‖ q[64]`                                 | `point[64]`  |
+//! | 9   | `EcaddMany`     | `point[64] × n`, with `n ≥ 1`                   | `point[64]`  |
+//! | 10  | `VerifyBip340`  | `pubkey_x[32] ‖ message[32] ‖ r[32] ‖ s[32]`    | —            |
+//!
+//! Points are SEC1 compressed on the way in and returned uncompressed (without the `0x04`
+//! prefix) via return data, except for `PointAdd` and `EcaddMany`, which take raw uncompressed
+//! points as is, without checking they are on curve, so each instruction costs what
+//! `solana_secp256k1::cu::cu_estimate` budgets for the operation it wraps. `VerifySchnorr`
+//! spells BIP-340 out by hand, while `VerifyBip340` calls `bip340::verify`.
+
+use sha2::{Digest, Sha256};
+use solana_program_error::ProgramError;
+use solana_secp256k1::{backend::secp256k1_recover, bip340, CompressedPoint, Curve, Secp256k1Error, Secp256k1Point, UncompressedPoint};
+
+/// ### Return Data
+///
+/// The value an instruction sets as return data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnData {
+    Point(UncompressedPoint),
+    Scalar([u8; 32]),
+}
+
+impl ReturnData {
+    /// ### As Bytes
+    ///
+    /// The return data as set by the program.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            ReturnData::Point(point) => &point.0,
+            ReturnData::Scalar(k) => k,
+        }
+    }
+}
+
+/// ### Process Instruction
+///
+/// Decodes and executes a single instruction, returning the value to be set as return data,
+/// if any.
+pub fn process_instruction(data: &[u8]) -> Result<Option<ReturnData>, ProgramError> {
+    let (tag, data) = data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+    match tag {
+        0 => {
+            let data: &[u8; 161] = data.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
+            verify_ecdsa(array(&data[..32]), array(&data[32..96]), data[96], array(&data[97..]))?;
+            Ok(None)
+        }
+        1 => {
+            let data: &[u8; 128] = data.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
+            verify_schnorr(array(&data[..32]), array(&data[32..64]), array(&data[64..]))?;
+            Ok(None)
+        }
+        2 => {
+            let (point, k) = point_and_scalar(data)?;
+            Ok(Some(ReturnData::Point(Curve::ecmul(&point, &k)?)))
+        }
+        3 => {
+            let (point, tweak) = point_and_scalar(data)?;
+            Ok(Some(ReturnData::Point(UncompressedPoint::try_from(point)?.tweak(tweak)?)))
+        }
+        4 => Ok(Some(ReturnData::Point(Curve::mul_g(scalar(data)?)?))),
+        5 => Ok(Some(ReturnData::Point(compressed(data)?.decompress()?))),
+        6 => {
+            let data: &[u8; 97] = data.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
+            let point = compressed(&data[32..65])?;
+            Ok(Some(ReturnData::Point(Curve::double_mul(array(&data[..32]), &point, array(&data[65..]))?)))
+        }
+        7 => Ok(Some(ReturnData::Scalar(Curve::mod_inv_n(scalar(data)?)?))),
+        8 => {
+            let data: &[u8; 128] = data.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
+            Ok(Some(ReturnData::Point((UncompressedPoint(*array(&data[..64])) + UncompressedPoint(*array(&data[64..])))?)))
+        }
+        9 => {
+            if data.is_empty() || data.len() % 64 != 0 {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            let mut points = [UncompressedPoint([0u8; 64]); MAX_POINTS];
+            let points = points.get_mut(..data.len() / 64).ok_or(ProgramError::InvalidInstructionData)?;
+            for (point, bytes) in points.iter_mut().zip(data.chunks_exact(64)) {
+                point.0.copy_from_slice(bytes);
+            }
+            Ok(Some(ReturnData::Point(Curve::ecadd_many(points)?)))
+        }
+        10 => {
+            let data: &[u8; 128] = data.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
+            bip340::verify(array(&data[..32]), &data[32..64], array(&data[64..]))?;
+            Ok(None)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// The most points `EcaddMany` takes, more than fit in a transaction.
+const MAX_POINTS: usize = 24;
+
+/// ### Verify ECDSA
+///
+/// Recovers the signer of `hash` and checks it matches `pubkey`.
+pub fn verify_ecdsa(hash: &[u8; 32], signature: &[u8; 64], v: u8, pubkey: &[u8; 64]) -> Result<(), ProgramError> {
+    if v > 1 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let signer = secp256k1_recover(hash, v == 1, signature).map_err(Secp256k1Error::from)?;
+    if &signer != pubkey {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// ### Verify Schnorr
+///
+/// Verifies a BIP-340 signature `(𝑟, 𝑠)` over `message` for the even-𝑌 public key with the
+/// given 𝑋-coordinate.
+///
+/// `𝑅 = 𝑠·𝐺 - 𝑒·𝑃` is calculated with a single recover, as `𝑃ₓ⁻¹(-𝑒𝑃ₓ·𝑃 + 𝑠𝑃ₓ·𝐺)`, and
+/// must have an even 𝑌-coordinate and an 𝑋-coordinate of 𝑟.
+pub fn verify_schnorr(pubkey_x: &[u8; 32], message: &[u8; 32], signature: &[u8; 64]) -> Result<(), ProgramError> {
+    let (r, s) = (array::<32>(&signature[..32]), array::<32>(&signature[32..]));
+    if *r >= Curve::P || *s >= Curve::N {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // 𝑒 = H(𝑟 ‖ 𝑃ₓ ‖ 𝑚) mod 𝑁
+    let tag = Sha256::digest(b"BIP0340/challenge");
+    let e: [u8; 32] = Sha256::new()
+        .chain_update(tag)
+        .chain_update(tag)
+        .chain_update(r)
+        .chain_update(pubkey_x)
+        .chain_update(message)
+        .finalize()
+        .into();
+    let e = Curve::add_mod_n(&e, &[0u8; 32]);
+
+    let mut sig = [0u8; 64];
+    sig[..32].copy_from_slice(pubkey_x);
+    sig[32..].copy_from_slice(&Curve::negate_n(&Curve::mul_mod_n(&e, pubkey_x)));
+    let z = Curve::negate_n(&Curve::mul_mod_n(s, pubkey_x));
+    let big_r = UncompressedPoint(secp256k1_recover(&z, false, &sig).map_err(Secp256k1Error::from)?);
+
+    if big_r.is_odd() || big_r.x() != *r {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+fn point_and_scalar(data: &[u8]) -> Result<(CompressedPoint, [u8; 32]), ProgramError> {
+    let data: &[u8; 65] = data.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
+    Ok((compressed(&data[..33])?, *array(&data[33..])))
+}
+
+fn compressed(data: &[u8]) -> Result<CompressedPoint, ProgramError> {
+    let point = CompressedPoint(*<&[u8; 33]>::try_from(data).map_err(|_| ProgramError::InvalidInstructionData)?);
+    if !point.is_odd() && !point.is_even() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(point)
+}
+
+fn scalar(data: &[u8]) -> Result<&[u8; 32], ProgramError> {
+    data.try_into().map_err(|_| ProgramError::InvalidInstructionData)
+}
+
+fn array<const N: usize>(s: &[u8]) -> &[u8; N] {
+    s.try_into().expect("length checked by caller")
+}
+
+#[cfg(target_os = "solana")]
+mod entrypoint {
+    use solana_program_error::ProgramError;
+
+    extern "C" {
+        fn sol_set_return_data(data: *const u8, length: u64);
+    }
+
+    /// ### Entrypoint
+    ///
+    /// The program takes no accounts, so the serialized input is just
+    /// `num_accounts[8] ‖ data_len[8] ‖ data ‖ program_id[32]`.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called by the runtime with a valid serialized input buffer.
+    #[no_mangle]
+    pub unsafe extern "C" fn entrypoint(input: *mut u8) -> u64 {
+        if *(input as *const u64) != 0 {
+            return ProgramError::InvalidArgument.into();
+        }
+        let len = *(input.add(8) as *const u64) as usize;
+        let data = core::slice::from_raw_parts(input.add(16), len);
+        match super::process_instruction(data) {
+            Ok(Some(data)) => {
+                let data = data.as_bytes();
+                sol_set_return_data(data.as_ptr(), data.len() as u64);
+                0
+            }
+            Ok(None) => 0,
+            Err(e) => e.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use k256::ecdsa::{signature::hazmat::PrehashSigner, RecoveryId, Signature, SigningKey};
+
+    use super::*;
+
+    fn from_hex<const N: usize>(s: &str) -> [u8; N] {
+        let mut out = [0u8; N];
+        for (i, b) in out.iter_mut().enumerate() {
+            *b = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn ecdsa() {
+        let key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let hash = [0x42u8; 32];
+        let (sig, v): (Signature, RecoveryId) = key.sign_prehash(&hash).unwrap();
+        let pubkey = key.verifying_key().to_encoded_point(false);
+
+        let mut data = vec![0u8];
+        data.extend_from_slice(&hash);
+        data.extend_from_slice(&sig.to_bytes());
+        data.push(v.to_byte());
+        data.extend_from_slice(&pubkey.as_bytes()[1..]);
+        assert_eq!(process_instruction(&data), Ok(None));
+
+        data[1] ^= 1;
+        assert!(process_instruction(&data).is_err());
+    }
+
+    #[test]
+    fn schnorr() {
+        // BIP-340 test vectors 0 and 1
+        for (pubkey_x, message, signature) in [
+            (
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+            ),
+            (
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a",
+            ),
+        ] {
+            let mut data = vec![1u8];
+            data.extend_from_slice(&from_hex::<32>(pubkey_x));
+            data.extend_from_slice(&from_hex::<32>(message));
+            data.extend_from_slice(&from_hex::<64>(signature));
+            assert_eq!(process_instruction(&data), Ok(None));
+
+            data[40] ^= 1;
+            assert_eq!(process_instruction(&data), Err(ProgramError::InvalidArgument));
+        }
+    }
+
+    #[test]
+    fn ecmul_and_tweak() {
+        let mut k = [0u8; 32];
+        k[31] = 3;
+        let g = CompressedPoint::from(Curve::G);
+
+        let mut data = vec![2u8];
+        data.extend_from_slice(&g.0);
+        data.extend_from_slice(&k);
+        assert_eq!(process_instruction(&data), Ok(Some(ReturnData::Point(Curve::G_MULTIPLES[2]))));
+
+        data[0] = 3;
+        assert_eq!(process_instruction(&data), Ok(Some(ReturnData::Point(Curve::G_MULTIPLES[3]))));
+
+        assert_eq!(process_instruction(&[4]), Err(ProgramError::InvalidInstructionData));
+        assert_eq!(process_instruction(&data[..64]), Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let scalar = |k: u8| {
+            let mut bytes = [0u8; 32];
+            bytes[31] = k;
+            bytes
+        };
+        let point = |i: usize| ReturnData::Point(Curve::G_MULTIPLES[i]);
+        let g = CompressedPoint::from(Curve::G);
+
+        assert_eq!(process_instruction(&[&[4u8][..], &scalar(3)].concat()), Ok(Some(point(2))));
+        assert_eq!(process_instruction(&[&[5u8][..], &Curve::G_MULTIPLES[4].compress().0].concat()), Ok(Some(point(4))));
+        // 2·𝐺 + 3·𝐺
+        assert_eq!(process_instruction(&[&[6u8][..], &scalar(2), &g.0, &scalar(3)].concat()), Ok(Some(point(4))));
+        let inverse = process_instruction(&[&[7u8][..], &scalar(3)].concat());
+        assert_eq!(inverse, Ok(Some(ReturnData::Scalar(Curve::mod_inv_n(&scalar(3)).unwrap()))));
+        assert_eq!(process_instruction(&[&[8u8][..], &Curve::G.0, &Curve::G_MULTIPLES[1].0].concat()), Ok(Some(point(2))));
+        // 𝐺 + 2𝐺 + 4𝐺
+        let points = [Curve::G.0, Curve::G_MULTIPLES[1].0, Curve::G_MULTIPLES[3].0].concat();
+        assert_eq!(process_instruction(&[&[9u8][..], &points].concat()), Ok(Some(point(6))));
+        assert_eq!(process_instruction(&[9]), Err(ProgramError::InvalidInstructionData));
+        assert_eq!(process_instruction(&[&[9u8][..], &points[..100]].concat()), Err(ProgramError::InvalidInstructionData));
+        assert_eq!(process_instruction(&[&[9u8][..], &[0u8; 64 * 25]].concat()), Err(ProgramError::InvalidInstructionData));
+        assert_eq!(process_instruction(&[5, 0x05]), Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn bip340() {
+        // BIP-340 test vector 1, through the crate's own verification
+        let mut data = vec![10u8];
+        data.extend_from_slice(&from_hex::<32>("dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659"));
+        data.extend_from_slice(&from_hex::<32>("243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89"));
+        data.extend_from_slice(&from_hex::<64>("6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a"));
+        assert_eq!(process_instruction(&data), Ok(None));
+
+        data[40] ^= 1;
+        assert!(process_instruction(&data).is_err());
+    }
+}
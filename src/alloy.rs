@@ -0,0 +1,36 @@
+//! # alloy
+//!
+//! Conversions between this crate's Ethereum types and their `alloy-primitives` equivalents, so
+//! bridge backends can hand values straight between an EVM stack and SVM program code.
+
+use ::alloy_primitives::{Address, Signature, U256};
+
+use crate::eth::{EthAddress, RecoverableSignature};
+
+impl From<EthAddress> for Address {
+    fn from(a: EthAddress) -> Self {
+        Address::from(a.0)
+    }
+}
+
+impl From<Address> for EthAddress {
+    fn from(a: Address) -> Self {
+        EthAddress(a.into_array())
+    }
+}
+
+impl From<RecoverableSignature> for Signature {
+    fn from(sig: RecoverableSignature) -> Self {
+        Signature::new(U256::from_be_bytes(sig.r), U256::from_be_bytes(sig.s), sig.recovery_id == 1)
+    }
+}
+
+impl From<Signature> for RecoverableSignature {
+    fn from(sig: Signature) -> Self {
+        RecoverableSignature {
+            r: sig.r().to_be_bytes(),
+            s: sig.s().to_be_bytes(),
+            recovery_id: sig.v() as u8,
+        }
+    }
+}
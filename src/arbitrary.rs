@@ -0,0 +1,41 @@
+//! # Arbitrary
+//!
+//! `Arbitrary` implementations for fuzzing. Secret keys are reduced into `1..𝑁`, and points are
+//! derived from arbitrary secret keys, so every generated value is valid.
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{eth::RecoverableSignature, CompressedPoint, Curve, SecretKey, UncompressedPoint};
+
+impl<'a> Arbitrary<'a> for SecretKey {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut k = Curve::add_mod_n(&u.arbitrary()?, &[0u8; 32]);
+        if k == [0u8; 32] {
+            k[31] = 1;
+        }
+        Ok(SecretKey::from_bytes(k).expect("reduced into 1..𝑁"))
+    }
+}
+
+impl<'a> Arbitrary<'a> for UncompressedPoint {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(SecretKey::arbitrary(u)?.public_key().expect("secret keys have public keys"))
+    }
+}
+
+impl<'a> Arbitrary<'a> for CompressedPoint {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(CompressedPoint::from(UncompressedPoint::arbitrary(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for RecoverableSignature {
+    /// Generates a well-formed signature with `𝑟, 𝑠` in `1..𝑁`, low-𝑠 and a recovery id of 0 or 1.
+    /// It is not guaranteed to recover to a public key.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let r = *SecretKey::arbitrary(u)?.as_bytes();
+        let s = *SecretKey::arbitrary(u)?.as_bytes();
+        let s = Curve::conditional_negate_n(&s, Curve::is_high(&s));
+        Ok(RecoverableSignature { r, s, recovery_id: u.arbitrary::<bool>()? as u8 })
+    }
+}
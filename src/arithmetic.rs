@@ -0,0 +1,481 @@
+//! # Arithmetic
+//!
+//! Fixed-width, allocation-free 256-bit modular arithmetic. Values are held as four
+//! little-endian `u64` limbs on the stack, so the hot paths (modular addition, multiplication
+//! and negation) never touch the heap, which saves CUs on-chain.
+//!
+//! Both 𝑁 and 𝑃 are of the form `2²⁵⁶ - 𝑐` for a small `𝑐`, which lets a 512-bit product be
+//! reduced with a couple of multiply-adds instead of a full division. The P-256 moduli share
+//! the same form with a larger `𝑐`, and simply take a few more folds to reduce. Long chains of
+//! multiplications, such as the exponentiations behind square roots and inversion, are
+//! instead performed in Montgomery form, which replaces each reduction with a single
+//! interleaved multiply-and-shift pass.
+
+/// Four little-endian 64-bit limbs of a 256-bit integer.
+pub(crate) type Limbs = [u64; 4];
+
+/// ### Modulus
+///
+/// A modulus `𝑚 = 2²⁵⁶ - 𝑐`, along with its Montgomery constants `𝑛₀ = -𝑚⁻¹ mod 2⁶⁴` and
+/// `𝑅² mod 𝑚` where `𝑅 = 2²⁵⁶`.
+pub(crate) struct Modulus {
+    pub(crate) m: Limbs,
+    pub(crate) c: Limbs,
+    pub(crate) n0: u64,
+    pub(crate) r2: Limbs,
+}
+
+impl Modulus {
+    /// Derives `𝑐` and the Montgomery constants of an odd modulus `𝑚 > 2²⁵⁵` at compile time.
+    pub(crate) const fn new(m: Limbs) -> Self {
+        let c = sbb(&[0u64; 4], &m).0;
+        // Newton's iteration doubles the correct low bits of 𝑚⁻¹ mod 2⁶⁴ each step
+        let mut inv = 1u64;
+        let mut i = 0;
+        while i < 6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m[0].wrapping_mul(inv)));
+            i += 1;
+        }
+        let partial = Modulus { m, c, n0: inv.wrapping_neg(), r2: [0u64; 4] };
+        // 𝑅 ≡ 𝑐, so 𝑅² ≡ 𝑐²
+        let r2 = mul_mod(&c, &c, &partial);
+        Modulus { r2, ..partial }
+    }
+}
+
+/// 𝑁 = 2²⁵⁶ - 0x14551231950b75fc4402da1732fc9bebf
+pub(crate) const N: Modulus =
+    Modulus::new([0xbfd25e8cd0364141, 0xbaaedce6af48a03b, 0xfffffffffffffffe, 0xffffffffffffffff]);
+
+/// 𝑃 = 2²⁵⁶ - 0x1000003d1
+pub(crate) const P: Modulus =
+    Modulus::new([0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff]);
+
+/// P-256 field prime 𝑃 = 2²⁵⁶ - 2²²⁴ + 2¹⁹² + 2⁹⁶ - 1
+pub(crate) const P256_P: Modulus =
+    Modulus::new([0xffffffffffffffff, 0x00000000ffffffff, 0x0000000000000000, 0xffffffff00000001]);
+
+/// P-256 group order 𝑁
+pub(crate) const P256_N: Modulus =
+    Modulus::new([0xf3b9cac2fc632551, 0xbce6faada7179e84, 0xffffffffffffffff, 0xffffffff00000000]);
+
+/// Looks up the precomputed context of a big-endian modulus used by this crate, so callers with
+/// a runtime modulus can share the compile-time constants instead of rebuilding them.
+pub(crate) fn modulus(m: &[u8; 32]) -> Option<&'static Modulus> {
+    let m = from_be_bytes(m);
+    [&N, &P, &P256_P, &P256_N].into_iter().find(|k| eq(&k.m, &m))
+}
+
+/// Loads a big-endian 32-byte integer into little-endian limbs.
+#[inline(always)]
+pub(crate) const fn from_be_bytes(b: &[u8; 32]) -> Limbs {
+    let mut r = [0u64; 4];
+    let mut i = 0;
+    while i < 32 {
+        r[3 - i / 8] |= (b[i] as u64) << (56 - (i % 8) * 8);
+        i += 1;
+    }
+    r
+}
+
+/// Stores little-endian limbs as a big-endian 32-byte integer.
+#[inline(always)]
+pub(crate) const fn to_be_bytes(a: &Limbs) -> [u8; 32] {
+    let mut r = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        r[i] = (a[3 - i / 8] >> (56 - (i % 8) * 8)) as u8;
+        i += 1;
+    }
+    r
+}
+
+/// Adds two integers, returning the sum and the carry out.
+#[inline(always)]
+pub(crate) const fn adc(a: &Limbs, b: &Limbs) -> (Limbs, bool) {
+    let mut r = [0u64; 4];
+    let mut carry = false;
+    let mut i = 0;
+    while i < 4 {
+        let (s, c1) = a[i].overflowing_add(b[i]);
+        let (s, c2) = s.overflowing_add(carry as u64);
+        r[i] = s;
+        carry = c1 | c2;
+        i += 1;
+    }
+    (r, carry)
+}
+
+/// Subtracts two integers, returning the difference and the borrow out.
+#[inline(always)]
+pub(crate) const fn sbb(a: &Limbs, b: &Limbs) -> (Limbs, bool) {
+    let mut r = [0u64; 4];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < 4 {
+        let (d, b1) = a[i].overflowing_sub(b[i]);
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        r[i] = d;
+        borrow = b1 | b2;
+        i += 1;
+    }
+    (r, borrow)
+}
+
+/// Reduces any 256-bit integer modulo 𝑚. As 𝑚 > 2²⁵⁵, a single subtraction suffices.
+#[inline(always)]
+pub(crate) const fn reduce(a: &Limbs, m: &Modulus) -> Limbs {
+    let (d, borrow) = sbb(a, &m.m);
+    if borrow {
+        *a
+    } else {
+        d
+    }
+}
+
+/// Calculates `a + b mod 𝑚`.
+pub(crate) const fn add_mod(a: &Limbs, b: &Limbs, m: &Modulus) -> Limbs {
+    let (s, carry) = adc(&reduce(a, m), &reduce(b, m));
+    let (d, borrow) = sbb(&s, &m.m);
+    if carry || !borrow {
+        d
+    } else {
+        s
+    }
+}
+
+/// Calculates `a - b mod 𝑚`.
+pub(crate) const fn sub_mod(a: &Limbs, b: &Limbs, m: &Modulus) -> Limbs {
+    let (d, borrow) = sbb(&reduce(a, m), &reduce(b, m));
+    if borrow {
+        adc(&d, &m.m).0
+    } else {
+        d
+    }
+}
+
+/// Calculates `-a mod 𝑚`.
+pub(crate) const fn neg_mod(a: &Limbs, m: &Modulus) -> Limbs {
+    sub_mod(&[0u64; 4], a, m)
+}
+
+/// Selects `b` when `choice` is set and `a` otherwise, using a mask rather than a branch.
+#[inline(always)]
+pub(crate) const fn select(a: &Limbs, b: &Limbs, choice: bool) -> Limbs {
+    let mask = 0u64.wrapping_sub(choice as u64);
+    [
+        a[0] ^ (mask & (a[0] ^ b[0])),
+        a[1] ^ (mask & (a[1] ^ b[1])),
+        a[2] ^ (mask & (a[2] ^ b[2])),
+        a[3] ^ (mask & (a[3] ^ b[3])),
+    ]
+}
+
+/// Calculates `-a mod 𝑚` with a fixed sequence of subtractions and masked selects, so the
+/// cost does not depend on the value of `a`.
+pub(crate) const fn neg_mod_ct(a: &Limbs, m: &Modulus) -> Limbs {
+    let (d, borrow) = sbb(a, &m.m);
+    let a = select(&d, a, borrow);
+    let is_zero = (a[0] | a[1] | a[2] | a[3]) == 0;
+    select(&sbb(&m.m, &a).0, &[0u64; 4], is_zero)
+}
+
+/// Multiplies two integers into a 512-bit product.
+#[inline(always)]
+pub(crate) const fn mul_wide(a: &Limbs, b: &Limbs) -> [u64; 8] {
+    let mut r = [0u64; 8];
+    let mut i = 0;
+    while i < 4 {
+        let mut carry = 0u128;
+        let mut j = 0;
+        while j < 4 {
+            let t = a[i] as u128 * b[j] as u128 + r[i + j] as u128 + carry;
+            r[i + j] = t as u64;
+            carry = t >> 64;
+            j += 1;
+        }
+        r[i + 4] = carry as u64;
+        i += 1;
+    }
+    r
+}
+
+/// Reduces a 512-bit integer modulo `𝑚 = 2²⁵⁶ - 𝑐` by repeatedly folding the high half back in
+/// as `ℎ𝑖·2²⁵⁶ + 𝑙𝑜 ≡ ℎ𝑖·𝑐 + 𝑙𝑜`.
+pub(crate) const fn reduce_wide(mut w: [u64; 8], m: &Modulus) -> Limbs {
+    while w[4] | w[5] | w[6] | w[7] != 0 {
+        let t = mul_wide(&[w[4], w[5], w[6], w[7]], &m.c);
+        let mut carry = 0u128;
+        let mut i = 0;
+        while i < 8 {
+            let lo = if i < 4 { w[i] as u128 } else { 0 };
+            let s = t[i] as u128 + lo + carry;
+            w[i] = s as u64;
+            carry = s >> 64;
+            i += 1;
+        }
+    }
+    reduce(&[w[0], w[1], w[2], w[3]], m)
+}
+
+/// Calculates `a · b mod 𝑚`.
+pub(crate) const fn mul_mod(a: &Limbs, b: &Limbs, m: &Modulus) -> Limbs {
+    reduce_wide(mul_wide(a, b), m)
+}
+
+/// Loads a big-endian integer of any length, reducing it modulo 𝑚.
+pub(crate) fn from_be_slice(b: &[u8], m: &Modulus) -> Limbs {
+    let mut acc = [0u64; 4];
+    let first = b.len() % 32;
+    let mut chunk = [0u8; 32];
+    chunk[32 - first..].copy_from_slice(&b[..first]);
+    if first != 0 {
+        acc = reduce(&from_be_bytes(&chunk), m);
+    }
+    for c in b[first..].chunks_exact(32) {
+        chunk.copy_from_slice(c);
+        let lo = from_be_bytes(&chunk);
+        acc = reduce_wide([lo[0], lo[1], lo[2], lo[3], acc[0], acc[1], acc[2], acc[3]], m);
+    }
+    acc
+}
+
+/// Calculates `a·b·𝑅⁻¹ mod 𝑚` for `a, b < 𝑚` using coarsely integrated operand scanning.
+pub(crate) const fn mont_mul(a: &Limbs, b: &Limbs, m: &Modulus) -> Limbs {
+    let mut t = [0u64; 6];
+    let mut i = 0;
+    while i < 4 {
+        // t += a[i] * b
+        let mut carry = 0u128;
+        let mut j = 0;
+        while j < 4 {
+            let s = t[j] as u128 + a[i] as u128 * b[j] as u128 + carry;
+            t[j] = s as u64;
+            carry = s >> 64;
+            j += 1;
+        }
+        let s = t[4] as u128 + carry;
+        t[4] = s as u64;
+        t[5] = (s >> 64) as u64;
+
+        // t = (t + u * m) / 2⁶⁴, where u is chosen so the lowest limb cancels
+        let u = t[0].wrapping_mul(m.n0);
+        let mut carry = (t[0] as u128 + u as u128 * m.m[0] as u128) >> 64;
+        let mut j = 1;
+        while j < 4 {
+            let s = t[j] as u128 + u as u128 * m.m[j] as u128 + carry;
+            t[j - 1] = s as u64;
+            carry = s >> 64;
+            j += 1;
+        }
+        let s = t[4] as u128 + carry;
+        t[3] = s as u64;
+        t[4] = t[5] + (s >> 64) as u64;
+        i += 1;
+    }
+    // The result is less than 2𝑚, so at most one subtraction is needed
+    let r = [t[0], t[1], t[2], t[3]];
+    let (d, borrow) = sbb(&r, &m.m);
+    if t[4] != 0 || !borrow {
+        d
+    } else {
+        r
+    }
+}
+
+/// Converts an integer into Montgomery form `a·𝑅 mod 𝑚`.
+pub(crate) const fn to_montgomery(a: &Limbs, m: &Modulus) -> Limbs {
+    mont_mul(&reduce(a, m), &m.r2, m)
+}
+
+/// Converts an integer out of Montgomery form.
+pub(crate) const fn from_montgomery(a: &Limbs, m: &Modulus) -> Limbs {
+    mont_mul(a, &[1, 0, 0, 0], m)
+}
+
+/// Calculates `a^e mod 𝑚` by left-to-right square-and-multiply in Montgomery form.
+pub(crate) const fn pow_mod(a: &Limbs, e: &Limbs, m: &Modulus) -> Limbs {
+    let a = to_montgomery(a, m);
+    // 1 in Montgomery form is 𝑅 mod 𝑚 = 𝑐
+    let mut r = m.c;
+    let mut i = 256;
+    while i > 0 {
+        i -= 1;
+        r = mont_mul(&r, &r, m);
+        if (e[i / 64] >> (i % 64)) & 1 == 1 {
+            r = mont_mul(&r, &a, m);
+        }
+    }
+    from_montgomery(&r, m)
+}
+
+/// Calculates `a⁻¹ mod 𝑚` using Fermat's Little Theorem, or `None` if `a ≡ 0`.
+pub(crate) const fn inv_mod(a: &Limbs, m: &Modulus) -> Option<Limbs> {
+    let a = reduce(a, m);
+    if a[0] | a[1] | a[2] | a[3] == 0 {
+        return None;
+    }
+    let (e, _) = sbb(&m.m, &[2, 0, 0, 0]);
+    Some(pow_mod(&a, &e, m))
+}
+
+/// Calculates `𝑥³ + 7 mod 𝑃`, the right-hand side of the curve equation.
+pub(crate) const fn curve_rhs(x: &Limbs) -> Limbs {
+    add_mod(&mul_mod(&mul_mod(x, x, &P), x, &P), &[7, 0, 0, 0], &P)
+}
+
+/// Shifts an integer right by `n < 64` bits.
+pub(crate) const fn shr(a: &Limbs, n: u32) -> Limbs {
+    if n == 0 {
+        return *a;
+    }
+    [
+        a[0] >> n | a[1] << (64 - n),
+        a[1] >> n | a[2] << (64 - n),
+        a[2] >> n | a[3] << (64 - n),
+        a[3] >> n,
+    ]
+}
+
+/// Compares two integers for equality in a `const` context.
+pub(crate) const fn eq(a: &Limbs, b: &Limbs) -> bool {
+    a[0] == b[0] && a[1] == b[1] && a[2] == b[2] && a[3] == b[3]
+}
+
+/// Joins two fixed-width 32-byte halves into a 64-byte `x‖y` buffer.
+///
+/// All coordinates and scalars are exactly 32 bytes wide, so there is no padding to get wrong.
+#[inline(always)]
+pub(crate) const fn concat(x: &[u8; 32], y: &[u8; 32]) -> [u8; 64] {
+    let mut r = [0u8; 64];
+    let mut i = 0;
+    while i < 32 {
+        r[i] = x[i];
+        r[i + 32] = y[i];
+        i += 1;
+    }
+    r
+}
+
+/// Splits a 64-byte `x‖y` buffer into its two fixed-width 32-byte halves.
+#[inline(always)]
+pub(crate) const fn split(b: &[u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut x = [0u8; 32];
+    let mut y = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        x[i] = b[i];
+        y[i] = b[i + 32];
+        i += 1;
+    }
+    (x, y)
+}
+
+/// Loads a big-endian 64-byte `x‖y` point into a pair of little-endian limbs.
+pub(crate) const fn point_from_be_bytes(b: &[u8; 64]) -> [Limbs; 2] {
+    let (x, y) = split(b);
+    [from_be_bytes(&x), from_be_bytes(&y)]
+}
+
+/// Stores a pair of little-endian limbs as a big-endian 64-byte `x‖y` point.
+pub(crate) const fn point_to_be_bytes(p: &[Limbs; 2]) -> [u8; 64] {
+    concat(&to_be_bytes(&p[0]), &to_be_bytes(&p[1]))
+}
+
+/// Adds two affine points, doubling when they are equal, or returns `None` if the result is the
+/// point at infinity.
+pub(crate) const fn affine_add_checked(p: &[Limbs; 2], q: &[Limbs; 2]) -> Option<[Limbs; 2]> {
+    let (num, den) = if eq(&reduce(&p[0], &P), &reduce(&q[0], &P)) {
+        if !eq(&reduce(&p[1], &P), &reduce(&q[1], &P)) {
+            return None;
+        }
+        // m = 3x² / 2y
+        (mul_mod(&[3, 0, 0, 0], &mul_mod(&p[0], &p[0], &P), &P), add_mod(&p[1], &p[1], &P))
+    } else {
+        // m = (y_q - y_p) / (x_q - x_p)
+        (sub_mod(&q[1], &p[1], &P), sub_mod(&q[0], &p[0], &P))
+    };
+    let inv = match inv_mod(&den, &P) {
+        Some(inv) => inv,
+        None => return None,
+    };
+    let m = mul_mod(&num, &inv, &P);
+
+    // xr = m² - x_p - x_q
+    let xr = sub_mod(&sub_mod(&mul_mod(&m, &m, &P), &p[0], &P), &q[0], &P);
+
+    // yr = m·(x_p - xr) - y_p
+    let yr = sub_mod(&mul_mod(&m, &sub_mod(&p[0], &xr, &P), &P), &p[1], &P);
+
+    Some([xr, yr])
+}
+
+/// The Jacobian point at infinity, any point with `Z = 0`.
+pub(crate) const JACOBIAN_IDENTITY: [Limbs; 3] = [[1, 0, 0, 0], [1, 0, 0, 0], [0; 4]];
+
+/// Doubles a Jacobian point `(X, Y, Z)`, representing `(X/Z², Y/Z³)`, without an inversion.
+pub(crate) const fn jacobian_double(p: &[Limbs; 3]) -> [Limbs; 3] {
+    // secp256k1 has no points of order 2, so only the identity has `Y = 0`
+    if eq(&p[1], &[0; 4]) || eq(&p[2], &[0; 4]) {
+        return JACOBIAN_IDENTITY;
+    }
+    let [x, y, z] = p;
+    let b = mul_mod(y, y, &P);
+    let c = mul_mod(&b, &b, &P);
+    // S = 4·X·Y², M = 3·X² as 𝑎 = 0
+    let s = mul_mod(&[4, 0, 0, 0], &mul_mod(x, &b, &P), &P);
+    let m = mul_mod(&[3, 0, 0, 0], &mul_mod(x, x, &P), &P);
+    let x3 = sub_mod(&mul_mod(&m, &m, &P), &add_mod(&s, &s, &P), &P);
+    let y3 = sub_mod(&mul_mod(&m, &sub_mod(&s, &x3, &P), &P), &mul_mod(&[8, 0, 0, 0], &c, &P), &P);
+    let z3 = mul_mod(&[2, 0, 0, 0], &mul_mod(y, z, &P), &P);
+    [x3, y3, z3]
+}
+
+/// Adds an affine point to a Jacobian point without an inversion, doubling when they are equal
+/// and returning the identity when they cancel.
+pub(crate) const fn jacobian_add_affine(p: &[Limbs; 3], q: &[Limbs; 2]) -> [Limbs; 3] {
+    let [x1, y1, z1] = p;
+    let (x2, y2) = (reduce(&q[0], &P), reduce(&q[1], &P));
+    if eq(z1, &[0; 4]) {
+        return [x2, y2, [1, 0, 0, 0]];
+    }
+    let z1z1 = mul_mod(z1, z1, &P);
+    let u2 = mul_mod(&x2, &z1z1, &P);
+    let s2 = mul_mod(&y2, &mul_mod(z1, &z1z1, &P), &P);
+    let h = sub_mod(&u2, x1, &P);
+    let r = sub_mod(&s2, y1, &P);
+    if eq(&h, &[0; 4]) {
+        return if eq(&r, &[0; 4]) { jacobian_double(p) } else { JACOBIAN_IDENTITY };
+    }
+    let hh = mul_mod(&h, &h, &P);
+    let hhh = mul_mod(&h, &hh, &P);
+    let v = mul_mod(x1, &hh, &P);
+    // X3 = r² - H³ - 2V, Y3 = r·(V - X3) - Y1·H³, Z3 = Z1·H
+    let x3 = sub_mod(&sub_mod(&mul_mod(&r, &r, &P), &hhh, &P), &add_mod(&v, &v, &P), &P);
+    let y3 = sub_mod(&mul_mod(&r, &sub_mod(&v, &x3, &P), &P), &mul_mod(y1, &hhh, &P), &P);
+    [x3, y3, mul_mod(z1, &h, &P)]
+}
+
+/// Converts a Jacobian point to affine with a single inversion, or returns `None` for the
+/// identity.
+pub(crate) const fn jacobian_to_affine(p: &[Limbs; 3]) -> Option<[Limbs; 2]> {
+    let zi = match inv_mod(&p[2], &P) {
+        Some(zi) => zi,
+        None => return None,
+    };
+    let zi2 = mul_mod(&zi, &zi, &P);
+    Some([mul_mod(&p[0], &zi2, &P), mul_mod(&p[1], &mul_mod(&zi2, &zi, &P), &P)])
+}
+
+/// Calculates a square root of `a mod 𝑚` for `𝑚 ≡ 3 mod 4` as `a^((𝑚+1)/4)`, or `None` if `a` is
+/// not a quadratic residue.
+pub(crate) const fn sqrt_mod(a: &Limbs, m: &Modulus) -> Option<Limbs> {
+    let a = reduce(a, m);
+    let e = shr(&adc(&m.m, &[1, 0, 0, 0]).0, 2);
+    let r = pow_mod(&a, &e, m);
+    if eq(&mul_mod(&r, &r, m), &a) {
+        Some(r)
+    } else {
+        None
+    }
+}
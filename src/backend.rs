@@ -0,0 +1,588 @@
+//! # Backend
+//!
+//! Every expensive primitive in this crate is dispatched through this module. When compiled
+//! for SVM (`target_os = "solana"`), operations abuse the `sol_secp256k1_recover`,
+//! `sol_keccak256`, `sol_sha256` and `sol_big_mod_exp` syscalls for cheap CU-efficient math,
+//! and derive program addresses with `sol_try_find_program_address`.
+//! Everywhere else, such as in unit tests and client code, they are computed natively with
+//! `k256`, `sha2`, `sha3` and `dashu`, so they link and run at native speed without the recover
+//! trick. With the `crypto-bigint` feature, arithmetic modulo anything other than 𝑃 and 𝑁 uses
+//! `crypto_bigint::U256` on the stack instead of `dashu`, which can then be dropped with
+//! `--no-default-features`. Native builds need one of the two.
+//!
+//! With the `test-stubs` feature, host builds take the syscall path too, linking against
+//! native stubs of each syscall, so `cargo test` exercises exactly the same code the SBF build
+//! runs.
+
+#[cfg(any(target_os = "solana", feature = "test-stubs"))]
+pub use syscall::*;
+
+#[cfg(not(any(target_os = "solana", feature = "test-stubs")))]
+pub use native::*;
+
+#[cfg(any(target_os = "solana", feature = "test-stubs"))]
+mod syscall {
+    use core::mem::MaybeUninit;
+
+    use solana_nostd_secp256k1_recover::Secp256k1RecoverError;
+
+    use crate::{arithmetic, Curve, Secp256k1Error, Secp256k1Point};
+
+    extern "C" {
+        fn sol_secp256k1_recover(hash: *const u8, recovery_id: u64, signature: *const u8, result: *mut u8) -> u64;
+        fn sol_keccak256(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64;
+        fn sol_sha256(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64;
+        fn sol_try_find_program_address(
+            seeds: *const u8,
+            seeds_len: u64,
+            program_id: *const u8,
+            address: *mut u8,
+            bump_seed: *mut u8,
+        ) -> u64;
+    }
+
+    #[cfg(feature = "big-mod-exp")]
+    #[repr(C)]
+    pub(super) struct BigModExpParams {
+        pub(super) base: *const u8,
+        pub(super) base_len: u64,
+        pub(super) exponent: *const u8,
+        pub(super) exponent_len: u64,
+        pub(super) modulus: *const u8,
+        pub(super) modulus_len: u64,
+    }
+
+    #[cfg(feature = "big-mod-exp")]
+    extern "C" {
+        fn sol_big_mod_exp(params: *const u8, result: *mut u8) -> u64;
+    }
+
+    /// ### Secp256k1 Recover
+    ///
+    /// Calls the `sol_secp256k1_recover` syscall, mapping its error codes onto
+    /// `Secp256k1RecoverError`.
+    #[inline(always)]
+    pub fn secp256k1_recover(
+        hash: &[u8; 32],
+        is_odd: bool,
+        signature: &[u8; 64],
+    ) -> Result<[u8; 64], Secp256k1RecoverError> {
+        let mut out = MaybeUninit::<[u8; 64]>::uninit();
+        match unsafe {
+            sol_secp256k1_recover(hash.as_ptr(), is_odd as u64, signature.as_ptr(), out.as_mut_ptr() as *mut u8)
+        } {
+            0 => Ok(unsafe { out.assume_init() }),
+            1 => Err(Secp256k1RecoverError::HashError),
+            3 => Err(Secp256k1RecoverError::SignatureError),
+            _ => Err(Secp256k1RecoverError::RecoveryError),
+        }
+    }
+
+    /// ### Keccak256
+    ///
+    /// Hashes the concatenation of `vals` with the `sol_keccak256` syscall.
+    #[inline(always)]
+    pub fn keccak256(vals: &[&[u8]]) -> [u8; 32] {
+        let mut out = MaybeUninit::<[u8; 32]>::uninit();
+        unsafe {
+            sol_keccak256(vals.as_ptr() as *const u8, vals.len() as u64, out.as_mut_ptr() as *mut u8);
+            out.assume_init()
+        }
+    }
+
+    /// ### SHA-256
+    ///
+    /// Hashes the concatenation of `vals` with the `sol_sha256` syscall.
+    #[inline(always)]
+    pub fn sha256(vals: &[&[u8]]) -> [u8; 32] {
+        let mut out = MaybeUninit::<[u8; 32]>::uninit();
+        unsafe {
+            sol_sha256(vals.as_ptr() as *const u8, vals.len() as u64, out.as_mut_ptr() as *mut u8);
+            out.assume_init()
+        }
+    }
+
+    /// ### Try Find Program Address
+    ///
+    /// Finds the program derived address and bump seed of `seeds` with the
+    /// `sol_try_find_program_address` syscall, or `None` if no bump gives an off-curve address.
+    #[inline(always)]
+    pub fn try_find_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+        let mut address = MaybeUninit::<[u8; 32]>::uninit();
+        let mut bump = 0u8;
+        match unsafe {
+            sol_try_find_program_address(
+                seeds.as_ptr() as *const u8,
+                seeds.len() as u64,
+                program_id.as_ptr(),
+                address.as_mut_ptr() as *mut u8,
+                &mut bump,
+            )
+        } {
+            0 => Some((unsafe { address.assume_init() }, bump)),
+            _ => None,
+        }
+    }
+
+    /// ### Mul 𝐺
+    ///
+    /// Uses the precomputed 𝑟 of 𝑘=1 (the 𝑋-coordinate of 𝐺) to recover 𝒌·𝐺. Recover only
+    /// fails here when `𝒌 ≡ 0`, so errors are reported as `IdentityPoint`, as natively.
+    pub fn mul_g(k: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+        let s = arithmetic::concat(&Curve::G.x(), &Curve::mul_mod_n(k, &Curve::G.x()));
+        secp256k1_recover(&[0u8; 32], false, &s).map_err(|_| Secp256k1Error::IdentityPoint)
+    }
+
+    /// ### Ecmul
+    ///
+    /// Uses the 𝑋-coordinate and parity of a point as 𝑟 and 𝑣 to recover 𝒌·𝑃. Recover fails
+    /// when `𝒌 ≡ 0` or the 𝑋-coordinate is off curve, reported as `IdentityPoint` and
+    /// `InvalidPublicKey` respectively, as natively.
+    pub fn ecmul(x: &[u8; 32], is_odd: bool, k: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+        let s = arithmetic::concat(x, &Curve::mul_mod_n(x, k));
+        secp256k1_recover(&[0u8; 32], is_odd, &s).map_err(|_| {
+            if arithmetic::reduce(&arithmetic::from_be_bytes(k), &arithmetic::N) == [0u64; 4] {
+                Secp256k1Error::IdentityPoint
+            } else {
+                Secp256k1Error::InvalidPublicKey
+            }
+        })
+    }
+
+    /// ### Tweak
+    ///
+    /// Uses `𝑠 = 𝑟` and `𝑧 = -𝑟·𝒕` to recover `𝑃 + 𝒕·𝐺`, entirely on the stack. Recover fails
+    /// when the 𝑋-coordinate is off curve or the sum is the point at infinity, which only the
+    /// failure path pays a square root to tell apart.
+    pub fn tweak(x: &[u8; 32], is_odd: bool, t: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+        let z = Curve::mul_mod_n(&Curve::negate_n(x), t);
+        secp256k1_recover(&z, is_odd, &arithmetic::concat(x, x)).map_err(|_| {
+            let rhs = arithmetic::curve_rhs(&arithmetic::from_be_bytes(x));
+            match Curve::is_quadratic_residue(&arithmetic::to_be_bytes(&rhs)) {
+                true => Secp256k1Error::IdentityPoint,
+                false => Secp256k1Error::InvalidPublicKey,
+            }
+        })
+    }
+
+    /// ### Double Mul
+    ///
+    /// Uses the 𝑋-coordinate and parity of a point as 𝑟 and 𝑣, with `𝑠 = 𝑟·𝒃` and `𝑧 = -𝑟·𝒂`,
+    /// to recover `𝒂·𝐺 + 𝒃·𝑃` with a single syscall. Recover rejects `𝑠 = 0`, so `𝒃 ≡ 0`
+    /// checks the 𝑋-coordinate and falls back to `mul_g`. Errors are reported as in `tweak`.
+    pub fn double_mul(a: &[u8; 32], x: &[u8; 32], is_odd: bool, b: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+        let on_curve = || Curve::is_quadratic_residue(&arithmetic::to_be_bytes(&arithmetic::curve_rhs(&arithmetic::from_be_bytes(x))));
+        if arithmetic::reduce(&arithmetic::from_be_bytes(b), &arithmetic::N) == [0u64; 4] {
+            if !on_curve() {
+                return Err(Secp256k1Error::InvalidPublicKey);
+            }
+            return mul_g(a);
+        }
+        let z = Curve::negate_n(&Curve::mul_mod_n(x, a));
+        secp256k1_recover(&z, is_odd, &arithmetic::concat(x, &Curve::mul_mod_n(x, b))).map_err(|_| match on_curve() {
+            true => Secp256k1Error::IdentityPoint,
+            false => Secp256k1Error::InvalidPublicKey,
+        })
+    }
+
+    /// ### Big Mod Exp
+    ///
+    /// Calculates `base^exponent mod modulus` with the `sol_big_mod_exp` syscall.
+    #[cfg(feature = "big-mod-exp")]
+    pub fn big_mod_exp(base: &[u8], exponent: &[u8], modulus: &[u8; 32]) -> [u8; 32] {
+        let params = BigModExpParams {
+            base: base.as_ptr(),
+            base_len: base.len() as u64,
+            exponent: exponent.as_ptr(),
+            exponent_len: exponent.len() as u64,
+            modulus: modulus.as_ptr(),
+            modulus_len: 32,
+        };
+        let mut out = [0u8; 32];
+        unsafe {
+            sol_big_mod_exp(&params as *const BigModExpParams as *const u8, out.as_mut_ptr());
+        }
+        out
+    }
+}
+
+#[cfg(all(not(target_os = "solana"), not(feature = "dashu"), not(feature = "crypto-bigint")))]
+compile_error!("native builds need the `dashu` or `crypto-bigint` feature");
+
+#[cfg(not(target_os = "solana"))]
+#[cfg_attr(feature = "test-stubs", allow(dead_code))]
+mod native {
+    #[cfg(feature = "crypto-bigint")]
+    use crypto_bigint::{modular::runtime_mod::{DynResidue, DynResidueParams}, Encoding, NonZero, U256 as BigU256, U512};
+    #[cfg(not(feature = "crypto-bigint"))]
+    use dashu::integer::{fast_div::ConstDivisor, modular::IntoRing, UBig};
+    use k256::{
+        elliptic_curve::{
+            ops::Reduce,
+            sec1::{FromEncodedPoint, ToEncodedPoint},
+            PrimeField,
+        },
+        AffinePoint, EncodedPoint, ProjectivePoint, Scalar, U256,
+    };
+    use sha2::Sha256;
+    use sha3::{Digest, Keccak256};
+    use solana_nostd_secp256k1_recover::Secp256k1RecoverError;
+
+    use crate::{arithmetic, Secp256k1Error};
+
+    const MAX_SEEDS: usize = 16;
+    const MAX_SEED_LEN: usize = 32;
+
+    /// ### Keccak256
+    ///
+    /// Hashes the concatenation of `vals`.
+    pub fn keccak256(vals: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        for val in vals {
+            hasher.update(val);
+        }
+        hasher.finalize().into()
+    }
+
+    /// ### SHA-256
+    ///
+    /// Hashes the concatenation of `vals`.
+    pub fn sha256(vals: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        for val in vals {
+            hasher.update(val);
+        }
+        hasher.finalize().into()
+    }
+
+    /// ### Try Find Program Address
+    ///
+    /// Native implementation of `sol_try_find_program_address`, hashing
+    /// `seeds‖bump‖program_id‖"ProgramDerivedAddress"` from a bump of 255 downwards until the
+    /// address is not a valid ed25519 point.
+    pub fn try_find_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+        // One seed slot is reserved for the bump
+        if seeds.len() >= MAX_SEEDS || seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+            return None;
+        }
+        (0..=255u8).rev().find_map(|bump| {
+            let mut hasher = Sha256::new();
+            for seed in seeds {
+                hasher.update(seed);
+            }
+            hasher.update([bump]);
+            hasher.update(program_id);
+            hasher.update(b"ProgramDerivedAddress");
+            let address: [u8; 32] = hasher.finalize().into();
+            (!is_ed25519_point(&address)).then_some((address, bump))
+        })
+    }
+
+    /// ### Secp256k1 Recover
+    ///
+    /// Native implementation of `sol_secp256k1_recover`, calculating `𝑄 = 𝑟⁻¹(𝑠·𝑅 - 𝑧·𝐺)`
+    /// directly rather than verifying a signature, so it accepts the same high-𝑠 inputs the
+    /// syscall does.
+    pub fn secp256k1_recover(
+        hash: &[u8; 32],
+        is_odd: bool,
+        signature: &[u8; 64],
+    ) -> Result<[u8; 64], Secp256k1RecoverError> {
+        let r = scalar_from_bytes(&signature[..32]).ok_or(Secp256k1RecoverError::SignatureError)?;
+        let s = scalar_from_bytes(&signature[32..]).ok_or(Secp256k1RecoverError::SignatureError)?;
+        let big_r = point_from_x(&signature[..32], is_odd).ok_or(Secp256k1RecoverError::RecoveryError)?;
+        let z = <Scalar as Reduce<U256>>::reduce_bytes(hash.into());
+        let r_inv = Option::<Scalar>::from(r.invert()).ok_or(Secp256k1RecoverError::RecoveryError)?;
+        let q = (big_r * s - ProjectivePoint::GENERATOR * z) * r_inv;
+        to_uncompressed(&q).ok_or(Secp256k1RecoverError::RecoveryError)
+    }
+
+    /// ### Mul 𝐺
+    ///
+    /// Calculates 𝒌·𝐺, reducing 𝒌 modulo 𝑁 first. Errors on the point at infinity.
+    pub fn mul_g(k: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+        #[allow(unused_mut)]
+        let mut k = <Scalar as Reduce<U256>>::reduce_bytes(k.into());
+        let p = to_uncompressed(&(ProjectivePoint::GENERATOR * k));
+        // 𝒌 is usually a secret key, so don't leave a copy of it on the stack
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(&mut k);
+        p.ok_or(Secp256k1Error::IdentityPoint)
+    }
+
+    /// ### Ecmul
+    ///
+    /// Lifts the 𝑋-coordinate with the given parity and calculates 𝒌·𝑃, reducing 𝒌 modulo 𝑁
+    /// first. Errors on an off-curve 𝑋-coordinate or the point at infinity.
+    pub fn ecmul(x: &[u8; 32], is_odd: bool, k: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+        let p = point_from_x(x, is_odd).ok_or(Secp256k1Error::InvalidPublicKey)?;
+        let k = <Scalar as Reduce<U256>>::reduce_bytes(k.into());
+        to_uncompressed(&(p * k)).ok_or(Secp256k1Error::IdentityPoint)
+    }
+
+    /// ### Tweak
+    ///
+    /// Lifts the 𝑋-coordinate with the given parity and calculates `𝑃 + 𝒕·𝐺`, reducing 𝒕
+    /// modulo 𝑁 first. Errors on an off-curve 𝑋-coordinate or the point at infinity.
+    pub fn tweak(x: &[u8; 32], is_odd: bool, t: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+        let p = point_from_x(x, is_odd).ok_or(Secp256k1Error::InvalidPublicKey)?;
+        let t = <Scalar as Reduce<U256>>::reduce_bytes(t.into());
+        to_uncompressed(&(p + ProjectivePoint::GENERATOR * t)).ok_or(Secp256k1Error::IdentityPoint)
+    }
+
+    /// ### Double Mul
+    ///
+    /// Lifts the 𝑋-coordinate with the given parity and calculates `𝒂·𝐺 + 𝒃·𝑃`, reducing 𝒂 and
+    /// 𝒃 modulo 𝑁 first. Errors on an off-curve 𝑋-coordinate or the point at infinity.
+    pub fn double_mul(a: &[u8; 32], x: &[u8; 32], is_odd: bool, b: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+        let p = point_from_x(x, is_odd).ok_or(Secp256k1Error::InvalidPublicKey)?;
+        let a = <Scalar as Reduce<U256>>::reduce_bytes(a.into());
+        let b = <Scalar as Reduce<U256>>::reduce_bytes(b.into());
+        to_uncompressed(&(ProjectivePoint::GENERATOR * a + p * b)).ok_or(Secp256k1Error::IdentityPoint)
+    }
+
+    /// ### Big Mod Exp
+    ///
+    /// Calculates `base^exponent mod modulus`. Moduli used by this crate reuse their
+    /// compile-time limb contexts, while any other modulus builds a `dashu` ring, or a
+    /// `crypto-bigint` Montgomery context, per call.
+    pub fn big_mod_exp(base: &[u8], exponent: &[u8], modulus: &[u8; 32]) -> [u8; 32] {
+        if let (Some(m), Ok(e)) = (arithmetic::modulus(modulus), <&[u8; 32]>::try_from(exponent)) {
+            let base = arithmetic::from_be_slice(base, m);
+            return arithmetic::to_be_bytes(&arithmetic::pow_mod(&base, &arithmetic::from_be_bytes(e), m));
+        }
+        mod_exp(base, exponent, modulus)
+    }
+
+    #[cfg(not(feature = "crypto-bigint"))]
+    fn mod_exp(base: &[u8], exponent: &[u8], modulus: &[u8; 32]) -> [u8; 32] {
+        let ring = ConstDivisor::new(UBig::from_be_bytes(modulus));
+        let res = UBig::from_be_bytes(base)
+            .into_ring(&ring)
+            .pow(&UBig::from_be_bytes(exponent))
+            .residue()
+            .to_be_bytes();
+        let mut out = [0u8; 32];
+        out[32 - res.len()..].clone_from_slice(&res);
+        out
+    }
+
+    /// Calculates `base^exponent mod modulus` over `crypto-bigint`, returning zero for a zero
+    /// modulus as the runtime does. Odd moduli get a Montgomery context, while even moduli fall
+    /// back to square-and-multiply with a wide remainder.
+    #[cfg(feature = "crypto-bigint")]
+    fn mod_exp(base: &[u8], exponent: &[u8], modulus: &[u8; 32]) -> [u8; 32] {
+        let Some(m) = Option::<NonZero<U512>>::from(NonZero::new(BigU256::from_be_slice(modulus).resize())) else {
+            return [0u8; 32];
+        };
+        let base = reduce_be(base, &m);
+        let bits = exponent.iter().flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1));
+        let r = if modulus[31] & 1 == 1 {
+            let params = DynResidueParams::new(&m.resize());
+            let base = DynResidue::new(&base, params);
+            bits.fold(DynResidue::one(params), |r, bit| if bit { r.square() * base } else { r.square() }).retrieve()
+        } else {
+            let mul = |a: &BigU256, b: &BigU256| U512::from(a.mul_wide(b)).rem(&m).resize();
+            bits.fold(U512::ONE.rem(&m).resize(), |r, bit| if bit { mul(&mul(&r, &r), &base) } else { mul(&r, &r) })
+        };
+        r.to_be_bytes()
+    }
+
+    /// Reduces a big-endian integer of any length modulo `m`, a byte at a time.
+    #[cfg(feature = "crypto-bigint")]
+    fn reduce_be(bytes: &[u8], m: &NonZero<U512>) -> BigU256 {
+        bytes
+            .iter()
+            .fold(BigU256::ZERO, |r, byte| r.resize::<8>().shl_vartime(8).wrapping_add(&U512::from_u8(*byte)).rem(m).resize())
+    }
+
+    /// Checks whether `bytes` decompress to an ed25519 point, ie: whether `𝑥² = (𝑦² - 1)/(𝑑𝑦² + 1)`
+    /// has a solution modulo `2²⁵⁵ - 19`, accepting non-canonical `𝑦` like the runtime does.
+    #[cfg(not(feature = "crypto-bigint"))]
+    fn is_ed25519_point(bytes: &[u8; 32]) -> bool {
+        let ring = ConstDivisor::new((UBig::ONE << 255) - UBig::from(19u8));
+        let mut y = *bytes;
+        y[31] &= 0x7f;
+        let y2 = UBig::from_le_bytes(&y).into_ring(&ring).pow(&UBig::from(2u8));
+        let one = UBig::ONE.into_ring(&ring);
+        // 𝑑 = -121665/121666
+        let d = -UBig::from(121665u32).into_ring(&ring) / UBig::from(121666u32).into_ring(&ring);
+        let u = y2.clone() - one.clone();
+        let v = d * y2 + one.clone();
+        let Some(v_inv) = v.inv() else {
+            return false;
+        };
+        let x2 = u * v_inv;
+        // Euler's criterion: 𝑥² is a square iff 𝑥²^((𝑝-1)/2) is 0 or 1
+        let legendre = x2.pow(&((ring.value() - UBig::ONE) >> 1));
+        legendre == one || legendre.residue() == UBig::ZERO
+    }
+
+    /// Checks whether `bytes` decompress to an ed25519 point, as above, over `crypto-bigint`.
+    #[cfg(feature = "crypto-bigint")]
+    fn is_ed25519_point(bytes: &[u8; 32]) -> bool {
+        const P: BigU256 = BigU256::from_be_hex("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed");
+        let params = DynResidueParams::new(&P);
+        let mut y = *bytes;
+        y[31] &= 0x7f;
+        y.reverse();
+        let y = DynResidue::new(&reduce_be(&y, &NonZero::<U512>::from_uint(P.resize())), params);
+        let y2 = y.square();
+        let one = DynResidue::one(params);
+        // 𝑑 = -121665/121666
+        let (inv_121666, _) = DynResidue::new(&BigU256::from_u32(121666), params).invert();
+        let d = -(DynResidue::new(&BigU256::from_u32(121665), params) * inv_121666);
+        let u = y2 - one;
+        let (v_inv, is_some) = (d * y2 + one).invert();
+        if !bool::from(is_some) {
+            return false;
+        }
+        let x2 = u * v_inv;
+        // Euler's criterion: 𝑥² is a square iff 𝑥²^((𝑝-1)/2) is 0 or 1
+        let legendre = x2.pow(&P.wrapping_sub(&BigU256::ONE).shr_vartime(1));
+        legendre == one || legendre.retrieve() == BigU256::ZERO
+    }
+
+    fn scalar_from_bytes(bytes: &[u8]) -> Option<Scalar> {
+        let bytes: [u8; 32] = bytes.try_into().ok()?;
+        let s = Option::<Scalar>::from(Scalar::from_repr(bytes.into()))?;
+        (!bool::from(s.is_zero())).then_some(s)
+    }
+
+    fn point_from_x(x: &[u8], is_odd: bool) -> Option<ProjectivePoint> {
+        let mut compressed = [0u8; 33];
+        compressed[0] = is_odd as u8 + 2;
+        compressed[1..].clone_from_slice(x);
+        let encoded = EncodedPoint::from_bytes(compressed).ok()?;
+        Option::<AffinePoint>::from(AffinePoint::from_encoded_point(&encoded)).map(ProjectivePoint::from)
+    }
+
+    fn to_uncompressed(p: &ProjectivePoint) -> Option<[u8; 64]> {
+        let encoded = p.to_affine().to_encoded_point(false);
+        let mut out = [0u8; 64];
+        out.clone_from_slice(encoded.as_bytes().get(1..65)?);
+        Some(out)
+    }
+}
+
+#[cfg(all(not(target_os = "solana"), feature = "test-stubs"))]
+mod stubs {
+    use solana_nostd_secp256k1_recover::Secp256k1RecoverError;
+
+    use super::native;
+
+    /// ### Secp256k1 Recover Stub
+    ///
+    /// Host implementation of the `sol_secp256k1_recover` syscall, returning the same error
+    /// codes as the runtime.
+    ///
+    /// # Safety
+    ///
+    /// `hash` and `result` must point to 32 and 64 valid bytes respectively, and `signature`
+    /// to 64 readable bytes.
+    #[no_mangle]
+    pub unsafe extern "C" fn sol_secp256k1_recover(
+        hash: *const u8,
+        recovery_id: u64,
+        signature: *const u8,
+        result: *mut u8,
+    ) -> u64 {
+        if recovery_id > 1 {
+            return 2;
+        }
+        let hash = &*(hash as *const [u8; 32]);
+        let signature = &*(signature as *const [u8; 64]);
+        match native::secp256k1_recover(hash, recovery_id == 1, signature) {
+            Ok(q) => {
+                core::ptr::copy_nonoverlapping(q.as_ptr(), result, 64);
+                0
+            }
+            Err(Secp256k1RecoverError::HashError) => 1,
+            // The runtime reports failed recoveries as invalid signatures
+            Err(Secp256k1RecoverError::SignatureError | Secp256k1RecoverError::RecoveryError) => 3,
+        }
+    }
+
+    /// ### Keccak256 Stub
+    ///
+    /// Host implementation of the `sol_keccak256` syscall.
+    ///
+    /// # Safety
+    ///
+    /// `vals` must point to `val_len` readable byte slices, and `hash_result` to 32 writable
+    /// bytes.
+    #[no_mangle]
+    pub unsafe extern "C" fn sol_keccak256(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64 {
+        let vals = core::slice::from_raw_parts(vals as *const &[u8], val_len as usize);
+        let out = native::keccak256(vals);
+        core::ptr::copy_nonoverlapping(out.as_ptr(), hash_result, 32);
+        0
+    }
+
+    /// ### SHA-256 Stub
+    ///
+    /// Host implementation of the `sol_sha256` syscall.
+    ///
+    /// # Safety
+    ///
+    /// `vals` must point to `val_len` readable byte slices, and `hash_result` to 32 writable
+    /// bytes.
+    #[no_mangle]
+    pub unsafe extern "C" fn sol_sha256(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64 {
+        let vals = core::slice::from_raw_parts(vals as *const &[u8], val_len as usize);
+        let out = native::sha256(vals);
+        core::ptr::copy_nonoverlapping(out.as_ptr(), hash_result, 32);
+        0
+    }
+
+    /// ### Big Mod Exp Stub
+    ///
+    /// Host implementation of the `sol_big_mod_exp` syscall for 32-byte moduli.
+    ///
+    /// # Safety
+    ///
+    /// `params` must point to a valid `BigModExpParams` whose buffers are readable for their
+    /// stated lengths, and `result` must point to 32 writable bytes.
+    #[cfg(feature = "big-mod-exp")]
+    #[no_mangle]
+    pub unsafe extern "C" fn sol_big_mod_exp(params: *const u8, result: *mut u8) -> u64 {
+        let params = &*(params as *const super::syscall::BigModExpParams);
+        if params.modulus_len != 32 {
+            return 1;
+        }
+        let base = core::slice::from_raw_parts(params.base, params.base_len as usize);
+        let exponent = core::slice::from_raw_parts(params.exponent, params.exponent_len as usize);
+        let modulus = &*(params.modulus as *const [u8; 32]);
+        let out = native::big_mod_exp(base, exponent, modulus);
+        core::ptr::copy_nonoverlapping(out.as_ptr(), result, 32);
+        0
+    }
+
+    /// ### Try Find Program Address Stub
+    ///
+    /// Host implementation of the `sol_try_find_program_address` syscall.
+    ///
+    /// # Safety
+    ///
+    /// `seeds` must point to `seeds_len` readable byte slices, `program_id` to 32 readable
+    /// bytes, and `address` and `bump_seed` to 32 and 1 writable bytes respectively.
+    #[no_mangle]
+    pub unsafe extern "C" fn sol_try_find_program_address(
+        seeds: *const u8,
+        seeds_len: u64,
+        program_id: *const u8,
+        address: *mut u8,
+        bump_seed: *mut u8,
+    ) -> u64 {
+        let seeds = core::slice::from_raw_parts(seeds as *const &[u8], seeds_len as usize);
+        let program_id = &*(program_id as *const [u8; 32]);
+        match native::try_find_program_address(seeds, program_id) {
+            Some((pda, bump)) => {
+                core::ptr::copy_nonoverlapping(pda.as_ptr(), address, 32);
+                *bump_seed = bump;
+                0
+            }
+            None => 1,
+        }
+    }
+}
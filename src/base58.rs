@@ -0,0 +1,67 @@
+//! # Base58
+//!
+//! Allocation-free Bitcoin alphabet base58, shared by base58check Bitcoin addresses and WIF
+//! keys and base58btc multibase `did:key` identifiers. Conversion is quadratic in the input
+//! length, which is fine for the short keys and addresses it is used for.
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// ### Encode
+///
+/// Base58 encodes `data` into the start of `out`, returning the number of characters written.
+/// Panics if `out` is too short, so callers size it for their longest input.
+pub(crate) fn encode(data: &[u8], out: &mut [u8]) -> usize {
+    // Repeatedly multiply the little-endian base58 digits by 256 and add each byte
+    let mut len = 0;
+    for &byte in data {
+        let mut carry = byte as u32;
+        for digit in &mut out[..len] {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            out[len] = (carry % 58) as u8;
+            len += 1;
+            carry /= 58;
+        }
+    }
+    // Each leading zero byte is a leading `1`
+    for _ in data.iter().take_while(|b| **b == 0) {
+        out[len] = 0;
+        len += 1;
+    }
+    out[..len].reverse();
+    for digit in &mut out[..len] {
+        *digit = ALPHABET[*digit as usize];
+    }
+    len
+}
+
+/// ### Decode
+///
+/// Base58 decodes `s` into the start of `buf`, returning the bytes written. Returns `None` on
+/// a character outside the alphabet or if `buf` is too short.
+pub(crate) fn decode<'a>(s: &[u8], buf: &'a mut [u8]) -> Option<&'a [u8]> {
+    // Repeatedly multiply the little-endian bytes by 58 and add each digit
+    let mut len = 0;
+    for c in s {
+        let mut carry = ALPHABET.iter().position(|d| d == c)? as u32;
+        for byte in &mut buf[..len] {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            *buf.get_mut(len)? = carry as u8;
+            len += 1;
+            carry >>= 8;
+        }
+    }
+    for _ in s.iter().take_while(|c| **c == b'1') {
+        *buf.get_mut(len)? = 0;
+        len += 1;
+    }
+    buf[..len].reverse();
+    Some(&buf[..len])
+}
@@ -0,0 +1,120 @@
+//! # Base64
+//!
+//! Allocation-free base64 encoding into caller-provided buffers and strict decoding, in both the
+//! standard alphabet with `=` padding, as in Bitcoin signed messages, and the unpadded URL-safe
+//! alphabet of JWTs and WebAuthn. Decoding rejects non-canonical input, such as missing padding
+//! or nonzero trailing bits, so each byte string has exactly one accepted encoding.
+
+use crate::Secp256k1Error;
+
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// ### Encoded Len
+///
+/// The length of the base64 encoding of `len` bytes, with or without padding.
+pub const fn encoded_len(len: usize, padded: bool) -> usize {
+    if padded {
+        len.div_ceil(3) * 4
+    } else {
+        (len * 4).div_ceil(3)
+    }
+}
+
+fn encode_with<'a>(bytes: &[u8], out: &'a mut [u8], alphabet: &[u8; 64], padded: bool) -> Result<&'a str, Secp256k1Error> {
+    if out.len() != encoded_len(bytes.len(), padded) {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    for (chunk, out) in bytes.chunks(3).zip(out.chunks_mut(4)) {
+        let mut group = [0u8; 4];
+        group[1..1 + chunk.len()].copy_from_slice(chunk);
+        let group = u32::from_be_bytes(group);
+        for (i, c) in out.iter_mut().enumerate() {
+            *c = if i <= chunk.len() { alphabet[(group >> (18 - 6 * i) & 0x3f) as usize] } else { b'=' };
+        }
+    }
+    Ok(core::str::from_utf8(out).expect("ASCII base64"))
+}
+
+fn decode_with(input: &str, out: &mut [u8], alphabet: &[u8; 64], padded: bool) -> Result<usize, Secp256k1Error> {
+    let mut input = input.as_bytes();
+    if padded {
+        if !input.len().is_multiple_of(4) {
+            return Err(Secp256k1Error::InvalidPayload);
+        }
+        let padding = input.iter().rev().take(2).take_while(|c| **c == b'=').count();
+        input = &input[..input.len() - padding];
+    }
+    let len = input.len() * 3 / 4;
+    if input.len() % 4 == 1 || len > out.len() {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    let value = |c: u8| alphabet.iter().position(|a| *a == c).map(|v| v as u32).ok_or(Secp256k1Error::InvalidPayload);
+    for (chunk, out) in input.chunks(4).zip(out.chunks_mut(3)) {
+        let mut group = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            group |= value(*c)? << (18 - 6 * i);
+        }
+        let n = chunk.len() - 1;
+        // The bits past the last whole byte must be zero
+        if group & (0x00ff_ffff >> (8 * n)) != 0 {
+            return Err(Secp256k1Error::InvalidPayload);
+        }
+        out[..n].copy_from_slice(&group.to_be_bytes()[1..1 + n]);
+    }
+    Ok(len)
+}
+
+/// ### Encode To Slice
+///
+/// Encodes `bytes` as padded standard base64 into `out`, returning it as a `str`. Errors with
+/// `InvalidPayload` unless `out` is exactly `encoded_len(bytes.len(), true)` long.
+pub fn encode_to_slice<'a>(bytes: &[u8], out: &'a mut [u8]) -> Result<&'a str, Secp256k1Error> {
+    encode_with(bytes, out, STANDARD, true)
+}
+
+/// ### Encode URL Safe To Slice
+///
+/// As `encode_to_slice`, in unpadded URL-safe base64, with `out` exactly
+/// `encoded_len(bytes.len(), false)` long.
+pub fn encode_url_safe_to_slice<'a>(bytes: &[u8], out: &'a mut [u8]) -> Result<&'a str, Secp256k1Error> {
+    encode_with(bytes, out, URL_SAFE, false)
+}
+
+/// ### Decode To Slice
+///
+/// Decodes padded standard base64 into the start of `out`, returning the number of bytes
+/// written. Errors with `InvalidPayload` if the input is not canonical base64 or `out` is too
+/// short.
+pub fn decode_to_slice(base64: &str, out: &mut [u8]) -> Result<usize, Secp256k1Error> {
+    decode_with(base64, out, STANDARD, true)
+}
+
+/// ### Decode URL Safe To Slice
+///
+/// As `decode_to_slice`, for unpadded URL-safe base64.
+pub fn decode_url_safe_to_slice(base64: &str, out: &mut [u8]) -> Result<usize, Secp256k1Error> {
+    decode_with(base64, out, URL_SAFE, false)
+}
+
+/// ### Decode
+///
+/// Decodes exactly `N` bytes of padded standard base64.
+pub fn decode<const N: usize>(base64: &str) -> Result<[u8; N], Secp256k1Error> {
+    let mut out = [0u8; N];
+    match decode_to_slice(base64, &mut out)? {
+        len if len == N => Ok(out),
+        _ => Err(Secp256k1Error::InvalidPayload),
+    }
+}
+
+/// ### Decode URL Safe
+///
+/// Decodes exactly `N` bytes of unpadded URL-safe base64.
+pub fn decode_url_safe<const N: usize>(base64: &str) -> Result<[u8; N], Secp256k1Error> {
+    let mut out = [0u8; N];
+    match decode_url_safe_to_slice(base64, &mut out)? {
+        len if len == N => Ok(out),
+        _ => Err(Secp256k1Error::InvalidPayload),
+    }
+}
@@ -0,0 +1,30 @@
+//! # BIP-32
+//!
+//! BIP-32 master key generation, bridging BIP-39 mnemonic seeds and the crate's derivation
+//! APIs. The master secret key and chain code are the two halves of `HMAC-SHA512("Bitcoin
+//! seed", seed)`. Child keys are tweaks of their parent, so `SecretKey::tweak_add` and
+//! `Secp256k1Point::tweak` pick up from the master key.
+
+use crate::{sha512::hmac_sha512, SecretKey};
+
+/// ### Chain Code
+///
+/// The 32 bytes of entropy extended keys carry alongside their key to derive children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainCode(pub [u8; 32]);
+
+/// ### Master Key From Seed
+///
+/// Derives the master secret key and chain code of a seed, usually the 64-byte output of
+/// BIP-39. BIP-32 leaves seeds whose left half is zero or `≥ 𝑁` invalid, which happens with
+/// probability below 2⁻¹²⁷, so as in SLIP-10 the HMAC is retried over its own output instead.
+pub fn master_key_from_seed(seed: &[u8]) -> (SecretKey, ChainCode) {
+    let mut i = hmac_sha512(b"Bitcoin seed", &[seed]);
+    loop {
+        let (key, chain_code) = i.split_at(32);
+        if let Ok(key) = SecretKey::from_bytes(key.try_into().expect("32 bytes")) {
+            return (key, ChainCode(chain_code.try_into().expect("32 bytes")));
+        }
+        i = hmac_sha512(b"Bitcoin seed", &[&i]);
+    }
+}
@@ -0,0 +1,141 @@
+//! # BIP-340
+//!
+//! BIP-340 Schnorr signing, verification with a single recover, and the nonce function, so
+//! external signers can produce compatible nonces and tests can reproduce the reference
+//! vectors. Hashes go through `sol_sha256` on-chain.
+
+use crate::{
+    arithmetic, backend,
+    challenge::{ChallengeHasher, TaggedSha256},
+    Curve, Message, Secp256k1Error, Secp256k1Point, SecretKey, UncompressedPoint,
+};
+
+/// ### Tagged Hash
+///
+/// Calculates `SHA-256(SHA-256(tag) ‖ SHA-256(tag) ‖ data)`, the domain-separated hash used
+/// throughout BIP-340 and BIP-341. `data` is hashed as the concatenation of at most 6 slices,
+/// which is checked at compile time.
+pub fn tagged_hash<const M: usize>(tag: &[u8], data: &[&[u8]; M]) -> [u8; 32] {
+    TaggedSha256::new(tag).hash(data)
+}
+
+/// ### Nonce
+///
+/// Derives the BIP-340 nonce `𝑘′ = tagged_hash("BIP0340/nonce", 𝑡 ‖ 𝑃ₓ ‖ 𝑚) mod 𝑁`, where
+/// `𝑡 = 𝒅 ⊕ tagged_hash("BIP0340/aux", 𝑎)` masks the secret key, negated for an even 𝑌, with
+/// the auxiliary randomness `aux_rand`. The signer uses `𝑘′` if `𝑘′·𝐺` has an even
+/// 𝑌-coordinate and `𝑁 - 𝑘′` otherwise. Errors with `ZeroScalar` in the negligible case that
+/// `𝑘′` is zero.
+pub fn nonce(key: &SecretKey, message: &[u8], aux_rand: &[u8; 32]) -> Result<[u8; 32], Secp256k1Error> {
+    let (pubkey, parity) = key.public_key()?.to_even_y();
+    let d = Curve::conditional_negate_n(key.as_bytes(), parity.is_odd());
+    let mut t = tagged_hash(b"BIP0340/aux", &[aux_rand]);
+    for (t, d) in t.iter_mut().zip(d) {
+        *t ^= d;
+    }
+    let k = Curve::add_mod_n(&tagged_hash(b"BIP0340/nonce", &[&t, pubkey.x_ref(), message]), &[0u8; 32]);
+    if k == [0u8; 32] {
+        return Err(Secp256k1Error::ZeroScalar);
+    }
+    Ok(k)
+}
+
+/// ### Signature
+///
+/// A BIP-340 signature `𝑟‖𝑠` as its own type, for converting to and from the Schnorr signature
+/// types of other libraries. `sign` and `verify` take the bytes directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Signature(pub [u8; 64]);
+
+impl From<[u8; 64]> for Signature {
+    fn from(bytes: [u8; 64]) -> Self {
+        Signature(bytes)
+    }
+}
+
+impl From<Signature> for [u8; 64] {
+    fn from(signature: Signature) -> Self {
+        signature.0
+    }
+}
+
+/// ### Sign
+///
+/// Signs `message` with BIP-340 Schnorr for the even-𝑌 public key of `key`, using the nonce
+/// from `nonce` with the auxiliary randomness `aux_rand`, and returns `𝑟‖𝑠` where `𝑟 = 𝑅ₓ` and
+/// `𝑠 = 𝑘 + 𝑒·𝒅 mod 𝑁`.
+pub fn sign(key: &SecretKey, message: &[u8], aux_rand: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+    sign_with_nonce(key, &nonce(key, message, aux_rand)?, message)
+}
+
+/// ### Sign Message
+///
+/// As `sign`, over a hashed `Message`, eg: `Message::tagged` with an application's own tag.
+pub fn sign_message(key: &SecretKey, message: &Message, aux_rand: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+    sign(key, message.as_bytes(), aux_rand)
+}
+
+/// Signs `message` with the given non-zero nonce `𝑘`, negated if `𝑘·𝐺` has an odd 𝑌. Reusing a
+/// nonce for two messages leaks the key, so only protocols that rely on that, such as DLC
+/// oracles and EOTS, pick their own.
+pub(crate) fn sign_with_nonce(key: &SecretKey, k: &[u8; 32], message: &[u8]) -> Result<[u8; 64], Secp256k1Error> {
+    let (pubkey, parity) = key.public_key()?.to_even_y();
+    let d = Curve::conditional_negate_n(key.as_bytes(), parity.is_odd());
+    let (big_r, parity) = Curve::mul_g_unchecked(k)?.to_even_y();
+    let k = Curve::conditional_negate_n(k, parity.is_odd());
+    let e = challenge(big_r.x_ref(), pubkey.x_ref(), message);
+    Ok(arithmetic::concat(big_r.x_ref(), &Curve::add_mod_n(&k, &Curve::mul_mod_n(&e, &d))))
+}
+
+/// The BIP-340 challenge `𝑒 = tagged_hash("BIP0340/challenge", 𝑟 ‖ 𝑃ₓ ‖ 𝑚) mod 𝑁`.
+pub(crate) fn challenge(r: &[u8; 32], pubkey_x: &[u8; 32], message: &[u8]) -> [u8; 32] {
+    TaggedSha256::new(b"BIP0340/challenge").challenge(&[r, pubkey_x, message])
+}
+
+/// ### Verify
+///
+/// Verifies a BIP-340 signature `𝑟‖𝑠` over `message` for the even-𝑌 public key with the
+/// 𝑋-coordinate `pubkey_x`, erroring with `InvalidSignature` on failure.
+///
+/// `𝑅 = 𝑠·𝐺 - 𝑒·𝑃` is calculated with a single recover by `double_mul`, and must have an
+/// even 𝑌-coordinate and an 𝑋-coordinate of 𝑟. The recover takes `𝑃ₓ` as its 𝑟, so keys with
+/// `𝑃ₓ ≥ 𝑁`, roughly a 2⁻¹²⁸ fraction of them, are rejected on every target.
+pub fn verify(pubkey_x: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> Result<(), Secp256k1Error> {
+    verify_with(TaggedSha256::new(b"BIP0340/challenge"), pubkey_x, message, signature)
+}
+
+/// ### Verify Message
+///
+/// As `verify`, over a hashed `Message`.
+pub fn verify_message(pubkey_x: &[u8; 32], message: &Message, signature: &[u8; 64]) -> Result<(), Secp256k1Error> {
+    verify(pubkey_x, message.as_bytes(), signature)
+}
+
+/// ### Verify With
+///
+/// Verifies a Schnorr signature as `verify` does, with the challenge `𝑒 = H(𝑟 ‖ 𝑃ₓ ‖ 𝑚) mod 𝑁`
+/// derived by `hasher` instead of the BIP-340 tagged hash, eg: `challenge::Keccak256` for
+/// EVM-flavored Schnorr.
+pub fn verify_with<H: ChallengeHasher>(
+    hasher: H,
+    pubkey_x: &[u8; 32],
+    message: &[u8],
+    signature: &[u8; 64],
+) -> Result<(), Secp256k1Error> {
+    let (r, s) = arithmetic::split(signature);
+    if r >= Curve::P || s >= Curve::N || *pubkey_x >= Curve::N {
+        return Err(Secp256k1Error::InvalidSignature);
+    }
+
+    // 𝑒 = H(𝑟 ‖ 𝑃ₓ ‖ 𝑚) mod 𝑁
+    let e = hasher.challenge(&[&r, pubkey_x, message]);
+    let big_r = UncompressedPoint(
+        backend::double_mul(&s, pubkey_x, false, &Curve::negate_n(&e)).map_err(|_| Secp256k1Error::InvalidSignature)?,
+    );
+
+    if big_r.is_odd() || *big_r.x_ref() != r {
+        return Err(Secp256k1Error::InvalidSignature);
+    }
+    Ok(())
+}
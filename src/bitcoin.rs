@@ -0,0 +1,252 @@
+//! # Bitcoin
+//!
+//! Verification of Bitcoin ECDSA signatures as they appear in `scriptSig` and witness data,
+//! for SPV and bridge programs checking each input of a transaction. Signatures are strict DER
+//! encoded per BIP-66, followed by a single sighash type byte.
+//!
+//! Addresses and WIF secret keys are parameterized by a `Network`, so Bitcoin testnet and
+//! Bitcoin-family chains such as Litecoin and Dogecoin share the same code.
+
+use core::fmt::{Debug, Display, Formatter};
+
+use crate::{backend, base58, base64, der, eth::RecoverableSignature, ripemd160::ripemd160, CompressedPoint, Curve, Secp256k1Error, SecretKey};
+
+pub const SIGHASH_ALL: u8 = 0x01;
+pub const SIGHASH_NONE: u8 = 0x02;
+pub const SIGHASH_SINGLE: u8 = 0x03;
+pub const SIGHASH_ANYONECANPAY: u8 = 0x80;
+
+/// ### Verify ECDSA
+///
+/// Verifies a DER signature with its trailing sighash type byte against `pubkey` and the
+/// `sighash` digest, returning the sighash type so the caller can check it matches the digest
+/// it computed.
+///
+/// The encoding must be strict DER as in BIP-66, the sighash type must be one of the defined
+/// types, and high-𝑠 signatures are rejected as in Bitcoin Core's standardness rules.
+pub fn verify_ecdsa(signature: &[u8], pubkey: &CompressedPoint, sighash: &[u8; 32]) -> Result<u8, Secp256k1Error> {
+    let (sighash_type, der) = signature.split_last().ok_or(Secp256k1Error::InvalidSignatureEncoding)?;
+    if !matches!(sighash_type & !SIGHASH_ANYONECANPAY, SIGHASH_ALL..=SIGHASH_SINGLE) {
+        return Err(Secp256k1Error::InvalidSignatureEncoding);
+    }
+    Curve::verify_ecdsa(sighash, &parse_der(der)?, pubkey)?;
+    Ok(*sighash_type)
+}
+
+/// ### Parse DER
+///
+/// Parses a strict DER `SEQUENCE { INTEGER 𝑟, INTEGER 𝑠 }` without a sighash type byte into a
+/// 64-byte `𝑟‖𝑠` signature, applying the BIP-66 rules: minimal lengths, no negative integers
+/// and no unnecessary leading zeroes. This is `der::parse_signature`, whose strict DER is
+/// exactly BIP-66.
+pub fn parse_der(der: &[u8]) -> Result<[u8; 64], Secp256k1Error> {
+    der::parse_signature(der)
+}
+
+/// ### Network
+///
+/// The version bytes and prefixes a Bitcoin-family network uses to encode addresses and secret
+/// keys. Networks without segwit have no bech32 human-readable part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Network {
+    pub p2pkh_version: u8,
+    pub p2sh_version: u8,
+    pub bech32_hrp: Option<&'static str>,
+    pub wif_prefix: u8,
+}
+
+impl Network {
+    pub const BITCOIN: Network = Network { p2pkh_version: 0x00, p2sh_version: 0x05, bech32_hrp: Some("bc"), wif_prefix: 0x80 };
+    pub const TESTNET: Network = Network { p2pkh_version: 0x6f, p2sh_version: 0xc4, bech32_hrp: Some("tb"), wif_prefix: 0xef };
+    pub const LITECOIN: Network = Network { p2pkh_version: 0x30, p2sh_version: 0x32, bech32_hrp: Some("ltc"), wif_prefix: 0xb0 };
+    pub const DOGECOIN: Network = Network { p2pkh_version: 0x1e, p2sh_version: 0x16, bech32_hrp: None, wif_prefix: 0x9e };
+}
+
+/// ### Encoded
+///
+/// A base58check or bech32 string, such as an address or WIF secret key, held on the stack.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Encoded {
+    bytes: [u8; 90],
+    len: usize,
+}
+
+impl Encoded {
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).expect("ASCII encoding")
+    }
+
+    fn push(&mut self, c: u8) {
+        self.bytes[self.len] = c;
+        self.len += 1;
+    }
+}
+
+impl Display for Encoded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Debug for Encoded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// ### Parse Message Signature
+///
+/// Parses a base64 BIP-137 signed message signature, `header ‖ 𝑟 ‖ 𝑠` with `header = 27 +
+/// recovery id`, plus 4 for a compressed P2PKH, 8 for a P2SH-P2WPKH or 12 for a P2WPKH
+/// signer, returning the signature and whether the signer's key is compressed. Errors with
+/// `InvalidSignatureEncoding` on malformed base64 or an unknown header.
+pub fn parse_message_signature(signature: &str) -> Result<(RecoverableSignature, bool), Secp256k1Error> {
+    let bytes: [u8; 65] = base64::decode(signature).map_err(|_| Secp256k1Error::InvalidSignatureEncoding)?;
+    let header = match bytes[0] {
+        27..=42 => bytes[0] - 27,
+        _ => return Err(Secp256k1Error::InvalidSignatureEncoding),
+    };
+    let mut r = [0u8; 32];
+    let mut s = [0u8; 32];
+    r.copy_from_slice(&bytes[1..33]);
+    s.copy_from_slice(&bytes[33..]);
+    Ok((RecoverableSignature { r, s, recovery_id: header & 3 }, header >= 4))
+}
+
+/// ### Encode Message Signature
+///
+/// Encodes a signature as a base64 BIP-137 signed message signature for a P2PKH signer with a
+/// compressed or uncompressed key.
+pub fn encode_message_signature<'a>(signature: &RecoverableSignature, compressed: bool, out: &'a mut [u8; 88]) -> &'a str {
+    let mut bytes = [0u8; 65];
+    bytes[0] = 27 + signature.recovery_id + if compressed { 4 } else { 0 };
+    bytes[1..33].copy_from_slice(&signature.r);
+    bytes[33..].copy_from_slice(&signature.s);
+    base64::encode_to_slice(&bytes, out).expect("88 base64 characters")
+}
+
+/// ### Hash160
+///
+/// Calculates `RIPEMD-160(SHA-256(data))`, the hash behind P2PKH and P2WPKH addresses.
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    ripemd160(&backend::sha256(&[data]))
+}
+
+/// ### P2PKH Address
+///
+/// Encodes the legacy pay-to-pubkey-hash address of a compressed public key.
+pub fn p2pkh_address(pubkey: &CompressedPoint, network: &Network) -> Encoded {
+    let mut payload = [network.p2pkh_version; 21];
+    payload[1..].copy_from_slice(&hash160(&pubkey.0));
+    base58check(&payload)
+}
+
+/// ### P2WPKH Address
+///
+/// Encodes the segwit v0 pay-to-witness-pubkey-hash address of a compressed public key, or
+/// `None` if the network has no segwit.
+pub fn p2wpkh_address(pubkey: &CompressedPoint, network: &Network) -> Option<Encoded> {
+    Some(bech32_v0(network.bech32_hrp?, &hash160(&pubkey.0)))
+}
+
+/// ### Encode WIF
+///
+/// Encodes a secret key in wallet import format, flagged as having a compressed public key.
+pub fn encode_wif(key: &SecretKey, network: &Network) -> Encoded {
+    let mut payload = [network.wif_prefix; 34];
+    payload[1..33].copy_from_slice(key.as_bytes());
+    payload[33] = 0x01;
+    base58check(&payload)
+}
+
+/// ### Decode WIF
+///
+/// Decodes a secret key in wallet import format for `network`, returning the key and whether
+/// it is flagged as having a compressed public key. Errors with `InvalidSecretKey` on a bad
+/// encoding, checksum, prefix or scalar.
+pub fn decode_wif(wif: &str, network: &Network) -> Result<(SecretKey, bool), Secp256k1Error> {
+    let mut buf = [0u8; 38];
+    let payload = base58check_decode(wif.as_bytes(), &mut buf).ok_or(Secp256k1Error::InvalidSecretKey)?;
+    let (compressed, key) = match payload {
+        [prefix, key @ .., 0x01] if key.len() == 32 && *prefix == network.wif_prefix => (true, key),
+        [prefix, key @ ..] if key.len() == 32 && *prefix == network.wif_prefix => (false, key),
+        _ => return Err(Secp256k1Error::InvalidSecretKey),
+    };
+    Ok((SecretKey::from_bytes(key.try_into().expect("32 bytes"))?, compressed))
+}
+
+const BECH32: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Appends a double SHA-256 checksum to `payload` of at most 34 bytes and base58 encodes it.
+fn base58check(payload: &[u8]) -> Encoded {
+    let mut data = [0u8; 38];
+    let checksum = backend::sha256(&[&backend::sha256(&[payload])]);
+    data[..payload.len()].copy_from_slice(payload);
+    data[payload.len()..payload.len() + 4].copy_from_slice(&checksum[..4]);
+
+    let mut out = Encoded { bytes: [0u8; 90], len: 0 };
+    out.len = base58::encode(&data[..payload.len() + 4], &mut out.bytes);
+    out
+}
+
+/// Base58 decodes `s` into `buf` and checks and strips its checksum.
+fn base58check_decode<'a>(s: &[u8], buf: &'a mut [u8; 38]) -> Option<&'a [u8]> {
+    let data = base58::decode(s, buf)?;
+    let (payload, checksum) = data.split_at(data.len().checked_sub(4)?);
+    (backend::sha256(&[&backend::sha256(&[payload])])[..4] == *checksum).then_some(payload)
+}
+
+/// Encodes a 20-byte witness v0 program as a bech32 address.
+fn bech32_v0(hrp: &str, program: &[u8; 20]) -> Encoded {
+    // Witness version, then the program regrouped from 8 to 5 bits, then 6 checksum characters
+    let mut data = [0u8; 39];
+    let mut bits = 0u32;
+    let mut acc = 0u32;
+    let mut len = 1;
+    for byte in program {
+        acc = (acc << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            data[len] = ((acc >> bits) & 31) as u8;
+            len += 1;
+        }
+    }
+
+    let mut chk = 1u32;
+    for c in hrp.bytes() {
+        chk = polymod(chk, c >> 5);
+    }
+    chk = polymod(chk, 0);
+    for c in hrp.bytes() {
+        chk = polymod(chk, c & 31);
+    }
+    for v in &data[..len] {
+        chk = polymod(chk, *v);
+    }
+    for _ in 0..6 {
+        chk = polymod(chk, 0);
+    }
+    chk ^= 1;
+    for i in 0..6 {
+        data[len + i] = ((chk >> (5 * (5 - i))) & 31) as u8;
+    }
+
+    let mut out = Encoded { bytes: [0u8; 90], len: 0 };
+    hrp.bytes().for_each(|c| out.push(c));
+    out.push(b'1');
+    data[..len + 6].iter().for_each(|v| out.push(BECH32[*v as usize]));
+    out
+}
+
+fn polymod(chk: u32, v: u8) -> u32 {
+    const GEN: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let top = chk >> 25;
+    let mut chk = ((chk & 0x1ffffff) << 5) ^ v as u32;
+    for (i, g) in GEN.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+            chk ^= g;
+        }
+    }
+    chk
+}
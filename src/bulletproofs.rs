@@ -0,0 +1,480 @@
+//! # Bulletproofs
+//!
+//! Bulletproofs range proofs that Pedersen commitments `𝑉 = 𝑣·𝐺 + 𝛾·𝐻`, such as the
+//! commitments of `elgamal` ciphertexts, hold 64-bit amounts. A `RangeProof<K>` covers
+//! `2ᴷ⁻⁶` commitments at once, so `K = 6` proves a single amount and each extra round doubles
+//! the number aggregated, for only two more points.
+//!
+//! Verification reduces a proof to a single equation `Σ 𝒌ᵢ·𝑃ᵢ = 𝒪`, as in the original paper's
+//! single multi-scalar multiplication form. Several proofs are verified together by weighting
+//! each one's equation with challenges drawn from all of them, so the vector generators `𝐺ᵢ`
+//! and `𝐻ᵢ`, the bulk of the terms, are shared across the batch. `verification_terms` writes
+//! the terms of the combined equation into a caller's buffer, and `finish` checks the sum an
+//! `msm::MsmState` reaches over them. Each term costs an `ecmul`, far more than one instruction
+//! allows, so programs recompute the terms in every instruction, step the stored state through
+//! a few of them and call `finish` once it is done. `verify` and `verify_batch` do it all at
+//! once, for clients.
+//!
+//! Challenges come from a SHA-256 `Transcript`. `prove`, which needs `std`, is for clients and
+//! tests.
+
+use crate::{
+    arithmetic, elgamal, hash_to_curve, msm::MsmState, transcript::Transcript, Curve, Secp256k1Error, UncompressedPoint,
+};
+
+#[cfg(feature = "std")]
+use std::{vec, vec::Vec};
+
+/// Bits in each proven amount.
+const BITS: usize = 64;
+
+const DOMAIN: &[u8] = b"SOLANA-SECP256K1-BULLETPROOFS-V1";
+const BATCH_DOMAIN: &[u8] = b"SOLANA-SECP256K1-BULLETPROOFS-BATCH-V1";
+#[cfg(feature = "std")]
+const PROVER_DOMAIN: &[u8] = b"SOLANA-SECP256K1-BULLETPROOFS-PROVER-V1";
+const GENERATOR_DST: &[u8] = b"SOLANA-SECP256K1-BULLETPROOFS-V1_XMD:SHA-256_SSWU_RO_";
+
+const ZERO: [u8; 32] = [0u8; 32];
+const ONE: [u8; 32] = arithmetic::to_be_bytes(&[1, 0, 0, 0]);
+
+/// ### Range Proof
+///
+/// An aggregated range proof over `2ᴷ⁻⁶` commitments: the bit and blinding vector commitments
+/// `𝐴` and `𝑆`, the polynomial commitments `𝑇₁` and `𝑇₂`, the evaluation `𝑡ₓ` with its
+/// blinding factors, and an inner product argument of `K` rounds of `𝐿` and `𝑅`, ending in
+/// the scalars `𝑎` and `𝑏`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct RangeProof<const K: usize> {
+    pub a: UncompressedPoint,
+    pub s: UncompressedPoint,
+    pub t_1: UncompressedPoint,
+    pub t_2: UncompressedPoint,
+    pub t_x: [u8; 32],
+    pub t_x_blinding: [u8; 32],
+    pub e_blinding: [u8; 32],
+    pub l: [UncompressedPoint; K],
+    pub r: [UncompressedPoint; K],
+    pub ipp_a: [u8; 32],
+    pub ipp_b: [u8; 32],
+}
+
+impl<const K: usize> RangeProof<K> {
+    /// ### Values
+    ///
+    /// The number of commitments the proof covers.
+    pub const VALUES: usize = {
+        assert!(K >= 6 && K <= 16, "range proofs aggregate 1 to 1024 values");
+        (1 << K) / BITS
+    };
+
+    /// ### Generators
+    ///
+    /// The number of vector generators verification needs, `𝐺ᵢ` and `𝐻ᵢ` for every bit.
+    pub const GENERATORS: usize = 2 * Self::VALUES * BITS;
+
+    /// ### Terms
+    ///
+    /// The number of terms `verification_terms` writes for a batch of `proofs` proofs: `𝐺`,
+    /// `𝐻` and the vector generators once, then `𝐴`, `𝑆`, `𝑇₁`, `𝑇₂`, each `𝐿` and `𝑅` and
+    /// each commitment for every proof.
+    pub const fn terms(proofs: usize) -> usize {
+        2 + Self::GENERATORS + proofs * (4 + 2 * K + Self::VALUES)
+    }
+
+    fn is_canonical(&self) -> bool {
+        [&self.t_x, &self.t_x_blinding, &self.e_blinding, &self.ipp_a, &self.ipp_b].iter().all(|k| **k < Curve::N)
+            && [&self.a, &self.s, &self.t_1, &self.t_2].iter().all(|p| p.is_on_curve())
+            && self.l.iter().chain(&self.r).all(|p| p.is_on_curve())
+    }
+}
+
+/// ### Generators
+///
+/// Fills `out` with the vector generators, `𝐺ᵢ` in the first half and `𝐻ᵢ` in the second, each
+/// hashed to curve so no discrete logarithms between them are known. They only depend on their
+/// index, so programs can derive them once and store them in an account. Errors with
+/// `InvalidPayload` if `out` has an odd length.
+pub fn generators(out: &mut [UncompressedPoint]) -> Result<(), Secp256k1Error> {
+    if !out.len().is_multiple_of(2) {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    let half = out.len() / 2;
+    for (i, point) in out.iter_mut().enumerate() {
+        let (label, index) = if i < half { (b'G', i) } else { (b'H', i - half) };
+        let mut msg = [label; 5];
+        msg[1..].copy_from_slice(&(index as u32).to_le_bytes());
+        *point = hash_to_curve::hash_to_curve(&msg, GENERATOR_DST)?;
+    }
+    Ok(())
+}
+
+/// ### Verification Terms
+///
+/// Writes the terms of the batch equation for `items`, each a proof with the commitments it
+/// covers, into `out`, returning the `RangeProof::<K>::terms(items.len())` it used. The batch
+/// holds if and only if the terms sum to the point at infinity. `generators` must be the
+/// `RangeProof::<K>::GENERATORS` points of `generators`.
+///
+/// Errors with `InvalidPayload` on an empty batch, a proof with the wrong number of
+/// commitments or short buffers, and `InvalidSignature` on an off-curve point or a scalar not
+/// less than 𝑁.
+pub fn verification_terms<'a, const K: usize>(
+    items: &[(&[UncompressedPoint], &RangeProof<K>)],
+    generators: &[UncompressedPoint],
+    out: &'a mut [(UncompressedPoint, [u8; 32])],
+) -> Result<&'a [(UncompressedPoint, [u8; 32])], Secp256k1Error> {
+    let values = RangeProof::<K>::VALUES;
+    let nm = values * BITS;
+    let len = RangeProof::<K>::terms(items.len());
+    if items.is_empty()
+        || generators.len() != RangeProof::<K>::GENERATORS
+        || out.len() < len
+        || items.iter().any(|(commitments, _)| commitments.len() != values)
+    {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    if items.iter().any(|(commitments, proof)| !proof.is_canonical() || commitments.iter().any(|v| !v.is_on_curve())) {
+        return Err(Secp256k1Error::InvalidSignature);
+    }
+    let out = &mut out[..len];
+
+    // Weights bind every proof in the batch, so none can be chosen to cancel another's error
+    let mut batch = Transcript::new(BATCH_DOMAIN);
+    for (commitments, proof) in items {
+        append_proof(&mut batch, commitments, proof);
+    }
+
+    out[0] = (Curve::G, ZERO);
+    out[1] = (elgamal::H, ZERO);
+    for (term, point) in out[2..2 + 2 * nm].iter_mut().zip(generators) {
+        *term = (*point, ZERO);
+    }
+    let (shared, rest) = out.split_at_mut(2 + 2 * nm);
+    for ((commitments, proof), terms) in items.iter().zip(rest.chunks_exact_mut(4 + 2 * K + values)) {
+        // `weight` scales the inner product argument and `c` the polynomial check
+        let weight = batch.challenge_scalar(b"r");
+        let c = batch.challenge_scalar(b"c");
+        let Challenges { y, z, x, w, u } = challenges(commitments, proof);
+        let u_inv = Curve::batch_mod_inv_n(&u).map_err(|_| Secp256k1Error::InvalidSignature)?;
+        let y_inv = Curve::mod_inv_n(&y).map_err(|_| Secp256k1Error::InvalidSignature)?;
+        let zz = mul(&z, &z);
+
+        let (mut y_i, mut y_inv_i, mut sum_y, mut z_j) = (ONE, ONE, ZERO, zz);
+        for i in 0..nm {
+            if i > 0 && i.is_multiple_of(BITS) {
+                z_j = mul(&z_j, &z);
+            }
+            // 𝐺ᵢ ends up scaled by 𝑠ᵢ, the product of each round's 𝑢 or 𝑢⁻¹ as bit `K - 1 - round`
+            // of 𝑖 is set or not, and 𝐻ᵢ by 1/𝑠ᵢ
+            let (mut s_i, mut s_inv_i) = (ONE, ONE);
+            for (round, (u, u_inv)) in u.iter().zip(&u_inv).enumerate() {
+                let (for_s, for_s_inv) = if (i >> (K - 1 - round)) & 1 == 1 { (u, u_inv) } else { (u_inv, u) };
+                s_i = mul(&s_i, for_s);
+                s_inv_i = mul(&s_inv_i, for_s_inv);
+            }
+            // 𝑔ᵢ = -𝑧 - 𝑎·𝑠ᵢ and ℎᵢ = 𝑧 + 𝑦⁻ⁱ·(𝑧²⁺ʲ·2ᵏ - 𝑏/𝑠ᵢ), for bit 𝑘 of value 𝑗
+            let g = Curve::negate_n(&add(&z, &mul(&proof.ipp_a, &s_i)));
+            let bit = mul(&z_j, &scalar(1 << (i % BITS)));
+            let h = add(&z, &mul(&y_inv_i, &sub(&bit, &mul(&proof.ipp_b, &s_inv_i))));
+            shared[2 + i].1 = add(&shared[2 + i].1, &mul(&weight, &g));
+            shared[2 + nm + i].1 = add(&shared[2 + nm + i].1, &mul(&weight, &h));
+            sum_y = add(&sum_y, &y_i);
+            y_i = mul(&y_i, &y);
+            y_inv_i = mul(&y_inv_i, &y_inv);
+        }
+
+        // 𝛿(𝑦, 𝑧) = (𝑧 - 𝑧²)·Σ𝑦ⁱ - Σ𝑧³⁺ʲ·(2⁶⁴ - 1)
+        let mut delta = mul(&sub(&z, &zz), &sum_y);
+        let mut z_j = mul(&zz, &z);
+        for _ in 0..values {
+            delta = sub(&delta, &mul(&z_j, &scalar(u64::MAX)));
+            z_j = mul(&z_j, &z);
+        }
+        let ab = mul(&proof.ipp_a, &proof.ipp_b);
+        let g = add(&mul(&weight, &mul(&w, &sub(&proof.t_x, &ab))), &mul(&c, &sub(&delta, &proof.t_x)));
+        let h = Curve::negate_n(&add(&mul(&weight, &proof.e_blinding), &mul(&c, &proof.t_x_blinding)));
+        shared[0].1 = add(&shared[0].1, &g);
+        shared[1].1 = add(&shared[1].1, &h);
+
+        let cx = mul(&c, &x);
+        terms[0] = (proof.a, weight);
+        terms[1] = (proof.s, mul(&weight, &x));
+        terms[2] = (proof.t_1, cx);
+        terms[3] = (proof.t_2, mul(&cx, &x));
+        for round in 0..K {
+            terms[4 + round] = (proof.l[round], mul(&weight, &mul(&u[round], &u[round])));
+            terms[4 + K + round] = (proof.r[round], mul(&weight, &mul(&u_inv[round], &u_inv[round])));
+        }
+        let mut z_j = mul(&c, &zz);
+        for (term, commitment) in terms[4 + 2 * K..].iter_mut().zip(*commitments) {
+            *term = (*commitment, z_j);
+            z_j = mul(&z_j, &z);
+        }
+    }
+    Ok(out)
+}
+
+/// ### Finish
+///
+/// Checks a finished `MsmState` over `verification_terms` reached the point at infinity.
+/// Errors with `InvalidState` if terms remain, or `InvalidSignature` if the batch is invalid.
+pub fn finish(state: &MsmState) -> Result<(), Secp256k1Error> {
+    match state.result() {
+        Err(Secp256k1Error::IdentityPoint) => Ok(()),
+        Err(Secp256k1Error::InvalidState) => Err(Secp256k1Error::InvalidState),
+        _ => Err(Secp256k1Error::InvalidSignature),
+    }
+}
+
+/// ### Verify Batch
+///
+/// Verifies every proof in `items` at once, with `terms` as scratch space for
+/// `verification_terms`. Errors as `verification_terms` and `finish` do.
+pub fn verify_batch<const K: usize>(
+    items: &[(&[UncompressedPoint], &RangeProof<K>)],
+    generators: &[UncompressedPoint],
+    terms: &mut [(UncompressedPoint, [u8; 32])],
+) -> Result<(), Secp256k1Error> {
+    let terms = verification_terms(items, generators, terms)?;
+    let mut state = MsmState::new(terms)?;
+    // A term can only fail on an invalid point, which `verification_terms` already rejected
+    state.step(terms, usize::MAX).map_err(|_| Secp256k1Error::InvalidSignature)?;
+    finish(&state)
+}
+
+/// ### Verify
+///
+/// Verifies a single proof over `commitments`, as `verify_batch`.
+pub fn verify<const K: usize>(
+    commitments: &[UncompressedPoint],
+    proof: &RangeProof<K>,
+    generators: &[UncompressedPoint],
+    terms: &mut [(UncompressedPoint, [u8; 32])],
+) -> Result<(), Secp256k1Error> {
+    verify_batch(&[(commitments, proof)], generators, terms)
+}
+
+/// ### Prove
+///
+/// Proves that the commitments `elgamal::commit(amounts[𝑗], &openings[𝑗])` all hold 64-bit
+/// amounts, with `seed` a secret, uniformly random scalar the blinding factors are derived
+/// from. Errors with `InvalidPayload` unless there are `RangeProof::<K>::VALUES` amounts and
+/// openings.
+#[cfg(feature = "std")]
+pub fn prove<const K: usize>(amounts: &[u64], openings: &[[u8; 32]], seed: &[u8; 32]) -> Result<RangeProof<K>, Secp256k1Error> {
+    let nm = RangeProof::<K>::VALUES * BITS;
+    if amounts.len() != RangeProof::<K>::VALUES || openings.len() != amounts.len() {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    let mut points = vec![Curve::G; 2 * nm];
+    generators(&mut points)?;
+    let (g, h) = points.split_at(nm);
+
+    // Blinding factors depend on the witness too, so a reused seed never reuses them for
+    // different amounts
+    let mut rng = Transcript::new(PROVER_DOMAIN);
+    rng.append_scalar(b"seed", seed);
+    for (amount, opening) in amounts.iter().zip(openings) {
+        rng.append_u64(b"v", *amount);
+        rng.append_scalar(b"gamma", opening);
+    }
+    let mut random = |label: &[u8]| rng.challenge_scalar(label);
+    let s_l: Vec<_> = (0..nm).map(|_| random(b"s_L")).collect();
+    let s_r: Vec<_> = (0..nm).map(|_| random(b"s_R")).collect();
+    let (alpha, rho, tau_1, tau_2) = (random(b"alpha"), random(b"rho"), random(b"tau_1"), random(b"tau_2"));
+
+    let commitments = amounts
+        .iter()
+        .zip(openings)
+        .map(|(amount, opening)| elgamal::commit(*amount, opening))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut transcript = transcript(&commitments);
+
+    // 𝒂_L holds the bits of every amount, and 𝒂_R = 𝒂_L - 1
+    let a_l: Vec<_> = (0..nm).map(|i| scalar((amounts[i / BITS] >> (i % BITS)) & 1)).collect();
+    let a_r: Vec<_> = a_l.iter().map(|bit| sub(bit, &ONE)).collect();
+    let a = vector_msm((elgamal::H, alpha), g, &a_l, h, &a_r)?;
+    let s = vector_msm((elgamal::H, rho), g, &s_l, h, &s_r)?;
+    transcript.append_point(b"A", &a);
+    transcript.append_point(b"S", &s);
+    let y = transcript.challenge_scalar(b"y");
+    let z = transcript.challenge_scalar(b"z");
+
+    // 𝑙(𝑋) = 𝒂_L - 𝑧 + 𝒔_L·𝑋 and 𝑟(𝑋) = 𝑦ⁱ·(𝒂_R + 𝑧 + 𝒔_R·𝑋) + 𝑧²⁺ʲ·2ᵏ
+    let zz = mul(&z, &z);
+    let (mut l_0, mut r_0, mut r_1) = (Vec::with_capacity(nm), Vec::with_capacity(nm), Vec::with_capacity(nm));
+    let (mut y_i, mut z_j) = (ONE, zz);
+    for i in 0..nm {
+        if i > 0 && i.is_multiple_of(BITS) {
+            z_j = mul(&z_j, &z);
+        }
+        l_0.push(sub(&a_l[i], &z));
+        r_0.push(add(&mul(&y_i, &add(&a_r[i], &z)), &mul(&z_j, &scalar(1 << (i % BITS)))));
+        r_1.push(mul(&y_i, &s_r[i]));
+        y_i = mul(&y_i, &y);
+    }
+    // 𝑡(𝑋) = ⟨𝑙(𝑋), 𝑟(𝑋)⟩ = 𝑡₀ + 𝑡₁·𝑋 + 𝑡₂·𝑋²
+    let t_1 = add(&inner(&l_0, &r_1), &inner(&s_l, &r_0));
+    let t_2 = inner(&s_l, &r_1);
+    let t_1 = msm(&[(Curve::G, t_1), (elgamal::H, tau_1)])?;
+    let t_2 = msm(&[(Curve::G, t_2), (elgamal::H, tau_2)])?;
+    transcript.append_point(b"T1", &t_1);
+    transcript.append_point(b"T2", &t_2);
+    let x = transcript.challenge_scalar(b"x");
+
+    let l: Vec<_> = l_0.iter().zip(&s_l).map(|(l_0, l_1)| add(l_0, &mul(l_1, &x))).collect();
+    let r: Vec<_> = r_0.iter().zip(&r_1).map(|(r_0, r_1)| add(r_0, &mul(r_1, &x))).collect();
+    let t_x = inner(&l, &r);
+    let mut t_x_blinding = add(&mul(&tau_2, &mul(&x, &x)), &mul(&tau_1, &x));
+    let mut z_j = zz;
+    for opening in openings {
+        t_x_blinding = add(&t_x_blinding, &mul(&z_j, opening));
+        z_j = mul(&z_j, &z);
+    }
+    let e_blinding = add(&alpha, &mul(&rho, &x));
+    transcript.append_scalar(b"t_x", &t_x);
+    transcript.append_scalar(b"t_x_blinding", &t_x_blinding);
+    transcript.append_scalar(b"e_blinding", &e_blinding);
+    let q = Curve::mul_g(&transcript.challenge_scalar(b"w"))?;
+
+    // The inner product argument runs over 𝐻ᵢ' = 𝑦⁻ⁱ·𝐻ᵢ, which takes the 𝑦ⁱ back out of 𝑟
+    let y_inv = Curve::mod_inv_n(&y)?;
+    let mut y_inv_i = ONE;
+    let mut h = h
+        .iter()
+        .map(|h_i| {
+            let h_i = Curve::ecmul(h_i, &y_inv_i);
+            y_inv_i = mul(&y_inv_i, &y_inv);
+            h_i
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (mut g, mut a_vec, mut b_vec) = (g.to_vec(), l, r);
+    let (mut l, mut r) = ([Curve::G; K], [Curve::G; K]);
+    for round in 0..K {
+        let half = a_vec.len() / 2;
+        let (a_lo, a_hi) = a_vec.split_at(half);
+        let (b_lo, b_hi) = b_vec.split_at(half);
+        let (g_lo, g_hi) = g.split_at(half);
+        let (h_lo, h_hi) = h.split_at(half);
+        l[round] = vector_msm((q, inner(a_lo, b_hi)), g_hi, a_lo, h_lo, b_hi)?;
+        r[round] = vector_msm((q, inner(a_hi, b_lo)), g_lo, a_hi, h_hi, b_lo)?;
+        transcript.append_point(b"L", &l[round]);
+        transcript.append_point(b"R", &r[round]);
+        let u = transcript.challenge_scalar(b"u");
+        let u_inv = Curve::mod_inv_n(&u)?;
+
+        let next_a = (0..half).map(|i| add(&mul(&a_lo[i], &u), &mul(&a_hi[i], &u_inv))).collect();
+        let next_b = (0..half).map(|i| add(&mul(&b_lo[i], &u_inv), &mul(&b_hi[i], &u))).collect();
+        let next_g = (0..half).map(|i| msm(&[(g_lo[i], u_inv), (g_hi[i], u)])).collect::<Result<_, _>>()?;
+        let next_h = (0..half).map(|i| msm(&[(h_lo[i], u), (h_hi[i], u_inv)])).collect::<Result<_, _>>()?;
+        (a_vec, b_vec, g, h) = (next_a, next_b, next_g, next_h);
+    }
+
+    Ok(RangeProof { a, s, t_1, t_2, t_x, t_x_blinding, e_blinding, l, r, ipp_a: a_vec[0], ipp_b: b_vec[0] })
+}
+
+struct Challenges<const K: usize> {
+    y: [u8; 32],
+    z: [u8; 32],
+    x: [u8; 32],
+    w: [u8; 32],
+    u: [[u8; 32]; K],
+}
+
+/// Starts a proof's transcript, binding the number of values and their commitments.
+fn transcript(commitments: &[UncompressedPoint]) -> Transcript {
+    let mut transcript = Transcript::new(DOMAIN);
+    transcript.append_u64(b"n", BITS as u64);
+    transcript.append_u64(b"m", commitments.len() as u64);
+    for commitment in commitments {
+        transcript.append_point(b"V", commitment);
+    }
+    transcript
+}
+
+/// Replays a proof's transcript, in the order `prove` derives its challenges.
+fn challenges<const K: usize>(commitments: &[UncompressedPoint], proof: &RangeProof<K>) -> Challenges<K> {
+    let mut transcript = transcript(commitments);
+    transcript.append_point(b"A", &proof.a);
+    transcript.append_point(b"S", &proof.s);
+    let y = transcript.challenge_scalar(b"y");
+    let z = transcript.challenge_scalar(b"z");
+    transcript.append_point(b"T1", &proof.t_1);
+    transcript.append_point(b"T2", &proof.t_2);
+    let x = transcript.challenge_scalar(b"x");
+    transcript.append_scalar(b"t_x", &proof.t_x);
+    transcript.append_scalar(b"t_x_blinding", &proof.t_x_blinding);
+    transcript.append_scalar(b"e_blinding", &proof.e_blinding);
+    let w = transcript.challenge_scalar(b"w");
+    let u = core::array::from_fn(|round| {
+        transcript.append_point(b"L", &proof.l[round]);
+        transcript.append_point(b"R", &proof.r[round]);
+        transcript.challenge_scalar(b"u")
+    });
+    Challenges { y, z, x, w, u }
+}
+
+fn append_proof<const K: usize>(transcript: &mut Transcript, commitments: &[UncompressedPoint], proof: &RangeProof<K>) {
+    for commitment in commitments {
+        transcript.append_point(b"V", commitment);
+    }
+    for (label, point) in [(&b"A"[..], &proof.a), (b"S", &proof.s), (b"T1", &proof.t_1), (b"T2", &proof.t_2)] {
+        transcript.append_point(label, point);
+    }
+    for (l, r) in proof.l.iter().zip(&proof.r) {
+        transcript.append_point(b"L", l);
+        transcript.append_point(b"R", r);
+    }
+    for (label, k) in [
+        (&b"t_x"[..], &proof.t_x),
+        (b"t_x_blinding", &proof.t_x_blinding),
+        (b"e_blinding", &proof.e_blinding),
+        (b"a", &proof.ipp_a),
+        (b"b", &proof.ipp_b),
+    ] {
+        transcript.append_scalar(label, k);
+    }
+}
+
+/// Sums `base + ⟨𝒂, 𝑮⟩ + ⟨𝒃, 𝑯⟩`.
+#[cfg(feature = "std")]
+fn vector_msm(
+    base: (UncompressedPoint, [u8; 32]),
+    g: &[UncompressedPoint],
+    a: &[[u8; 32]],
+    h: &[UncompressedPoint],
+    b: &[[u8; 32]],
+) -> Result<UncompressedPoint, Secp256k1Error> {
+    let terms: Vec<_> =
+        core::iter::once(base).chain(g.iter().copied().zip(a.iter().copied())).chain(h.iter().copied().zip(b.iter().copied())).collect();
+    msm(&terms)
+}
+
+#[cfg(feature = "std")]
+fn msm(terms: &[(UncompressedPoint, [u8; 32])]) -> Result<UncompressedPoint, Secp256k1Error> {
+    let mut state = MsmState::new(terms)?;
+    state.step(terms, usize::MAX)?;
+    state.result()
+}
+
+#[cfg(feature = "std")]
+fn inner(a: &[[u8; 32]], b: &[[u8; 32]]) -> [u8; 32] {
+    a.iter().zip(b).fold(ZERO, |sum, (a, b)| add(&sum, &mul(a, b)))
+}
+
+const fn scalar(value: u64) -> [u8; 32] {
+    arithmetic::to_be_bytes(&[value, 0, 0, 0])
+}
+
+const fn add(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    Curve::add_mod_n(a, b)
+}
+
+const fn sub(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    Curve::add_mod_n(a, &Curve::negate_n(b))
+}
+
+const fn mul(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    Curve::mul_mod_n(a, b)
+}
@@ -0,0 +1,86 @@
+//! # Challenge
+//!
+//! Pluggable challenge hashers, so the same proof code serves EVM-flavored protocols, which
+//! derive challenges with Keccak-256, and Bitcoin-flavored ones, which use BIP-340 tagged
+//! SHA-256. Schnorr verification (`bip340::verify_with`), the DLEQ proofs of `oprf` and
+//! `transcript::Transcript` all take any `ChallengeHasher`.
+
+use crate::{backend, Curve};
+
+/// ### Challenge Hasher
+///
+/// A 256-bit hash over the concatenation of a few slices, used to derive Fiat-Shamir
+/// challenges. The number of slices is a const generic, so hashers that prepend their own
+/// slices can check their capacity at compile time.
+pub trait ChallengeHasher: Copy {
+    /// ### Hash
+    ///
+    /// Hashes the concatenation of `vals`.
+    fn hash<const M: usize>(&self, vals: &[&[u8]; M]) -> [u8; 32];
+
+    /// ### Challenge
+    ///
+    /// Hashes the concatenation of `vals` into a scalar, reduced modulo 𝑁 as BIP-340 does.
+    fn challenge<const M: usize>(&self, vals: &[&[u8]; M]) -> [u8; 32] {
+        Curve::add_mod_n(&self.hash(vals), &[0u8; 32])
+    }
+}
+
+/// ### SHA-256
+///
+/// Plain SHA-256, through `sol_sha256` on-chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sha256;
+
+impl ChallengeHasher for Sha256 {
+    fn hash<const M: usize>(&self, vals: &[&[u8]; M]) -> [u8; 32] {
+        backend::sha256(vals)
+    }
+}
+
+/// ### Keccak-256
+///
+/// Keccak-256 as used by Ethereum, through `sol_keccak256` on-chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Keccak256;
+
+impl ChallengeHasher for Keccak256 {
+    fn hash<const M: usize>(&self, vals: &[&[u8]; M]) -> [u8; 32] {
+        backend::keccak256(vals)
+    }
+}
+
+/// ### Tagged SHA-256
+///
+/// The BIP-340 tagged hash `SHA-256(SHA-256(tag) ‖ SHA-256(tag) ‖ data)`. The tag is hashed
+/// once up front, so each hash costs a single syscall. `data` is at most 6 slices, which is
+/// checked at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaggedSha256 {
+    tag_hash: [u8; 32],
+}
+
+impl TaggedSha256 {
+    /// ### New
+    ///
+    /// Hashes `tag`, eg: `b"BIP0340/challenge"`.
+    pub fn new(tag: &[u8]) -> Self {
+        Self { tag_hash: backend::sha256(&[tag]) }
+    }
+
+    /// ### Tag Hash
+    ///
+    /// The hashed tag, `SHA-256(tag)`.
+    pub const fn tag_hash(&self) -> &[u8; 32] {
+        &self.tag_hash
+    }
+}
+
+impl ChallengeHasher for TaggedSha256 {
+    fn hash<const M: usize>(&self, vals: &[&[u8]; M]) -> [u8; 32] {
+        const { assert!(M <= 6, "at most 6 data slices") };
+        let mut data: [&[u8]; 8] = [&self.tag_hash, &self.tag_hash, &[], &[], &[], &[], &[], &[]];
+        data[2..2 + M].copy_from_slice(vals);
+        backend::sha256(&data[..2 + M])
+    }
+}
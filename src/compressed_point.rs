@@ -1,13 +1,13 @@
-use dashu::integer::UBig;
-use solana_nostd_secp256k1_recover::secp256k1_recover;
 
-use crate::*;
+use crate::{backend, *};
 use core::{fmt::{Debug, Formatter}, ops::{Add, Mul}};
 
 pub const SEC1_OCTET_COMPRESSED_EVEN: u8 = 0x02;
 pub const SEC1_OCTET_COMPRESSED_ODD: u8 = 0x03;
 
 #[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+#[repr(transparent)]
 pub struct CompressedPoint(pub [u8; Self::SIZE]);
 
 impl Secp256k1Point for CompressedPoint {
@@ -21,24 +21,14 @@ impl Secp256k1Point for CompressedPoint {
         self.0[0] == SEC1_OCTET_COMPRESSED_EVEN
     }
 
-    fn x(&self) -> [u8; 32] {
-        [
-            self.0[1], self.0[2], self.0[3], self.0[4], self.0[5], self.0[6], self.0[7], self.0[8],
-            self.0[9], self.0[10], self.0[11], self.0[12], self.0[13], self.0[14], self.0[15],
-            self.0[16], self.0[17], self.0[18], self.0[19], self.0[20], self.0[21], self.0[22],
-            self.0[23], self.0[24], self.0[25], self.0[26], self.0[27], self.0[28], self.0[29],
-            self.0[30], self.0[31], self.0[32],
-        ]
+    fn x_ref(&self) -> &[u8; 32] {
+        self.0[1..].try_into().expect("33 byte point")
     }
 
     fn y(&self) -> [u8; 32] {
-        // Raise X to uncompressed point first
-        let mut p = UncompressedPoint::lift_x_unchecked(&self.x());
-        // If resulting Y-coordinate polarity doesn't match, invert it.
-        if p.is_even() != self.is_even() {
-            p.invert()
-        }
-        p.y()
+        // Raise X to both uncompressed points, and take the one matching our parity
+        let (even, odd) = Curve::lift_x_both_unchecked(self.x_ref());
+        if self.is_even() { even.y() } else { odd.y() }
     }
 
     fn lift_x(x: &[u8; 32]) -> Result<Self, Secp256k1Error> {
@@ -57,31 +47,20 @@ impl Secp256k1Point for CompressedPoint {
         *self
     }
     
-    fn decompress(&self) -> UncompressedPoint {
+    fn decompress(&self) -> Result<UncompressedPoint, Secp256k1Error> {
+        Curve::decompress(*self)
+    }
+
+    fn decompress_unchecked(&self) -> UncompressedPoint {
         Curve::decompress_unchecked(*self)
     }
 
-    fn tweak(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error> {
-        // Compute z = (-r * k) mod N
-        let z_scalar = ((UBig::from_be_bytes(&Curve::negate_n(&self.x())) * UBig::from_be_bytes(&tweak)) % UBig::from_be_bytes(&Curve::N)).to_be_bytes();
-               
-        // Ensure z and s are 32 bytes
-        let mut z = [0u8; 32];
-        z[32 - z_scalar.len()..].copy_from_slice(&z_scalar);
-
-        let s: [u8; 64] = [
-            self.0[1], self.0[2], self.0[3], self.0[4], self.0[5], self.0[6], self.0[7], self.0[8], 
-            self.0[9], self.0[10], self.0[11], self.0[12], self.0[13], self.0[14], self.0[15], self.0[16], 
-            self.0[17], self.0[18], self.0[19], self.0[20], self.0[21], self.0[22], self.0[23], self.0[24], 
-            self.0[25], self.0[26], self.0[27], self.0[28], self.0[29], self.0[30], self.0[31], self.0[32],
-            self.0[1], self.0[2], self.0[3], self.0[4], self.0[5], self.0[6], self.0[7], self.0[8], 
-            self.0[9], self.0[10], self.0[11], self.0[12], self.0[13], self.0[14], self.0[15], self.0[16], 
-            self.0[17], self.0[18], self.0[19], self.0[20], self.0[21], self.0[22], self.0[23], self.0[24], 
-            self.0[25], self.0[26], self.0[27], self.0[28], self.0[29], self.0[30], self.0[31], self.0[32],
-        ];
-
-        // Use ecrecover with negated z to perform ECAdd
-        Ok(UncompressedPoint(secp256k1_recover(&z, self.is_odd(), &s)?).into())
+    fn tweak_unchecked(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error> {
+        Ok(UncompressedPoint(backend::tweak(self.x_ref(), self.is_odd(), &tweak)?).into())
+    }
+
+    fn tweak_mul_unchecked(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error> {
+        Ok(UncompressedPoint(backend::ecmul(self.x_ref(), self.is_odd(), &tweak)?).into())
     }
 }
 
@@ -94,7 +73,7 @@ impl Mul<&[u8;32]> for CompressedPoint {
 }
 
 impl Add<UncompressedPoint> for CompressedPoint {
-    type Output = UncompressedPoint;
+    type Output = Result<UncompressedPoint, Secp256k1Error>;
 
     fn add(self, point: UncompressedPoint) -> Self::Output {
         point.add(self)
@@ -102,66 +81,49 @@ impl Add<UncompressedPoint> for CompressedPoint {
 }
 
 impl Add for CompressedPoint {
-    type Output = UncompressedPoint;
+    type Output = Result<UncompressedPoint, Secp256k1Error>;
 
     fn add(self, point: Self) -> Self::Output {
-        let p = point.decompress();
-        p.add(point)
+        self.decompress()?.add(point)
     }
 }
 
 impl Debug for CompressedPoint {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for byte in &self.0 {
-            write!(f, "{:02X}", byte)?;
-        }
-        Ok(())
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(hex::encode_upper_to_slice(&self.0, &mut [0u8; 66]).expect("66 hex digits"))
     }
 }
 
 impl From<UncompressedPoint> for CompressedPoint {
     fn from(p: UncompressedPoint) -> Self {
-        CompressedPoint([
-            p.is_odd() as u8 + 2,
-            p.0[0],
-            p.0[1],
-            p.0[2],
-            p.0[3],
-            p.0[4],
-            p.0[5],
-            p.0[6],
-            p.0[7],
-            p.0[8],
-            p.0[9],
-            p.0[10],
-            p.0[11],
-            p.0[12],
-            p.0[13],
-            p.0[14],
-            p.0[15],
-            p.0[16],
-            p.0[17],
-            p.0[18],
-            p.0[19],
-            p.0[20],
-            p.0[21],
-            p.0[22],
-            p.0[23],
-            p.0[24],
-            p.0[25],
-            p.0[26],
-            p.0[27],
-            p.0[28],
-            p.0[29],
-            p.0[30],
-            p.0[31],
-        ])
+        let mut c = [p.is_odd() as u8 + 2; 33];
+        c[1..].copy_from_slice(p.x_ref());
+        CompressedPoint(c)
     }
 }
 
-impl From<[u8; 65]> for CompressedPoint {
-    fn from(p: [u8; 65]) -> Self {
-        UncompressedPoint::from(p).into()
+impl CompressedPoint {
+    /// ### From SEC1
+    /// 
+    /// Parses a 33-byte SEC1 compressed point, checking its prefix and that it is on curve.
+    pub fn from_sec1(p: &[u8; 33]) -> Result<Self, Secp256k1Error> {
+        Ok(Self(*p).decompress()?.compress())
+    }
+
+    /// ### From SEC1 Unchecked
+    /// 
+    /// Wraps a 33-byte SEC1 compressed point without any checks. Only use this when you know
+    /// you are dealing with a valid point.
+    pub const fn from_sec1_unchecked(p: &[u8; 33]) -> Self {
+        Self(*p)
+    }
+}
+
+impl TryFrom<[u8; 65]> for CompressedPoint {
+    type Error = Secp256k1Error;
+
+    fn try_from(p: [u8; 65]) -> Result<Self, Secp256k1Error> {
+        Ok(UncompressedPoint::try_from(p)?.into())
     }
 }
 
@@ -172,3 +134,28 @@ impl TryFrom<[u8; 32]> for CompressedPoint {
         Ok(UncompressedPoint::try_from(scalar)?.into())
     }
 }
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for CompressedPoint {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        writer.write_all(&self.0)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for CompressedPoint {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let mut p = [0u8; Self::SIZE];
+        reader.read_exact(&mut p)?;
+        CompressedPoint::from_sec1(&p).map_err(|_| borsh::io::Error::new(borsh::io::ErrorKind::InvalidData, "Invalid point"))
+    }
+}
+
+/// `CompressedPoint` is 33 bytes with an alignment of 1, so it has no padding of its own and
+/// can be cast directly out of account data. Zero-copy structs that need wider alignment for
+/// neighbouring fields should place it next to explicit `[u8; N]` padding.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for CompressedPoint {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for CompressedPoint {}
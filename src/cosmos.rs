@@ -0,0 +1,44 @@
+//! # Cosmos
+//!
+//! Verification of Cosmos SDK and Tendermint secp256k1 signatures, for IBC-adjacent programs
+//! validating validator or user signatures. Cosmos signs the SHA-256 hash of its sign bytes
+//! with a fixed 64-byte low-𝑠 `𝑟‖𝑠` signature, and identifies signers by their 33-byte
+//! compressed public key.
+
+use crate::{backend, CompressedPoint, Curve, Secp256k1Error};
+
+const ADR36_PREFIX: &[u8] = br#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","msgs":[{"type":"sign/MsgSignData","value":{"data":""#;
+const ADR36_SIGNER: &[u8] = br#"","signer":""#;
+const ADR36_SUFFIX: &[u8] = br#""}}],"sequence":"0"}"#;
+
+/// ### Verify
+///
+/// Verifies a signature over `sign_bytes`, eg: a serialized `SignDoc` or Tendermint vote.
+pub fn verify(sign_bytes: &[u8], signature: &[u8; 64], pubkey: &CompressedPoint) -> Result<(), Secp256k1Error> {
+    verify_prehashed(&backend::sha256(&[sign_bytes]), signature, pubkey)
+}
+
+/// ### Verify ADR-36
+///
+/// Verifies an ADR-36 signature over arbitrary data, as produced by wallets such as Keplr's
+/// `signArbitrary`. `data` is the base64 encoded data as it appears in the amino JSON sign doc,
+/// and `signer` is the bech32 address of the signer.
+pub fn verify_adr36(signer: &str, data: &str, signature: &[u8; 64], pubkey: &CompressedPoint) -> Result<(), Secp256k1Error> {
+    verify_prehashed(&adr36_hash(signer, data), signature, pubkey)
+}
+
+/// ### ADR-36 Hash
+///
+/// Hashes the canonical amino JSON sign doc of an ADR-36 `MsgSignData`, without building it in
+/// memory.
+pub fn adr36_hash(signer: &str, data: &str) -> [u8; 32] {
+    backend::sha256(&[ADR36_PREFIX, data.as_bytes(), ADR36_SIGNER, signer.as_bytes(), ADR36_SUFFIX])
+}
+
+/// ### Verify Prehashed
+///
+/// Verifies a signature over the SHA-256 `hash` of some sign bytes. As in the Cosmos SDK,
+/// high-𝑠 signatures are rejected to prevent malleability.
+pub fn verify_prehashed(hash: &[u8; 32], signature: &[u8; 64], pubkey: &CompressedPoint) -> Result<(), Secp256k1Error> {
+    Curve::verify_ecdsa(hash, signature, pubkey)
+}
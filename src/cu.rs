@@ -0,0 +1,128 @@
+//! # Compute Units
+//!
+//! Estimated compute unit (CU) costs of each primitive, so programs can check their compute
+//! budget up front and clients can set `ComputeBudget` limits without guessing.
+//!
+//! Syscall costs are those agave charges under the default compute budget, and are checked
+//! against its `ComputeBudget` in tests. The limb arithmetic surrounding each syscall is priced
+//! with per-multiplication and per-operation allowances which have not yet been calibrated
+//! against the SBF build. `test-utils` has a compute unit bench printing the allowances its
+//! measurements imply, and a test failing on any operation costing more than its estimate, both
+//! run against an SBF build of the verifier. Until then, estimates are approximate rather than
+//! upper bounds, so leave a margin when budgeting against them.
+
+/// Cost of `sol_secp256k1_recover`, charged in full with no per-syscall base cost.
+const SECP256K1_RECOVER: u64 = 25_000;
+
+/// Base cost of `sol_keccak256` and `sol_sha256`.
+const HASH_BASE: u64 = 85;
+
+/// Minimum cost of each slice hashed by `sol_keccak256` and `sol_sha256`, otherwise charged a
+/// unit per two bytes.
+const HASH_SLICE_MIN: u64 = 10;
+
+/// Allowance for a 256-bit modular multiplication with fixed-width limbs, not yet measured.
+const FIELD_MUL: u64 = 1_500;
+
+/// Allowance for a modular inverse, computed as `𝒌⁽ᴹ⁻²⁾` with ~290 multiplications.
+const FIELD_INV: u64 = 290 * FIELD_MUL;
+
+/// Allowance for argument checks and copies around each operation, not yet measured.
+const OVERHEAD: u64 = 500;
+
+/// ### Operation
+///
+/// A primitive whose compute unit cost can be estimated with `cu_estimate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// A raw `secp256k1_recover` syscall.
+    Recover,
+    /// `Curve::mul_g`, calculating `𝒌·𝐺`.
+    MulG,
+    /// `Curve::ecmul`, calculating `𝒌·𝑃`.
+    Ecmul,
+    /// `Secp256k1Point::tweak`, calculating `𝑃 + 𝒕·𝐺`.
+    Tweak,
+    /// `Curve::double_mul`, calculating `𝒂·𝐺 + 𝒃·𝑃` with a single recover.
+    DoubleMul,
+    /// `Secp256k1Point::decompress` of a `CompressedPoint`.
+    Decompress,
+    /// `Curve::mod_inv_n` or `Curve::mod_inv_p`.
+    ModInv,
+    /// `Curve::batch_mod_inv_n` over `count` scalars.
+    BatchModInv { count: u64 },
+    /// Point addition, eg: `UncompressedPoint + UncompressedPoint`. `Curve::ecadd` costs `Ecmul`
+    /// instead when doubling or adding `±𝐺`.
+    PointAdd,
+    /// ECDSA verification by recovering the signer and comparing it to a public key.
+    VerifyEcdsa,
+    /// BIP-340 Schnorr verification with a single recover, hashing with `sol_sha256`.
+    VerifySchnorr,
+    /// `taproot::verify_key_path`, tweaking the internal key and verifying against the output key.
+    VerifyTaprootKeyPath,
+    /// `eth::RecoverableSignature::recover_address`.
+    EthRecoverAddress,
+    /// `eth::recover_addresses` over `count` signatures.
+    EthRecoverAddresses { count: u64 },
+    /// `cosmos::verify` over `len` sign bytes, recovering with both parities of `𝑅` at worst.
+    VerifyCosmos { len: u64 },
+    /// `sol_keccak256` over `len` bytes in a single slice.
+    Keccak256 { len: u64 },
+    /// A multi-scalar multiplication `Σ 𝒌ᵢ·𝑃ᵢ` over `terms` terms, eg: with `msm::MsmState`.
+    Msm { terms: u64 },
+    /// `count` independent ECDSA verifications in a single instruction.
+    VerifyEcdsaBatch { count: u64 },
+    /// One point of `msm::AddCursor::step`, a Jacobian addition after decompressing the point
+    /// if it is `compressed`.
+    AddStep { compressed: bool },
+    /// `Curve::ecadd_many` over `count` points, with a single inversion at the end.
+    EcaddMany { count: u64, compressed: bool },
+}
+
+/// ### CU Estimate
+///
+/// Returns the estimated compute unit cost of `op`, suitable for a `ComputeBudget` limit or
+/// checking against `sol_remaining_compute_units` before starting an operation.
+pub const fn cu_estimate(op: Operation) -> u64 {
+    const RECOVER: u64 = SECP256K1_RECOVER;
+    const ECMUL: u64 = RECOVER + FIELD_MUL + OVERHEAD;
+    // `𝑠 = 𝑟·𝒃` and `𝑧 = -𝑟·𝒂`, and checking both scalars, where `ecmul` has a single product
+    const DOUBLE_MUL: u64 = ECMUL + 3 * FIELD_MUL;
+    const POINT_ADD: u64 = FIELD_INV + 3 * FIELD_MUL + OVERHEAD;
+    // A mixed Jacobian addition takes 11 multiplications and squarings, a doubling fewer
+    const JACOBIAN_ADD: u64 = 12 * FIELD_MUL + OVERHEAD;
+    const VERIFY_ECDSA: u64 = RECOVER + OVERHEAD;
+    const ETH_RECOVER: u64 = RECOVER + hash(64) + OVERHEAD;
+    // Tagged challenge hash and a handful of scalar multiplications around one `double_mul`
+    const VERIFY_SCHNORR: u64 = RECOVER + 3 * hash(96) + 4 * FIELD_MUL + OVERHEAD;
+    match op {
+        Operation::Recover => RECOVER,
+        Operation::MulG | Operation::Ecmul | Operation::Tweak => ECMUL,
+        Operation::DoubleMul => DOUBLE_MUL,
+        Operation::Decompress => RECOVER + OVERHEAD,
+        Operation::ModInv => FIELD_INV + OVERHEAD,
+        Operation::BatchModInv { count } => count.saturating_mul(3 * FIELD_MUL).saturating_add(FIELD_INV + OVERHEAD),
+        Operation::PointAdd => POINT_ADD,
+        Operation::VerifyEcdsa => VERIFY_ECDSA,
+        Operation::VerifySchnorr => VERIFY_SCHNORR,
+        // The tagged tweak hash and one tweak recover, then the usual verification
+        Operation::VerifyTaprootKeyPath => 3 * hash(64) + ECMUL + VERIFY_SCHNORR,
+        Operation::EthRecoverAddress => ETH_RECOVER,
+        Operation::EthRecoverAddresses { count } => count.saturating_mul(ETH_RECOVER),
+        Operation::VerifyCosmos { len } => hash(len).saturating_add(2 * RECOVER + OVERHEAD),
+        Operation::Keccak256 { len } => hash(len),
+        Operation::Msm { terms } => terms.saturating_mul(ECMUL + POINT_ADD),
+        Operation::VerifyEcdsaBatch { count } => count.saturating_mul(VERIFY_ECDSA),
+        Operation::AddStep { compressed: false } => JACOBIAN_ADD,
+        Operation::AddStep { compressed: true } => JACOBIAN_ADD + RECOVER + OVERHEAD,
+        Operation::EcaddMany { count, compressed } => {
+            count.saturating_mul(cu_estimate(Operation::AddStep { compressed })).saturating_add(FIELD_INV + OVERHEAD)
+        }
+    }
+}
+
+/// Cost of hashing `len` bytes in a single slice, the same for `sol_keccak256` and `sol_sha256`.
+const fn hash(len: u64) -> u64 {
+    let slice = if len / 2 > HASH_SLICE_MIN { len / 2 } else { HASH_SLICE_MIN };
+    HASH_BASE + slice
+}
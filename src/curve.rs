@@ -1,7 +1,5 @@
-use dashu::integer::{fast_div::ConstDivisor, modular::IntoRing, UBig};
-use solana_nostd_secp256k1_recover::secp256k1_recover;
+use crate::{arithmetic, backend::{self, secp256k1_recover}, *};
 
-use crate::*;
 pub struct Curve;
 
 impl Curve {
@@ -27,23 +25,15 @@ impl Curve {
     ///
     /// The precomputed value of 𝑁−2 where 𝑁 is the order of the secp256k1 curve. 
     /// Used in modular arithmetic operations, such as modular inverse.
-    pub const N_SUB_2: [u8; 32] = [
-        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-        0xfe, 0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36,
-        0x41, 0x3f,
-    ];
+    pub const N_SUB_2: [u8; 32] = arithmetic::to_be_bytes(&arithmetic::sbb(&arithmetic::N.m, &[2, 0, 0, 0]).0);
 
-    /// ### Curve order 𝑁-2
+    /// ### Curve order 𝑁/2
     /// 
-    /// 0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd036413f
+    /// 0x7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0
     ///
     /// The precomputed value of 𝑁/2 where 𝑁 is the order of the secp256k1 curve. 
     /// Used in integer comparison for high S checks in ECDSA.
-    pub const N_DIV_2: [u8;32] = [
-        0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 
-        0xFF, 0x5D, 0x57, 0x6E, 0x73, 0x57, 0xA4, 0x50, 0x1D, 0xDF, 0xE9, 0x2F, 0x46, 0x68, 0x1B, 
-        0x20, 0xA0
-    ];
+    pub const N_DIV_2: [u8; 32] = arithmetic::to_be_bytes(&arithmetic::shr(&arithmetic::N.m, 1));
 
     /// ### Field Prime Modulus 𝑃
     /// 
@@ -63,11 +53,7 @@ impl Curve {
     ///  
     /// This represents 𝑃−2, where 𝑃 is the field prime of the secp256k1 curve. This value 
     /// is modular arithmetic operations, such as modular inverse.
-    pub const P_SUB_2: [u8; 32] = [
-        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe, 0xff, 0xff,
-        0xfc, 0x2d,
-    ];
+    pub const P_SUB_2: [u8; 32] = arithmetic::to_be_bytes(&arithmetic::sbb(&arithmetic::P.m, &[2, 0, 0, 0]).0);
 
     /// ### Field Prime Modulus 𝑃+1/4
     /// 
@@ -75,11 +61,15 @@ impl Curve {
     /// 
     /// This is the value 𝑃+1/4, where 𝑃 is the field prime. This value is precomputed for 
     /// efficiency and is used in calculating square roots in the field.
-    pub const P_1_4: [u8; 32] = [
-        0x3f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
-        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xbf, 0xff,
-        0xff, 0x0c,
-    ];
+    pub const P_1_4: [u8; 32] = arithmetic::to_be_bytes(&arithmetic::shr(&arithmetic::adc(&arithmetic::P.m, &[1, 0, 0, 0]).0, 2));
+
+    /// ### Field Prime Modulus 𝑃-1/2
+    /// 
+    /// 0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffff7ffffe17
+    /// 
+    /// This is the value 𝑃-1/2, where 𝑃 is the field prime. This value is precomputed for
+    /// efficiency and is used as the exponent of Euler's criterion for quadratic residues.
+    pub const P_SUB_1_DIV_2: [u8; 32] = arithmetic::to_be_bytes(&arithmetic::shr(&arithmetic::P.m, 1));
 
     /// ### Generator Point 𝐺
     /// 
@@ -96,227 +86,203 @@ impl Curve {
         0xFB, 0x10, 0xD4, 0xB8,
     ]);
 
+    /// ### Generator Multiples
+    /// 
+    /// The points `1·𝐺, 2·𝐺, …, 16·𝐺`, where `G_MULTIPLES[i]` is `(i+1)·𝐺`.
+    /// 
+    /// The table is evaluated entirely at compile time, so window-based and fixed-base
+    /// multiplication can index into it without any runtime setup cost.
+    pub const G_MULTIPLES: [UncompressedPoint; 16] = Self::g_multiples();
+
+    const fn g_multiples<const K: usize>() -> [UncompressedPoint; K] {
+        let g = arithmetic::point_from_be_bytes(&Self::G.0);
+        let mut table = [Self::G; K];
+        let mut acc = g;
+        let mut i = 1;
+        while i < K {
+            acc = match arithmetic::affine_add_checked(&acc, &g) {
+                Some(next) => next,
+                // 𝑖·𝐺 is never the identity for 𝑖 < 𝑁, and this only runs at compile time
+                None => panic!("Point at infinity"),
+            };
+            table[i] = UncompressedPoint(arithmetic::point_to_be_bytes(&acc));
+            i += 1;
+        }
+        table
+    }
+
     /// ### Add Mod Point 𝑁
     /// 
     /// Adds two scalars modulus curve order N.
-    pub fn add_mod_n(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
-        // Convert the input &[u8; 32] to BigUint
-        let a_int = UBig::from_be_bytes(a);
-        let b_int = UBig::from_be_bytes(b);
-        let n_int = UBig::from_be_bytes(&Self::N);
-    
-        // Perform the modular addition
-        let res_int = (a_int + b_int) % n_int;
-        let res_bytes = res_int.to_be_bytes();
-    
-        // Prepare a fixed 32-byte array for the result
-        let mut result = [0u8; 32];
-    
-        // Copy the bytes from res_bytes into result, ensuring correct size
-        let len = res_bytes.len();
-        result[32 - len..].copy_from_slice(&res_bytes);
-        result
-    }    
+    pub const fn add_mod_n(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        arithmetic::to_be_bytes(&arithmetic::add_mod(
+            &arithmetic::from_be_bytes(a),
+            &arithmetic::from_be_bytes(b),
+            &arithmetic::N,
+        ))
+    }
 
     /// ### Mul Mod Point 𝑁
     /// 
     /// Multiplies a scalar by another scalar modulus curve order N. Typically used to create
     /// a normalized nonce/private key scalar.
-    pub fn mul_mod_n(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
-        // Convert the input &[u8; 32] to Integer
-        let a_int = UBig::from_be_bytes(a);
-        let b_int = UBig::from_be_bytes(b);
-        let n_int = UBig::from_be_bytes(&Self::N);
-
-        // Perform the modular addition
-        let res_int = (a_int * b_int) % n_int;
-        let res_bytes = res_int.to_be_bytes();
-    
-        // Prepare a fixed 32-byte array for the result
-        let mut result = [0u8; 32];
-    
-        // Copy the bytes from res_bytes into result, ensuring correct size
-        let len = res_bytes.len();
-        result[32 - len..].copy_from_slice(&res_bytes);
-        result
+    pub const fn mul_mod_n(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        arithmetic::to_be_bytes(&arithmetic::mul_mod(
+            &arithmetic::from_be_bytes(a),
+            &arithmetic::from_be_bytes(b),
+            &arithmetic::N,
+        ))
     }
 
     /// ### Add Mod Point 𝑃
     /// 
     /// Adds two scalars modulus prime order 𝑃.
-    pub fn add_mod_p(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
-        // Convert the input &[u8; 32] to BigUint
-        let a_int = UBig::from_be_bytes(a);
-        let b_int = UBig::from_be_bytes(b);
-        let p_int = UBig::from_be_bytes(&Self::P);
-    
-        // Perform the modular addition
-        let res_int = (a_int + b_int) % p_int;
-        let res_bytes = res_int.to_be_bytes();
-    
-        // Prepare a fixed 32-byte array for the result
-        let mut result = [0u8; 32];
-    
-        // Copy the bytes from res_bytes into result, ensuring correct size
-        let len = res_bytes.len();
-        result[32 - len..].copy_from_slice(&res_bytes);
-        result
-    }    
+    pub const fn add_mod_p(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        arithmetic::to_be_bytes(&arithmetic::add_mod(
+            &arithmetic::from_be_bytes(a),
+            &arithmetic::from_be_bytes(b),
+            &arithmetic::P,
+        ))
+    }
 
     /// ### Mul Mod Point 𝑃
     /// 
     /// Multiplies a scalar by another scalar modulus prime order 𝑃.
-    pub fn mul_mod_p(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
-        // Convert the input &[u8; 32] to Integer
-        let a_int = UBig::from_be_bytes(a);
-        let b_int = UBig::from_be_bytes(b);
-        let p_int = UBig::from_be_bytes(&Self::P);
-
-        // Perform the modular addition
-        let res_int = (a_int * b_int) % p_int;
-        let res_bytes = res_int.to_be_bytes();
-    
-        // Prepare a fixed 32-byte array for the result
-        let mut result = [0u8; 32];
-    
-        // Copy the bytes from res_bytes into result, ensuring correct size
-        let len = res_bytes.len();
-        result[32 - len..].copy_from_slice(&res_bytes);
-        result
+    pub const fn mul_mod_p(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        arithmetic::to_be_bytes(&arithmetic::mul_mod(
+            &arithmetic::from_be_bytes(a),
+            &arithmetic::from_be_bytes(b),
+            &arithmetic::P,
+        ))
     }
 
     /// ### Decompress Point
     /// 
-    /// Decompresses a point by recovering it with parity
+    /// Decompresses a point by recovering it with parity. Errors if the prefix is not a valid
+    /// SEC1 compressed prefix or the 𝑋-coordinate is not on curve.
+    /// 
+    /// As the 𝑋-coordinate is used as 𝑟, points with `𝑋 ≥ 𝑁` cannot be recovered and are
+    /// rejected. These occur with negligible probability for honestly generated keys.
     pub fn decompress(p: CompressedPoint) -> Result<UncompressedPoint, Secp256k1Error> {
-        let mut s = [0u8;64];
-        s[..32].clone_from_slice(&p.0[1..33]);
-        s[32..].clone_from_slice(&p.0[1..33]);
-        Ok(UncompressedPoint(secp256k1_recover(&[0u8; 32], false, &s)?))
+        if !p.is_odd() && !p.is_even() {
+            return Err(Secp256k1Error::InvalidPublicKey);
+        }
+        let x = p.x();
+        Ok(UncompressedPoint(secp256k1_recover(&[0u8; 32], p.is_odd(), &arithmetic::concat(&x, &x))?))
     }
 
     /// ### Decompress Point Unchecked
     /// 
-    /// Decompresses a point by recovering it with parity without checking it is on curve
+    /// Decompresses a point by lifting its 𝑋-coordinate and matching parity, without checking
+    /// it is on curve. Only use this when you know you are dealing with a valid point.
     pub fn decompress_unchecked(p: CompressedPoint) -> UncompressedPoint {
-        let mut s = [0u8;64];
-        s[..32].clone_from_slice(&p.0[1..33]);
-        s[32..].clone_from_slice(&p.0[1..33]);
-        UncompressedPoint(secp256k1_recover(&[0u8; 32], p.is_odd(), &s).expect("Point off curve"))
+        let (even, odd) = Self::lift_x_both_unchecked(p.x_ref());
+        if p.is_odd() { odd } else { even }
     }
 
     /// ### Lift X coordinate to curve
     /// 
     /// Lifts an X coordinate to curve and checks for a valid Y coordinate
-    pub fn lift_x(x: &[u8;32]) -> Result<UncompressedPoint, Secp256k1Error> {        
-        let p = UBig::from_be_bytes(&Curve::P);
-
-        // Calculate right side: x³ + 7
-        let x_3 = UBig::from_be_bytes(x).cubic() + UBig::from_word(7);
-        
-        let divisor = ConstDivisor::new(p.clone());
-        let exp = x_3.clone().into_ring(&divisor);
-        
-        // Calculate y = (x³ + 7)^((p+1)/4) mod p
-        let y = exp.pow(&(UBig::from_be_bytes(&Curve::P_1_4))).residue() % &p;
-
-        if y.sqr() % &p != x_3 % &p {
-            return Err(Secp256k1Error::InvalidYCoordinate);
-        }
-
-        let y_bytes = y.to_be_bytes();
-
-        let mut point = [0u8;64];
-        point[..32].clone_from_slice(x);
-        point[64 - y_bytes.len()..].clone_from_slice(&y_bytes);
-        Ok(UncompressedPoint(point))
+    pub fn lift_x(x: &[u8;32]) -> Result<UncompressedPoint, Secp256k1Error> {
+        // y = √(x³ + 7) mod p
+        let x_3 = arithmetic::to_be_bytes(&arithmetic::curve_rhs(&arithmetic::from_be_bytes(x)));
+        let y = Self::mod_sqrt(&x_3).ok_or(Secp256k1Error::InvalidYCoordinate)?;
+        Ok(UncompressedPoint(arithmetic::concat(x, &y)))
     }
 
     /// ### Lift X coordinate to curve unchecked
     /// 
     /// Lifts an X coordinate to curve and checks for a valid Y coordinate
-    pub fn lift_x_unchecked(x: &[u8;32]) -> UncompressedPoint {        
-        let p = UBig::from_be_bytes(&Curve::P);
+    pub fn lift_x_unchecked(x: &[u8;32]) -> UncompressedPoint {
+        // y = (x³ + 7)^((p+1)/4) mod p, which is only a square root if x is on curve
+        let x_3 = arithmetic::to_be_bytes(&arithmetic::curve_rhs(&arithmetic::from_be_bytes(x)));
+        UncompressedPoint(arithmetic::concat(x, &Self::sqrt_candidate(&x_3)))
+    }
+
+    /// ### Lift X coordinate to both points
+    /// 
+    /// Lifts an X coordinate to curve, returning the points with an even and an odd 𝑌-coordinate
+    /// in that order from a single square root, so callers that need a given parity neither
+    /// check nor invert the point themselves. Errors with `InvalidYCoordinate` if X is off curve.
+    pub fn lift_x_both(x: &[u8;32]) -> Result<(UncompressedPoint, UncompressedPoint), Secp256k1Error> {
+        let x_3 = arithmetic::to_be_bytes(&arithmetic::curve_rhs(&arithmetic::from_be_bytes(x)));
+        let y = Self::mod_sqrt(&x_3).ok_or(Secp256k1Error::InvalidYCoordinate)?;
+        Ok(Self::both_roots(x, &y))
+    }
+
+    /// ### Lift X coordinate to both points unchecked
+    /// 
+    /// As `lift_x_both`, without checking X is on curve. Only use this when you know you are
+    /// dealing with a valid point.
+    pub fn lift_x_both_unchecked(x: &[u8;32]) -> (UncompressedPoint, UncompressedPoint) {
+        let x_3 = arithmetic::to_be_bytes(&arithmetic::curve_rhs(&arithmetic::from_be_bytes(x)));
+        Self::both_roots(x, &Self::sqrt_candidate(&x_3))
+    }
+
+    /// Orders the points `(𝑥, 𝑦)` and `(𝑥, 𝑃 - 𝑦)` by the parity of their 𝑌-coordinates.
+    fn both_roots(x: &[u8; 32], y: &[u8; 32]) -> (UncompressedPoint, UncompressedPoint) {
+        let neg_y = Self::negate_p(y);
+        let (even, odd) = if y[31] & 1 == 0 { (y, &neg_y) } else { (&neg_y, y) };
+        (UncompressedPoint(arithmetic::concat(x, even)), UncompressedPoint(arithmetic::concat(x, odd)))
+    }
+
+    /// ### Mod Sqrt
+    /// 
+    /// Returns a square root of `𝒂` modulo 𝑃, or `None` if `𝒂` is not a quadratic residue. As
+    /// `𝑃 ≡ 3 mod 4`, the root is `𝒂^((𝑃+1)/4)`, calculated with the `sol_big_mod_exp` syscall
+    /// when the `big-mod-exp` feature is enabled. Either root may be returned, so negate it
+    /// with `negate_p` for the other parity.
+    pub fn mod_sqrt(a: &[u8; 32]) -> Option<[u8; 32]> {
+        let a = arithmetic::reduce(&arithmetic::from_be_bytes(a), &arithmetic::P);
+        let r = Self::sqrt_candidate(&arithmetic::to_be_bytes(&a));
+        let r_limbs = arithmetic::from_be_bytes(&r);
+        (arithmetic::mul_mod(&r_limbs, &r_limbs, &arithmetic::P) == a).then_some(r)
+    }
 
-        // Calculate right side: x³ + 7
-        let x_3 = UBig::from_be_bytes(x).cubic() + UBig::from_word(7);
-        
-        let divisor = ConstDivisor::new(p.clone());
-        let exp = x_3.into_ring(&divisor);
-        
-        // Calculate y = (x³ + 7)^((p+1)/4) mod p
-        let y = exp.pow(&(UBig::from_be_bytes(&Curve::P_1_4))).residue() % &p;
+    /// ### Is Quadratic Residue
+    /// 
+    /// Returns true if `𝒂` has a square root modulo 𝑃, by Euler's criterion: `𝒂^((𝑃-1)/2)` is
+    /// 1 for a residue, 𝑃-1 for a non-residue and 0 for `𝒂 ≡ 0`, which counts as a residue.
+    /// This costs a single exponentiation, with the `sol_big_mod_exp` syscall when the
+    /// `big-mod-exp` feature is enabled, where `mod_sqrt` also squares the root.
+    pub fn is_quadratic_residue(a: &[u8; 32]) -> bool {
+        let legendre = arithmetic::from_be_bytes(&Self::pow_mod_p(a, &Self::P_SUB_1_DIV_2));
+        legendre == [1, 0, 0, 0] || legendre == [0u64; 4]
+    }
 
-        let y_bytes = y.to_be_bytes();
+    /// Calculates `𝒂^((𝑃+1)/4) mod 𝑃`, the square root of `𝒂` if it has one.
+    fn sqrt_candidate(a: &[u8; 32]) -> [u8; 32] {
+        Self::pow_mod_p(a, &Self::P_1_4)
+    }
 
-        let mut point = [0u8;64];
-        point[..32].clone_from_slice(x);
-        point[64 - y_bytes.len()..].clone_from_slice(&y_bytes);
-        UncompressedPoint(point)
+    /// Calculates `𝒂^𝒆 mod 𝑃`, with `sol_big_mod_exp` when the `big-mod-exp` feature is enabled.
+    fn pow_mod_p(a: &[u8; 32], e: &[u8; 32]) -> [u8; 32] {
+        #[cfg(feature = "big-mod-exp")]
+        return backend::big_mod_exp(a, e, &Self::P);
+        #[cfg(not(feature = "big-mod-exp"))]
+        arithmetic::to_be_bytes(&arithmetic::pow_mod(&arithmetic::from_be_bytes(a), &arithmetic::from_be_bytes(e), &arithmetic::P))
     }
 
     /// # Fast Mod 𝑃
     /// 
-    /// Taking advantage of the fact that:
-    /// - This function is only used with 256-bit numbers
-    /// - We will almost never need to mod 𝑃 as 𝑃 is very large
-    /// - In the case that we do, we only need to handle the last 8 bytes.
+    /// Reduces a 256-bit big-endian integer modulo 𝑃 in place.
     /// 
-    /// We can optimize this beyond 
+    /// As 𝑃 > 2²⁵⁵, any 256-bit integer is less than 2𝑃, so a single conditional subtraction
+    /// suffices. The bytes are loaded into limbs explicitly as big-endian, so this behaves the
+    /// same on every target regardless of native endianness.
     pub fn fast_mod_p(a: &mut [u8; 32]) {
-        // Transmute the &mut [u8; 32] into &mut [u64; 4]
-        let a_u64: &mut [u64; 4] = unsafe { std::mem::transmute(a) };
-    
-        if a_u64[0] < u64::MAX || a_u64[1] < u64::MAX || a_u64[2] < u64::MAX {
-            return;
-        }
-    
-        let max = 0xfffffffefffffc2f;
-        if a_u64[3] >= max {
-            a_u64[0] = 0;
-            a_u64[1] = 0;
-            a_u64[2] = 0;
-            a_u64[3] -= max;
-        }
+        *a = arithmetic::to_be_bytes(&arithmetic::reduce(&arithmetic::from_be_bytes(a), &arithmetic::P));
     }
 
     /// # Fast Mod 𝑁
     /// 
-    /// Taking advantage of the fact that:
-    /// - This function is only used with 256-bit numbers
-    /// - We will almost never need to mod 𝑁 as 𝑁 is very large
-    /// - In the case that we do, we only need to handle the last 33 bytes.
+    /// Reduces a 256-bit big-endian integer modulo 𝑁 in place.
     /// 
-    /// While this may not necessarily be faster in all cases, it will be faster 
-    /// on average to veto modulus by the first limb.
+    /// As 𝑁 > 2²⁵⁵, any 256-bit integer is less than 2𝑁, so a single conditional subtraction
+    /// suffices. The bytes are loaded into limbs explicitly as big-endian, so this behaves the
+    /// same on every target regardless of native endianness.
     pub fn fast_mod_n(a: &mut [u8; 32]) {
-        // Transmute the &mut [u8; 32] into &mut [u64; 4]
-        let a_u64: &mut [u64; 4] = unsafe { std::mem::transmute(a) };
-    
-        // This will almost always be true. Skip to avoid allocating and comparing remaining limbs
-        if a_u64[0] < u64::MAX {
-            return
-        }
-
-        let n1 = u64::MAX-1;
-        let n2 = 0xbaaedce6af48a03bu64;
-        let n3 = 0xbfd25e8cd036413fu64;
-        
-        // This will also almost always be true, if not, we can directly set a_u64[0] to 0 and subtract the remaining limbs
-        if a_u64[1] < n1 && a_u64[2] < n2 && a_u64[3] < n3 {
-            return
-        }
-
-        // Subtraction with borrow propagation (from LSB to MSB)
-        let (new3, borrow3) = a_u64[3].overflowing_sub(n3);
-        let (new2, borrow2) = a_u64[2].overflowing_sub(n2 + borrow3 as u64);
-        let (new1, _) = a_u64[1].overflowing_sub(n1 + borrow2 as u64);
-
-        // Assign results back
-        a_u64[3] = new3;
-        a_u64[2] = new2;
-        a_u64[1] = new1;
-        a_u64[0] = 0;
+        *a = arithmetic::to_be_bytes(&arithmetic::reduce(&arithmetic::from_be_bytes(a), &arithmetic::N));
     }
 
     /// ### Negate
@@ -341,45 +307,53 @@ impl Curve {
     /// // `k` now contains the value (𝑁 - original_k) modulo 𝑁.
     /// ```
     /// 
-    pub fn negate_n(k: &[u8; 32]) -> [u8;32] {
-        let n = UBig::from_be_bytes(&Curve::N);
-        let x = ((&n + &n - UBig::from_be_bytes(k)) % &n).to_be_bytes();
-        let mut r = [0u8;32];
-        r[32-x.len()..].clone_from_slice(&x);
-        r
+    pub const fn negate_n(k: &[u8; 32]) -> [u8;32] {
+        arithmetic::to_be_bytes(&arithmetic::neg_mod(&arithmetic::from_be_bytes(k), &arithmetic::N))
     }
 
     pub fn negate_n_assign(k: &mut [u8; 32]) {
-        let n = UBig::from_be_bytes(&Curve::N);
-        let x = ((n.clone() + n.clone() - UBig::from_be_bytes(k)) % n.clone()).to_be_bytes();
-        k[..32-x.len()].clone_from_slice(&[0u8;32][..32-x.len()]);
-        k[32-x.len()..].clone_from_slice(&x);
+        *k = Self::negate_n(k);
     }
 
-    pub fn negate_p(k: &[u8; 32]) -> [u8;32] {
-        let p = UBig::from_be_bytes(&Curve::P);
-        let x = ((p.clone() + p.clone() - UBig::from_be_bytes(k)) % p.clone()).to_be_bytes();
-        let mut r = [0u8;32];
-        r.clone_from_slice(&x);
-        r
+    /// ### Conditional Negate 𝑁
+    /// 
+    /// Returns `𝑁 - 𝒌` if `choice` is set and `𝒌` otherwise, in constant time, eg: to negate a
+    /// secret key for an odd 𝑌-coordinate in BIP-340, or to normalize a high 𝑠.
+    pub const fn conditional_negate_n(k: &[u8; 32], choice: bool) -> [u8; 32] {
+        let k = arithmetic::from_be_bytes(k);
+        arithmetic::to_be_bytes(&arithmetic::select(&k, &arithmetic::neg_mod_ct(&k, &arithmetic::N), choice))
+    }
+
+    /// ### Is Zero
+    /// 
+    /// Returns true if `𝒌` is zero, in constant time.
+    pub const fn is_zero(k: &[u8; 32]) -> bool {
+        let k = arithmetic::from_be_bytes(k);
+        (k[0] | k[1] | k[2] | k[3]) == 0
+    }
+
+    /// ### Is High
+    /// 
+    /// Returns true if `𝒌 > 𝑁/2`, ie: a high 𝑠 rejected by low-𝑠 signature rules, in constant
+    /// time.
+    pub const fn is_high(k: &[u8; 32]) -> bool {
+        arithmetic::sbb(&arithmetic::from_be_bytes(&Self::N_DIV_2), &arithmetic::from_be_bytes(k)).1
+    }
+
+    /// ### Negate 𝑃
+    /// 
+    /// Negates the provided 32-byte value `𝒌` modulo the field prime 𝑃, as used to invert the
+    /// 𝑌-coordinate of a point.
+    /// 
+    /// Calculates `𝑃 - 𝒌` over fixed-width limbs with masked selects in place of branches, so
+    /// inputs of any magnitude, including `0` and values `≥ 𝑃`, take the same path.
+    pub const fn negate_p(k: &[u8; 32]) -> [u8;32] {
+        arithmetic::to_be_bytes(&arithmetic::neg_mod_ct(&arithmetic::from_be_bytes(k), &arithmetic::P))
     }
 
     pub fn negate_p_assign(k: &mut [u8; 32]) {
-        let p = UBig::from_be_bytes(&Curve::P);
-        let x = ((p.clone() + p.clone() - UBig::from_be_bytes(k)) % p.clone()).to_be_bytes();
-        k[..32-x.len()].clone_from_slice(&[0u8;32][..32-x.len()]);
-        k[32-x.len()..].clone_from_slice(&x);
-    }
-
-    // TODO: Check which is cheaper on CUs.
-    // pub fn negate_p_assign(k: &mut [u8; 32]) {
-    //     let mut borrow = 0u8;
-    //     for i in (0..32).rev() {
-    //         let (res, b) = Self::P[i].overflowing_sub(k[i] + borrow);
-    //         k[i] = res;
-    //         borrow = if b { 1 } else { 0 };
-    //     }
-    // }
+        *k = Self::negate_p(k);
+    }
 
     /// ### Modular Inverse 𝑁
     /// 
@@ -388,7 +362,7 @@ impl Curve {
     /// 
     /// The modulus with the exponent `𝑁-2`.
     /// 
-    /// The `big_mod_exp` function is used to efficiently compute the modular exponentiation.
+    /// The exponentiation is performed with fixed-width limb arithmetic.
     /// 
     /// # Example
     ///
@@ -405,11 +379,39 @@ impl Curve {
     /// // `inv_k` now contains the value of (𝒌⁻¹) modulo 𝑁.
     /// ```
     pub fn mod_inv_n(k: &[u8]) -> Result<[u8; 32], Secp256k1Error> {
-        let mut inv_k: [u8; 32] = [0u8; 32];
-        let ring = ConstDivisor::new(UBig::from_be_bytes(&Self::N));
-        let res = ring.reduce(UBig::from_be_bytes(k)).inv().ok_or(Secp256k1Error::ArithmeticOverflow)?.residue().to_be_bytes();
-        inv_k[32-res.len()..].clone_from_slice(&res);
-        Ok(inv_k)
+        let k = arithmetic::from_be_slice(k, &arithmetic::N);
+        let inv_k = arithmetic::inv_mod(&k, &arithmetic::N).ok_or(Secp256k1Error::ArithmeticOverflow)?;
+        Ok(arithmetic::to_be_bytes(&inv_k))
+    }
+
+    /// ### Batch Modular Inverse 𝑁
+    /// 
+    /// Calculates `𝒌ᵢ⁻¹ mod 𝑁` for every scalar with Montgomery's trick: a single inversion of
+    /// the product `𝒌₀·𝒌₁·…·𝒌ₘ₋₁`, unwound with three multiplications per scalar. Each inversion
+    /// costs around 290 multiplications, so this is far cheaper than `mod_inv_n` per scalar,
+    /// eg: for the `𝑠⁻¹` of every signature in a batch.
+    /// 
+    /// Scalars `≥ 𝑁` are reduced first. Errors with `ArithmeticOverflow` if any scalar is
+    /// zero, as `mod_inv_n` does, without revealing which.
+    pub fn batch_mod_inv_n<const M: usize>(k: &[[u8; 32]; M]) -> Result<[[u8; 32]; M], Secp256k1Error> {
+        const ONE: arithmetic::Limbs = [1, 0, 0, 0];
+        let k = k.map(|k| arithmetic::reduce(&arithmetic::from_be_bytes(&k), &arithmetic::N));
+        // prefix[𝑖] = 𝒌₀·𝒌₁·…·𝒌ᵢ
+        let mut prefix = [ONE; M];
+        let mut acc = ONE;
+        for (prefix, k) in prefix.iter_mut().zip(&k) {
+            acc = arithmetic::mul_mod(&acc, k, &arithmetic::N);
+            *prefix = acc;
+        }
+        let mut inv = arithmetic::inv_mod(&acc, &arithmetic::N).ok_or(Secp256k1Error::ArithmeticOverflow)?;
+        let mut out = [[0u8; 32]; M];
+        for i in (0..M).rev() {
+            // 𝒌ᵢ⁻¹ = (𝒌₀·…·𝒌ᵢ)⁻¹·(𝒌₀·…·𝒌ᵢ₋₁)
+            let before = if i == 0 { ONE } else { prefix[i - 1] };
+            out[i] = arithmetic::to_be_bytes(&arithmetic::mul_mod(&inv, &before, &arithmetic::N));
+            inv = arithmetic::mul_mod(&inv, &k[i], &arithmetic::N);
+        }
+        Ok(out)
     }
 
     /// ### Modular Inverse 𝑃
@@ -419,7 +421,7 @@ impl Curve {
     /// 
     /// The modulus with the exponent `𝑃-2`.
     /// 
-    /// The `big_mod_exp` function is used to efficiently compute the modular exponentiation.
+    /// The exponentiation is performed with fixed-width limb arithmetic.
     /// 
     /// # Example
     ///
@@ -437,17 +439,17 @@ impl Curve {
     /// // `inv_k` now contains the value of (𝒌⁻¹) modulo 𝑃.
     /// ```
     pub fn mod_inv_p(k: &[u8]) -> Result<[u8; 32], Secp256k1Error> {
-        let mut inv_k: [u8; 32] = [0u8; 32];
-        let ring = ConstDivisor::new(UBig::from_be_bytes(&Self::P));
-        let res = ring.reduce(UBig::from_be_bytes(k)).inv().ok_or(Secp256k1Error::ArithmeticOverflow)?.residue().to_be_bytes();
-        inv_k[32-res.len()..].clone_from_slice(&res);
-        Ok(inv_k)
+        let k = arithmetic::from_be_slice(k, &arithmetic::P);
+        let inv_k = arithmetic::inv_mod(&k, &arithmetic::P).ok_or(Secp256k1Error::ArithmeticOverflow)?;
+        Ok(arithmetic::to_be_bytes(&inv_k))
     }
 
     /// ### Mul 𝐺
     /// 
     /// Abuse Secp256k1Recover to Calculate the UncompressedPoint of Scalar `𝒌`
     /// 
+    /// Returns `ZeroScalar` if `𝒌` is zero and `ScalarOutOfRange` if `𝒌 ≥ 𝑁`.
+    /// 
     /// # Example
     ///
     /// ```rust
@@ -462,11 +464,16 @@ impl Curve {
     /// let p = Curve::mul_g(&k);
     /// ```
     pub fn mul_g(k: &[u8;32]) -> Result<UncompressedPoint, Secp256k1Error> {
-        let result = Self::mul_mod_n(&k, &Self::G.x());
-        let mut s = [0u8;64];
-        s[..32].clone_from_slice(&Self::G.x());
-        s[32..].clone_from_slice(&result);
-        Ok(UncompressedPoint(secp256k1_recover(&[0u8; 32], false, &s)?))
+        Self::check_scalar(k)?;
+        Self::mul_g_unchecked(k)
+    }
+
+    /// ### Mul 𝐺 Unchecked
+    /// 
+    /// As `mul_g`, without checking that `𝒌` is in the range `1..𝑁`. Scalars `≥ 𝑁` are
+    /// implicitly reduced. Only use this when `𝒌` is already known to be a valid scalar.
+    pub fn mul_g_unchecked(k: &[u8;32]) -> Result<UncompressedPoint, Secp256k1Error> {
+        Ok(UncompressedPoint(backend::mul_g(k)?))
     }
 
     /// ### Ecmul
@@ -478,6 +485,8 @@ impl Curve {
     /// 
     /// This unlocks efficient implementations of ECDH, Pedersen commitments.
     /// 
+    /// Returns `ZeroScalar` if `𝒌` is zero and `ScalarOutOfRange` if `𝒌 ≥ 𝑁`.
+    /// 
     /// # Example
     ///
     /// ```rust
@@ -499,12 +508,183 @@ impl Curve {
     /// // A compressed or uncompressed point
     /// let p = Curve::ecmul::<CompressedPoint>(&point, &k);
     /// ```
-    
     pub fn ecmul<T: Secp256k1Point>(point: &T, k: &[u8;32]) -> Result<UncompressedPoint, Secp256k1Error> {
-        let result = Self::mul_mod_n(&point.x(), k);
-        let mut s = [0u8;64];
-        s[..32].clone_from_slice(&point.x());
-        s[32..].clone_from_slice(&result);
-        Ok(UncompressedPoint(secp256k1_recover(&[0u8; 32], point.is_odd(), &s)?))
+        Self::check_scalar(k)?;
+        Self::ecmul_unchecked(point, k)
+    }
+
+    /// ### Ecmul Unchecked
+    /// 
+    /// As `ecmul`, without checking that `𝒌` is in the range `1..𝑁`. Scalars `≥ 𝑁` are
+    /// implicitly reduced. Only use this when `𝒌` is already known to be a valid scalar.
+    pub fn ecmul_unchecked<T: Secp256k1Point>(point: &T, k: &[u8;32]) -> Result<UncompressedPoint, Secp256k1Error> {
+        Ok(UncompressedPoint(backend::ecmul(point.x_ref(), point.is_odd(), k)?))
+    }
+
+    /// ### Double Mul
+    /// 
+    /// Calculates `𝒂·𝐺 + 𝒃·𝑃`, the shape at the heart of ECDSA and Schnorr verification, with a
+    /// single recover rather than two `ecmul`s and an affine addition. Either scalar may be zero.
+    /// 
+    /// Returns `ScalarOutOfRange` if `𝒂 ≥ 𝑁` or `𝒃 ≥ 𝑁`, and `IdentityPoint` if the sum is the
+    /// point at infinity. As with `ecmul`, the recover takes `𝑃ₓ` as its 𝑟, so on-chain points
+    /// with `𝑃ₓ ≥ 𝑁` are rejected.
+    pub fn double_mul<T: Secp256k1Point>(a: &[u8;32], point: &T, b: &[u8;32]) -> Result<UncompressedPoint, Secp256k1Error> {
+        if *a >= Self::N || *b >= Self::N {
+            return Err(Secp256k1Error::ScalarOutOfRange);
+        }
+        Self::double_mul_unchecked(a, point, b)
+    }
+
+    /// ### Double Mul Unchecked
+    /// 
+    /// As `double_mul`, without checking that `𝒂` and `𝒃` are less than `𝑁`. Scalars `≥ 𝑁` are
+    /// implicitly reduced.
+    pub fn double_mul_unchecked<T: Secp256k1Point>(a: &[u8;32], point: &T, b: &[u8;32]) -> Result<UncompressedPoint, Secp256k1Error> {
+        Ok(UncompressedPoint(backend::double_mul(a, point.x_ref(), point.is_odd(), b)?))
+    }
+
+    /// ### Ecadd
+    /// 
+    /// Calculates `𝑃 + 𝑄`, erroring with `IdentityPoint` if `𝑄 = -𝑃`.
+    /// 
+    /// Recover can only add multiples of `𝐺`, so the sum goes through a recover where one is
+    /// known: doubling is an `ecmul` by 2, and adding `±𝐺` is a `tweak` by `±1`, each costing
+    /// about 27k CUs. Any other sum falls back to affine addition, whose field inversion costs
+    /// about 440k CUs on-chain, plus a recover to decompress each `CompressedPoint`. When `𝑄`
+    /// is `𝒕·𝐺` for a known `𝒕`, use `tweak` instead.
+    pub fn ecadd<T: Secp256k1Point, U: Secp256k1Point>(p: &T, q: &U) -> Result<UncompressedPoint, Secp256k1Error> {
+        const ONE: [u8; 32] = { let mut k = [0u8; 32]; k[31] = 1; k };
+        const TWO: [u8; 32] = { let mut k = [0u8; 32]; k[31] = 2; k };
+        let g = Self::G.x_ref();
+        let sign = |is_odd: bool| if is_odd { Self::negate_n(&ONE) } else { ONE };
+        if p.x_ref() == q.x_ref() {
+            if p.is_odd() != q.is_odd() {
+                return Err(Secp256k1Error::IdentityPoint);
+            }
+            return Self::ecmul_unchecked(p, &TWO);
+        }
+        if q.x_ref() == g {
+            return Ok(UncompressedPoint(backend::tweak(p.x_ref(), p.is_odd(), &sign(q.is_odd()))?));
+        }
+        if p.x_ref() == g {
+            return Ok(UncompressedPoint(backend::tweak(q.x_ref(), q.is_odd(), &sign(p.is_odd()))?));
+        }
+        let (p, q) = (p.decompress()?, q.decompress()?);
+        arithmetic::affine_add_checked(&arithmetic::point_from_be_bytes(&p.0), &arithmetic::point_from_be_bytes(&q.0))
+            .map(|r| UncompressedPoint(arithmetic::point_to_be_bytes(&r)))
+            .ok_or(Secp256k1Error::IdentityPoint)
+    }
+
+    /// ### Ecadd Many
+    /// 
+    /// Calculates `Σ 𝑃ᵢ`, erroring with `IdentityPoint` if the sum is empty or the point at
+    /// infinity. Points are summed in Jacobian coordinates, so each costs about 18k CUs plus a
+    /// recover to decompress a `CompressedPoint`, with one field inversion at the end instead
+    /// of one per `ecadd`. To split a long sum across instructions within a CU budget, use
+    /// `msm::AddCursor`.
+    pub fn ecadd_many<T: Secp256k1Point>(points: &[T]) -> Result<UncompressedPoint, Secp256k1Error> {
+        let mut cursor = msm::AddCursor::new(points)?;
+        cursor.step(points, u64::MAX)?;
+        cursor.result()
+    }
+
+    /// ### Verify ECDSA
+    /// 
+    /// Verifies a low-𝑠 `𝑟‖𝑠` signature over `hash` against a compressed public key, rejecting
+    /// high-𝑠 signatures to prevent malleability.
+    /// 
+    /// Without a recovery id, the signer is recovered with each parity of `𝑅` in turn and
+    /// compared to `pubkey`, costing at most two recovers. When `𝑟 + 𝑁 < 𝑃`, which happens for
+    /// roughly a 2⁻¹²⁸ fraction of signatures, `𝑅ₓ` may also be `𝑟 + 𝑁`, the case of recovery
+    /// ids 2 and 3. Recover only takes a parity, so that case is checked directly as
+    /// `(𝑧𝑠⁻¹·𝐺 + 𝑟𝑠⁻¹·𝑄)ₓ = 𝑟 + 𝑁` with one more recover.
+    pub fn verify_ecdsa(hash: &[u8;32], signature: &[u8;64], pubkey: &CompressedPoint) -> Result<(), Secp256k1Error> {
+        let (r, s) = arithmetic::split(signature);
+        if Self::is_zero(&r) || r >= Self::N || Self::is_zero(&s) || Self::is_high(&s) {
+            return Err(Secp256k1Error::InvalidSignature);
+        }
+        for is_odd in [false, true] {
+            if let Ok(signer) = secp256k1_recover(hash, is_odd, signature) {
+                if UncompressedPoint(signer).compress() == *pubkey {
+                    return Ok(());
+                }
+            }
+        }
+
+        let (r_plus_n, carry) = arithmetic::adc(&arithmetic::from_be_bytes(&r), &arithmetic::N.m);
+        let r_plus_n = arithmetic::to_be_bytes(&r_plus_n);
+        if !carry && r_plus_n < Self::P {
+            let s_inv = Self::mod_inv_n(&s)?;
+            let u1 = Self::mul_mod_n(&Self::add_mod_n(hash, &[0u8; 32]), &s_inv);
+            let u2 = Self::mul_mod_n(&r, &s_inv);
+            if let Ok(big_r) = Self::double_mul(&u1, pubkey, &u2) {
+                if big_r.x() == r_plus_n {
+                    return Ok(());
+                }
+            }
+        }
+        Err(Secp256k1Error::InvalidSignature)
+    }
+
+    /// ### Verify ECDSA Message
+    ///
+    /// As `verify_ecdsa`, over a hashed `Message`.
+    pub fn verify_ecdsa_message(message: &Message, signature: &[u8; 64], pubkey: &CompressedPoint) -> Result<(), Secp256k1Error> {
+        Self::verify_ecdsa(message.as_bytes(), signature, pubkey)
+    }
+
+    /// Checks that `𝒌` is a valid scalar in the range `1..𝑁`.
+    fn check_scalar(k: &[u8;32]) -> Result<(), Secp256k1Error> {
+        if *k == [0u8; 32] {
+            return Err(Secp256k1Error::ZeroScalar);
+        }
+        if *k >= Self::N {
+            return Err(Secp256k1Error::ScalarOutOfRange);
+        }
+        Ok(())
+    }
+}
+impl WeierstrassCurve for Curve {
+    const N: [u8; 32] = Curve::N;
+    const P: [u8; 32] = Curve::P;
+    const A: [u8; 32] = [0u8; 32];
+    const B: [u8; 32] = arithmetic::to_be_bytes(&[7, 0, 0, 0]);
+    const G: [u8; 64] = Curve::G.0;
+
+    fn add_mod_n(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        Curve::add_mod_n(a, b)
+    }
+
+    fn mul_mod_n(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        Curve::mul_mod_n(a, b)
+    }
+
+    fn negate_n(k: &[u8; 32]) -> [u8; 32] {
+        Curve::negate_n(k)
+    }
+
+    fn mod_inv_n(k: &[u8]) -> Result<[u8; 32], Secp256k1Error> {
+        Curve::mod_inv_n(k)
+    }
+
+    fn add_mod_p(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        Curve::add_mod_p(a, b)
+    }
+
+    fn mul_mod_p(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        Curve::mul_mod_p(a, b)
+    }
+
+    fn negate_p(k: &[u8; 32]) -> [u8; 32] {
+        Curve::negate_p(k)
+    }
+
+    fn mod_inv_p(k: &[u8]) -> Result<[u8; 32], Secp256k1Error> {
+        Curve::mod_inv_p(k)
+    }
+
+    fn sqrt_mod_p(a: &[u8; 32]) -> Option<[u8; 32]> {
+        Curve::mod_sqrt(a)
     }
 }
@@ -0,0 +1,233 @@
+//! # DER
+//!
+//! Strict parsing of the DER encodings cloud signers, HSMs and OpenSSL use, such as AWS KMS:
+//! ECDSA signatures as `SEQUENCE { r INTEGER, s INTEGER }`, secp256k1 public keys as X.509
+//! `SubjectPublicKeyInfo`, and secret keys as SEC1 `ECPrivateKey` or PKCS#8 `PrivateKeyInfo`.
+//! Only the canonical encoding of each value is accepted, so a signature or public key has
+//! exactly one valid encoding. Secret keys may omit their optional curve and public key fields,
+//! and are encoded the way OpenSSL writes them.
+
+use crate::{CompressedPoint, Secp256k1Error, Secp256k1Point, SecretKey, UncompressedPoint};
+
+/// The DER `AlgorithmIdentifier` of a secp256k1 key, `SEQUENCE { id-ecPublicKey, secp256k1 }`.
+pub const ALGORITHM_IDENTIFIER: [u8; 18] = [
+    0x30, 0x10, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x0a,
+];
+
+/// The `SubjectPublicKeyInfo` header of an uncompressed secp256k1 key, `SEQUENCE {
+/// SEQUENCE { id-ecPublicKey, secp256k1 }, BIT STRING }`, followed by the 65-byte SEC1 point.
+pub const SPKI_UNCOMPRESSED_PREFIX: [u8; 23] = [
+    0x30, 0x56, 0x30, 0x10, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x05, 0x2b, 0x81, 0x04,
+    0x00, 0x0a, 0x03, 0x42, 0x00,
+];
+
+/// The `SubjectPublicKeyInfo` header of a compressed secp256k1 key, followed by the 33-byte
+/// SEC1 point.
+pub const SPKI_COMPRESSED_PREFIX: [u8; 23] = [
+    0x30, 0x36, 0x30, 0x10, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x05, 0x2b, 0x81, 0x04,
+    0x00, 0x0a, 0x03, 0x22, 0x00,
+];
+
+/// ### Parse Signature
+///
+/// Parses a DER `SEQUENCE { r INTEGER, s INTEGER }` ECDSA signature into `𝑟‖𝑠`. Errors with
+/// `InvalidSignatureEncoding` on trailing data, non-minimal lengths or integers, negative
+/// integers, or integers of more than 32 bytes. `𝑟` and `𝑠` are not range checked.
+pub fn parse_signature(der: &[u8]) -> Result<[u8; 64], Secp256k1Error> {
+    // Both integers fit in at most 70 bytes, so the sequence length is always in short form
+    let [0x30, len, body @ ..] = der else {
+        return Err(Secp256k1Error::InvalidSignatureEncoding);
+    };
+    if *len as usize != body.len() || *len > 0x7f {
+        return Err(Secp256k1Error::InvalidSignatureEncoding);
+    }
+    let (r, body) = parse_integer(body)?;
+    let (s, body) = parse_integer(body)?;
+    if !body.is_empty() {
+        return Err(Secp256k1Error::InvalidSignatureEncoding);
+    }
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&r);
+    signature[32..].copy_from_slice(&s);
+    Ok(signature)
+}
+
+/// ### Encode Signature
+///
+/// Encodes an `𝑟‖𝑠` ECDSA signature as DER into `out`, returning the encoding, which is at most
+/// 72 bytes long.
+pub fn encode_signature<'a>(signature: &[u8; 64], out: &'a mut [u8; 72]) -> &'a [u8] {
+    let mut len = 2;
+    for k in signature.chunks(32) {
+        // Strip leading zeros, keeping one for zero itself, then restore one to clear the sign bit
+        let k = &k[k.iter().position(|b| *b != 0).unwrap_or(31)..];
+        let pad = (k[0] & 0x80 != 0) as usize;
+        out[len..len + 2].copy_from_slice(&[0x02, (k.len() + pad) as u8]);
+        out[len + 2] = 0;
+        out[len + 2 + pad..len + 2 + pad + k.len()].copy_from_slice(k);
+        len += 2 + pad + k.len();
+    }
+    out[..2].copy_from_slice(&[0x30, (len - 2) as u8]);
+    &out[..len]
+}
+
+/// ### Parse SPKI
+///
+/// Parses a DER `SubjectPublicKeyInfo` secp256k1 public key, in either SEC1 encoding, and
+/// checks it is on curve. Errors with `InvalidPublicKey` on any other algorithm, curve or
+/// encoding.
+pub fn parse_spki(der: &[u8]) -> Result<UncompressedPoint, Secp256k1Error> {
+    if let Some(point) = der.strip_prefix(&SPKI_UNCOMPRESSED_PREFIX) {
+        let point: &[u8; 65] = point.try_into().map_err(|_| Secp256k1Error::InvalidPublicKey)?;
+        return UncompressedPoint::from_sec1(point);
+    }
+    let point = der.strip_prefix(&SPKI_COMPRESSED_PREFIX).ok_or(Secp256k1Error::InvalidPublicKey)?;
+    let point: &[u8; 33] = point.try_into().map_err(|_| Secp256k1Error::InvalidPublicKey)?;
+    CompressedPoint::from_sec1_unchecked(point).decompress()
+}
+
+/// ### Encode SPKI
+///
+/// Encodes an uncompressed `SubjectPublicKeyInfo` public key, as OpenSSL and AWS KMS do.
+pub fn encode_spki(pubkey: &UncompressedPoint) -> [u8; 88] {
+    let mut der = [0u8; 88];
+    der[..23].copy_from_slice(&SPKI_UNCOMPRESSED_PREFIX);
+    der[23..].copy_from_slice(&pubkey.to_sec1_bytes());
+    der
+}
+
+/// ### Encode SPKI Compressed
+///
+/// Encodes a compressed `SubjectPublicKeyInfo` public key.
+pub fn encode_spki_compressed(pubkey: &CompressedPoint) -> [u8; 56] {
+    let mut der = [0u8; 56];
+    der[..23].copy_from_slice(&SPKI_COMPRESSED_PREFIX);
+    der[23..].copy_from_slice(&pubkey.0);
+    der
+}
+
+/// ### Parse EC Private Key
+///
+/// Parses a SEC1 `ECPrivateKey`, `SEQUENCE { 1, privateKey OCTET STRING, [0] parameters
+/// OPTIONAL, [1] publicKey OPTIONAL }`, as written by `openssl ec -outform DER`. Errors with
+/// `InvalidSecretKey` if it is malformed, for another curve, or the key is out of range, and
+/// with `InvalidPublicKey` if the embedded public key does not belong to the secret key.
+pub fn parse_ec_private_key(der: &[u8]) -> Result<SecretKey, Secp256k1Error> {
+    let body = read_only(der, 0x30).ok_or(Secp256k1Error::InvalidSecretKey)?;
+    let (version, body) = read(body, 0x02).ok_or(Secp256k1Error::InvalidSecretKey)?;
+    let (key, mut body) = read(body, 0x04).ok_or(Secp256k1Error::InvalidSecretKey)?;
+    if version != [1] {
+        return Err(Secp256k1Error::InvalidSecretKey);
+    }
+    let key = SecretKey::from_bytes(key.try_into().map_err(|_| Secp256k1Error::InvalidSecretKey)?)?;
+    if body.first() == Some(&0xa0) {
+        let (parameters, rest) = read(body, 0xa0).ok_or(Secp256k1Error::InvalidSecretKey)?;
+        if *parameters != ALGORITHM_IDENTIFIER[11..] {
+            return Err(Secp256k1Error::InvalidSecretKey);
+        }
+        body = rest;
+    }
+    if body.first() == Some(&0xa1) {
+        let (pubkey, rest) = read(body, 0xa1).ok_or(Secp256k1Error::InvalidSecretKey)?;
+        let pubkey = read_only(pubkey, 0x03).and_then(|bits| bits.strip_prefix(&[0])).ok_or(Secp256k1Error::InvalidSecretKey)?;
+        let pubkey = match pubkey.len() {
+            65 => UncompressedPoint::from_sec1(pubkey.try_into().expect("65 bytes"))?,
+            33 => CompressedPoint::from_sec1_unchecked(pubkey.try_into().expect("33 bytes")).decompress()?,
+            _ => return Err(Secp256k1Error::InvalidPublicKey),
+        };
+        if pubkey != key.public_key()? {
+            return Err(Secp256k1Error::InvalidPublicKey);
+        }
+        body = rest;
+    }
+    if !body.is_empty() {
+        return Err(Secp256k1Error::InvalidSecretKey);
+    }
+    Ok(key)
+}
+
+/// ### Encode EC Private Key
+///
+/// Encodes a SEC1 `ECPrivateKey` with its curve and uncompressed public key, byte for byte as
+/// `openssl ec -outform DER` does.
+pub fn encode_ec_private_key(key: &SecretKey) -> Result<[u8; 118], Secp256k1Error> {
+    let mut der = [0u8; 118];
+    der[..7].copy_from_slice(&[0x30, 0x74, 0x02, 0x01, 0x01, 0x04, 0x20]);
+    der[7..39].copy_from_slice(key.as_bytes());
+    der[39..41].copy_from_slice(&[0xa0, 0x07]);
+    der[41..48].copy_from_slice(&ALGORITHM_IDENTIFIER[11..]);
+    der[48..53].copy_from_slice(&[0xa1, 0x44, 0x03, 0x42, 0x00]);
+    der[53..].copy_from_slice(&key.public_key()?.to_sec1_bytes());
+    Ok(der)
+}
+
+/// ### Parse PKCS#8
+///
+/// Parses a PKCS#8 `PrivateKeyInfo`, `SEQUENCE { 0, AlgorithmIdentifier, privateKey OCTET
+/// STRING }`, wrapping a SEC1 `ECPrivateKey` as in `parse_ec_private_key`. Errors with
+/// `InvalidSecretKey` if it is malformed, for another algorithm or curve, or has attributes.
+pub fn parse_pkcs8(der: &[u8]) -> Result<SecretKey, Secp256k1Error> {
+    let body = read_only(der, 0x30).ok_or(Secp256k1Error::InvalidSecretKey)?;
+    let (version, body) = read(body, 0x02).ok_or(Secp256k1Error::InvalidSecretKey)?;
+    let body = body.strip_prefix(&ALGORITHM_IDENTIFIER).ok_or(Secp256k1Error::InvalidSecretKey)?;
+    let key = read_only(body, 0x04).ok_or(Secp256k1Error::InvalidSecretKey)?;
+    if version != [0] {
+        return Err(Secp256k1Error::InvalidSecretKey);
+    }
+    parse_ec_private_key(key)
+}
+
+/// ### Encode PKCS#8
+///
+/// Encodes a PKCS#8 `PrivateKeyInfo` wrapping an `ECPrivateKey` with its uncompressed public
+/// key, byte for byte as `openssl pkcs8 -topk8 -nocrypt -outform DER` does.
+pub fn encode_pkcs8(key: &SecretKey) -> Result<[u8; 135], Secp256k1Error> {
+    let mut der = [0u8; 135];
+    der[..6].copy_from_slice(&[0x30, 0x81, 0x84, 0x02, 0x01, 0x00]);
+    der[6..24].copy_from_slice(&ALGORITHM_IDENTIFIER);
+    der[24..33].copy_from_slice(&[0x04, 0x6d, 0x30, 0x6b, 0x02, 0x01, 0x01, 0x04, 0x20]);
+    der[33..65].copy_from_slice(key.as_bytes());
+    der[65..70].copy_from_slice(&[0xa1, 0x44, 0x03, 0x42, 0x00]);
+    der[70..].copy_from_slice(&key.public_key()?.to_sec1_bytes());
+    Ok(der)
+}
+
+/// Reads a DER value with the given tag and a minimal length of at most 2 bytes, returning it
+/// and the rest of the input.
+fn read(der: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    let (len, rest) = match der {
+        [t, len @ 0..=0x7f, rest @ ..] if *t == tag => (*len as usize, rest),
+        [t, 0x81, len @ 0x80..=0xff, rest @ ..] if *t == tag => (*len as usize, rest),
+        _ => return None,
+    };
+    (rest.len() >= len).then(|| rest.split_at(len))
+}
+
+/// Reads a DER value with the given tag that spans the whole input.
+fn read_only(der: &[u8], tag: u8) -> Option<&[u8]> {
+    read(der, tag).and_then(|(value, rest)| rest.is_empty().then_some(value))
+}
+
+/// Parses a positive DER `INTEGER` of at most 32 bytes, returning it left-padded to 32 bytes
+/// and the rest of the input.
+fn parse_integer(der: &[u8]) -> Result<([u8; 32], &[u8]), Secp256k1Error> {
+    let [0x02, len, rest @ ..] = der else {
+        return Err(Secp256k1Error::InvalidSignatureEncoding);
+    };
+    let len = *len as usize;
+    if len == 0 || len > 33 || rest.len() < len {
+        return Err(Secp256k1Error::InvalidSignatureEncoding);
+    }
+    let (value, rest) = rest.split_at(len);
+    // The sign bit must be clear, and a leading zero is only allowed to clear it
+    let value = match value {
+        [b, ..] if b & 0x80 != 0 => return Err(Secp256k1Error::InvalidSignatureEncoding),
+        [0, b, ..] if b & 0x80 == 0 => return Err(Secp256k1Error::InvalidSignatureEncoding),
+        [0, value @ ..] if len == 33 => value,
+        _ if len == 33 => return Err(Secp256k1Error::InvalidSignatureEncoding),
+        value => value,
+    };
+    let mut out = [0u8; 32];
+    out[32 - value.len()..].copy_from_slice(value);
+    Ok((out, rest))
+}
@@ -0,0 +1,84 @@
+//! # DID
+//!
+//! `did:key` identifiers and multicodec public keys, so decentralized identity integrations can
+//! move between DIDs and this crate's point types. A secp256k1 key is the multicodec
+//! `secp256k1-pub` prefix `0xe7`, as the varint `0xe7 0x01`, followed by the compressed point.
+//! Its `did:key` is `"did:key:z" ‖ base58btc(multicodec)`, which always starts `did:key:zQ3s`,
+//! and the multibase part alone is a verification method's `publicKeyMultibase`.
+
+use crate::{base58, CompressedPoint, Secp256k1Error, Secp256k1Point};
+
+/// The varint encoded multicodec of a compressed secp256k1 public key.
+pub const SECP256K1_PUB_MULTICODEC: [u8; 2] = [0xe7, 0x01];
+
+/// The length of a base58btc multibase public key.
+pub const MULTIBASE_LEN: usize = 49;
+
+/// The length of a `did:key` identifier.
+pub const DID_KEY_LEN: usize = 8 + MULTIBASE_LEN;
+
+/// ### To Multicodec
+///
+/// Prefixes a compressed public key with its multicodec.
+pub fn to_multicodec(pubkey: &CompressedPoint) -> [u8; 35] {
+    let mut bytes = [0u8; 35];
+    bytes[..2].copy_from_slice(&SECP256K1_PUB_MULTICODEC);
+    bytes[2..].copy_from_slice(&pubkey.0);
+    bytes
+}
+
+/// ### From Multicodec
+///
+/// Parses a multicodec prefixed compressed public key. Errors with `InvalidPublicKey` on
+/// another multicodec or if the point is not on curve.
+pub fn from_multicodec(bytes: &[u8]) -> Result<CompressedPoint, Secp256k1Error> {
+    let pubkey = match bytes {
+        [0xe7, 0x01, key @ ..] => CompressedPoint(key.try_into().map_err(|_| Secp256k1Error::InvalidPublicKey)?),
+        _ => return Err(Secp256k1Error::InvalidPublicKey),
+    };
+    pubkey.decompress().map_err(|_| Secp256k1Error::InvalidPublicKey)?;
+    Ok(pubkey)
+}
+
+/// ### Encode Multibase
+///
+/// Encodes a public key as a base58btc multibase string, `"z" ‖ base58btc(multicodec)`.
+pub fn encode_multibase<'a>(pubkey: &CompressedPoint, out: &'a mut [u8; MULTIBASE_LEN]) -> &'a str {
+    out[0] = b'z';
+    let len = base58::encode(&to_multicodec(pubkey), &mut out[1..]);
+    debug_assert_eq!(len, MULTIBASE_LEN - 1);
+    core::str::from_utf8(out).expect("ASCII multibase")
+}
+
+/// ### Parse Multibase
+///
+/// Parses a base58btc multibase public key. Errors with `InvalidPublicKey` on another
+/// multibase, multicodec or a malformed or off-curve key.
+pub fn parse_multibase(multibase: &str) -> Result<CompressedPoint, Secp256k1Error> {
+    let base58 = multibase.strip_prefix('z').ok_or(Secp256k1Error::InvalidPublicKey)?;
+    from_multicodec(base58::decode(base58.as_bytes(), &mut [0u8; 35]).ok_or(Secp256k1Error::InvalidPublicKey)?)
+}
+
+/// ### Encode DID Key
+///
+/// Encodes a public key as a `did:key` identifier.
+pub fn encode_did_key<'a>(pubkey: &CompressedPoint, out: &'a mut [u8; DID_KEY_LEN]) -> &'a str {
+    out[..8].copy_from_slice(b"did:key:");
+    encode_multibase(pubkey, (&mut out[8..]).try_into().expect("multibase length"));
+    core::str::from_utf8(out).expect("ASCII did:key")
+}
+
+/// ### Parse DID Key
+///
+/// Parses a `did:key` identifier, or the DID URL of its verification method,
+/// `did:key:z…#z…`, whose fragment must repeat the key. Errors with `InvalidPublicKey` on any
+/// other DID or a malformed key.
+pub fn parse_did_key(did: &str) -> Result<CompressedPoint, Secp256k1Error> {
+    let did = did.strip_prefix("did:key:").ok_or(Secp256k1Error::InvalidPublicKey)?;
+    let multibase = match did.split_once('#') {
+        Some((multibase, fragment)) if multibase == fragment => multibase,
+        Some(_) => return Err(Secp256k1Error::InvalidPublicKey),
+        None => did,
+    };
+    parse_multibase(multibase)
+}
@@ -0,0 +1,164 @@
+//! # DLC
+//!
+//! Discreet Log Contract oracles, so programs settling DLC-style contracts can consume the
+//! announcements and attestations of Bitcoin oracles directly. An oracle announces an event by
+//! publishing one x-only nonce point `𝑅ᵢ` per outcome it will sign, then attests to the outcome
+//! with the BIP-340 signature `𝑅ᵢₓ‖𝑠ᵢ` over `tagged_hash("DLC/oracle/attestation/v0",
+//! outcome)`, using the announced nonce. Each `𝑠ᵢ` is the discrete log of the attestation point
+//! `𝑆ᵢ = 𝑅ᵢ + 𝑒ᵢ·𝑃`, which contracts use as an adaptor point before the outcome is known.
+//!
+//! Numeric events are decomposed into `D` digits in a given base, most significant first, with
+//! a nonce and attestation per digit whose outcome is the digit in decimal, eg: `"1"`.
+
+use crate::{arithmetic, bip340, Curve, Secp256k1Error, Secp256k1Point, SecretKey, UncompressedPoint};
+
+/// The tag of an attestation's message hash.
+pub const ATTESTATION_TAG: &[u8] = b"DLC/oracle/attestation/v0";
+
+/// ### Announcement
+///
+/// An oracle's x-only public key and the x-only nonce points it will attest to the `D` digits
+/// of an event with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Announcement<const D: usize> {
+    pub oracle: [u8; 32],
+    pub nonces: [[u8; 32]; D],
+}
+
+impl<const D: usize> Announcement<D> {
+    /// ### New
+    ///
+    /// Announces an event with one secret nonce per digit. Each nonce must be freshly random,
+    /// kept secret until attesting, and never reused, as two attestations with the same nonce
+    /// leak the oracle's key.
+    pub fn new(key: &SecretKey, nonces: &[[u8; 32]; D]) -> Result<Self, Secp256k1Error> {
+        let mut points = [[0u8; 32]; D];
+        for (point, nonce) in points.iter_mut().zip(nonces) {
+            *point = Curve::mul_g(nonce)?.x();
+        }
+        Ok(Self { oracle: key.public_key()?.x(), nonces: points })
+    }
+
+    /// ### Verify Numeric
+    ///
+    /// Verifies an attestation to the digits of a numeric event and returns its value. Errors
+    /// with `InvalidPayload` on a digit outside `base` or a value above `u64::MAX`, and
+    /// `InvalidSignature` if any digit's attestation does not verify.
+    pub fn verify_numeric(&self, base: u8, digits: &[u8; D], signatures: &[[u8; 32]; D]) -> Result<u64, Secp256k1Error> {
+        let value = compose(digits, base)?;
+        for ((nonce, signature), digit) in self.nonces.iter().zip(signatures).zip(digits) {
+            verify_attestation(&self.oracle, nonce, digit_outcome(*digit, &mut [0u8; 3]), signature)?;
+        }
+        Ok(value)
+    }
+}
+
+/// ### Attestation Message
+///
+/// The 32-byte message `tagged_hash("DLC/oracle/attestation/v0", outcome)` an oracle signs.
+pub fn attestation_message(outcome: &[u8]) -> [u8; 32] {
+    bip340::tagged_hash(ATTESTATION_TAG, &[outcome])
+}
+
+/// ### Attest
+///
+/// Oracle side: signs `outcome` with the announced secret `nonce`, returning `𝑠`. The full
+/// BIP-340 signature is `𝑅ₓ‖𝑠`. Errors with `ZeroScalar` if `nonce` is zero.
+pub fn attest(key: &SecretKey, nonce: &[u8; 32], outcome: &[u8]) -> Result<[u8; 32], Secp256k1Error> {
+    if Curve::is_zero(nonce) {
+        return Err(Secp256k1Error::ZeroScalar);
+    }
+    let (_, s) = arithmetic::split(&bip340::sign_with_nonce(key, nonce, &attestation_message(outcome))?);
+    Ok(s)
+}
+
+/// ### Attest Numeric
+///
+/// Oracle side: attests to `value` in `base`, returning the `𝑠ᵢ` of each digit, most
+/// significant first. `nonces` are the secret nonces the event was announced with. Errors
+/// with `InvalidPayload` if `value` needs more than `D` digits.
+pub fn attest_numeric<const D: usize>(
+    key: &SecretKey,
+    nonces: &[[u8; 32]; D],
+    base: u8,
+    value: u64,
+) -> Result<[[u8; 32]; D], Secp256k1Error> {
+    let digits = decompose::<D>(value, base)?;
+    let mut signatures = [[0u8; 32]; D];
+    for ((signature, nonce), digit) in signatures.iter_mut().zip(nonces).zip(digits) {
+        *signature = attest(key, nonce, digit_outcome(digit, &mut [0u8; 3]))?;
+    }
+    Ok(signatures)
+}
+
+/// ### Verify Attestation
+///
+/// Verifies the attestation `𝑠` to `outcome` by `oracle` with the announced nonce point
+/// `nonce`, erroring with `InvalidSignature` on failure.
+pub fn verify_attestation(oracle: &[u8; 32], nonce: &[u8; 32], outcome: &[u8], s: &[u8; 32]) -> Result<(), Secp256k1Error> {
+    bip340::verify(oracle, &attestation_message(outcome), &arithmetic::concat(nonce, s))
+}
+
+/// ### Attestation Point
+///
+/// Calculates the attestation point `𝑆 = 𝑅 + 𝑒·𝑃` of `outcome`, whose discrete log is the
+/// oracle's eventual `𝑠`, so contracts can be prepared for every outcome in advance. Errors
+/// with `InvalidPublicKey` if either key is not on curve.
+pub fn attestation_point(oracle: &[u8; 32], nonce: &[u8; 32], outcome: &[u8]) -> Result<UncompressedPoint, Secp256k1Error> {
+    let (pubkey, _) = Curve::lift_x_both(oracle).map_err(|_| Secp256k1Error::InvalidPublicKey)?;
+    let (big_r, _) = Curve::lift_x_both(nonce).map_err(|_| Secp256k1Error::InvalidPublicKey)?;
+    Curve::ecadd(&big_r, &Curve::ecmul(&pubkey, &bip340::challenge(nonce, oracle, &attestation_message(outcome)))?)
+}
+
+/// ### Decompose
+///
+/// Splits `value` into `D` digits in `base`, most significant first. Errors with
+/// `InvalidPayload` if `base` is below 2 or `value` needs more than `D` digits.
+pub fn decompose<const D: usize>(mut value: u64, base: u8) -> Result<[u8; D], Secp256k1Error> {
+    if base < 2 {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    let mut digits = [0u8; D];
+    for digit in digits.iter_mut().rev() {
+        *digit = (value % base as u64) as u8;
+        value /= base as u64;
+    }
+    if value != 0 {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    Ok(digits)
+}
+
+/// ### Compose
+///
+/// Joins digits in `base`, most significant first, into a value. Errors with `InvalidPayload`
+/// if `base` is below 2, a digit is not below `base` or the value overflows a `u64`.
+pub fn compose(digits: &[u8], base: u8) -> Result<u64, Secp256k1Error> {
+    if base < 2 {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    digits.iter().try_fold(0u64, |value, &digit| {
+        if digit >= base {
+            return Err(Secp256k1Error::InvalidPayload);
+        }
+        value
+            .checked_mul(base as u64)
+            .and_then(|value| value.checked_add(digit as u64))
+            .ok_or(Secp256k1Error::InvalidPayload)
+    })
+}
+
+/// Writes a digit's outcome, its value in decimal.
+fn digit_outcome(digit: u8, buf: &mut [u8; 3]) -> &[u8] {
+    let len = match digit {
+        0..=9 => 1,
+        10..=99 => 2,
+        _ => 3,
+    };
+    let mut digit = digit;
+    for byte in buf[..len].iter_mut().rev() {
+        *byte = b'0' + digit % 10;
+        digit /= 10;
+    }
+    &buf[..len]
+}
@@ -0,0 +1,174 @@
+//! # Twisted ElGamal
+//!
+//! Twisted ElGamal encryption of `u64` amounts, as used by confidential token protocols, over
+//! secp256k1. A ciphertext is a Pedersen commitment `𝐶 = 𝑥·𝐺 + 𝑟·𝐻` to the amount `𝑥` with
+//! opening `𝑟`, and a decryption handle `𝐷 = 𝑟·𝑃` for the recipient's public key `𝑃 = 𝒔⁻¹·𝐻`.
+//! The recipient recovers `𝑥·𝐺 = 𝐶 - 𝒔·𝐷`, and since the commitment does not depend on the
+//! key, the same commitment can carry handles for several recipients.
+//!
+//! Ciphertexts under the same key are additively homomorphic, so balances can be updated
+//! without decrypting. Only small amounts can be recovered from `𝑥·𝐺`, by brute force. Points
+//! cannot hold infinity, so arithmetic landing on it, such as subtracting a ciphertext from
+//! itself, errors with `IdentityPoint`.
+
+use crate::{arithmetic, Curve, Secp256k1Error, Secp256k1Point, SecretKey, UncompressedPoint};
+
+/// ### Pedersen 𝐻
+///
+/// The second Pedersen generator, `hash_to_curve("H", "SOLANA-SECP256K1-PEDERSEN-V1")`, so its
+/// discrete logarithm with respect to 𝐺 is unknown.
+pub const H: UncompressedPoint = UncompressedPoint([
+    0xbe, 0x96, 0xf5, 0x4a, 0x06, 0x09, 0x6e, 0xe7, 0x41, 0xfb, 0xf7, 0xa1, 0x1d, 0x65, 0x6a, 0x4c,
+    0xba, 0x61, 0x9a, 0xb1, 0x4e, 0xa4, 0x42, 0x2a, 0xb7, 0xbc, 0x75, 0x38, 0x04, 0xce, 0xc8, 0xe5,
+    0xb4, 0x9e, 0x19, 0x10, 0x7d, 0x99, 0x20, 0x51, 0x40, 0x7d, 0xc7, 0x1d, 0x32, 0xb1, 0x9a, 0x4e,
+    0xc6, 0x0b, 0x6d, 0xd5, 0xd8, 0x41, 0x66, 0x33, 0x9f, 0x68, 0xda, 0x3d, 0x54, 0xc2, 0xab, 0x79,
+]);
+
+/// ### Ciphertext
+///
+/// A twisted ElGamal ciphertext, a Pedersen commitment and its decryption handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ciphertext {
+    pub commitment: UncompressedPoint,
+    pub handle: UncompressedPoint,
+}
+
+/// ### Public Key
+///
+/// Calculates the encryption public key `𝑃 = 𝒔⁻¹·𝐻` of a secret key.
+pub fn public_key(secret: &SecretKey) -> Result<UncompressedPoint, Secp256k1Error> {
+    Curve::ecmul(&H, &Curve::mod_inv_n(secret.as_bytes())?)
+}
+
+/// ### Commit
+///
+/// Calculates the Pedersen commitment `𝑥·𝐺 + 𝑟·𝐻` to `amount` with a secret, uniformly random
+/// `opening`.
+pub fn commit(amount: u64, opening: &[u8; 32]) -> Result<UncompressedPoint, Secp256k1Error> {
+    let blinding = Curve::ecmul(&H, opening)?;
+    match amount {
+        0 => Ok(blinding),
+        _ => add(&mul_g_amount(amount)?, &blinding),
+    }
+}
+
+/// ### Encrypt
+///
+/// Encrypts `amount` to `pubkey` with a secret, uniformly random `opening`, which must never
+/// be reused.
+pub fn encrypt(pubkey: &UncompressedPoint, amount: u64, opening: &[u8; 32]) -> Result<Ciphertext, Secp256k1Error> {
+    if !pubkey.is_on_curve() {
+        return Err(Secp256k1Error::InvalidPublicKey);
+    }
+    Ok(Ciphertext { commitment: commit(amount, opening)?, handle: Curve::ecmul(pubkey, opening)? })
+}
+
+/// ### Decrypt
+///
+/// Recovers `𝑥·𝐺 = 𝐶 - 𝒔·𝐷`, erroring with `IdentityPoint` if the amount is zero.
+pub fn decrypt(secret: &SecretKey, ciphertext: &Ciphertext) -> Result<UncompressedPoint, Secp256k1Error> {
+    let blinding = Curve::ecmul(&ciphertext.handle, secret.as_bytes())?;
+    add(&ciphertext.commitment, &blinding.negate())
+}
+
+/// ### Decrypt Amount
+///
+/// Recovers an amount of at most `max` by searching `𝐶 = 𝑥·𝐺 + 𝒔·𝐷` one `𝐺` at a time, or
+/// returns `None` if it is larger. This is only practical off-chain, for small amounts.
+pub fn decrypt_amount(secret: &SecretKey, ciphertext: &Ciphertext, max: u64) -> Option<u64> {
+    let blinding = Curve::ecmul(&ciphertext.handle, secret.as_bytes()).ok()?;
+    let target = arithmetic::point_from_be_bytes(&ciphertext.commitment.0);
+    let g = arithmetic::point_from_be_bytes(&Curve::G.0);
+    // A running sum at infinity is held as `None`
+    let mut acc = Some(arithmetic::point_from_be_bytes(&blinding.0));
+    for amount in 0..=max {
+        if acc.is_some_and(|p| arithmetic::eq(&p[0], &target[0]) && arithmetic::eq(&p[1], &target[1])) {
+            return Some(amount);
+        }
+        acc = match acc {
+            None => Some(g),
+            Some(p) => arithmetic::affine_add_checked(&p, &g),
+        };
+    }
+    None
+}
+
+impl Ciphertext {
+    /// ### Size
+    ///
+    /// Serialized size: `commitment[64] ‖ handle[64]`.
+    pub const SIZE: usize = 128;
+
+    /// ### Add
+    ///
+    /// Adds two ciphertexts under the same key, encrypting the sum of their amounts.
+    pub fn add(&self, other: &Ciphertext) -> Result<Ciphertext, Secp256k1Error> {
+        Ok(Ciphertext {
+            commitment: add(&self.commitment, &other.commitment)?,
+            handle: add(&self.handle, &other.handle)?,
+        })
+    }
+
+    /// ### Sub
+    ///
+    /// Subtracts a ciphertext under the same key, encrypting the difference of their amounts.
+    pub fn sub(&self, other: &Ciphertext) -> Result<Ciphertext, Secp256k1Error> {
+        self.add(&Ciphertext { commitment: other.commitment.negate(), handle: other.handle.negate() })
+    }
+
+    /// ### Add Amount
+    ///
+    /// Adds a public `amount` to the encrypted amount, leaving the handle unchanged.
+    pub fn add_amount(&self, amount: u64) -> Result<Ciphertext, Secp256k1Error> {
+        if amount == 0 {
+            return Ok(*self);
+        }
+        Ok(Ciphertext { commitment: add(&self.commitment, &mul_g_amount(amount)?)?, handle: self.handle })
+    }
+
+    /// ### Sub Amount
+    ///
+    /// Subtracts a public `amount` from the encrypted amount, leaving the handle unchanged.
+    pub fn sub_amount(&self, amount: u64) -> Result<Ciphertext, Secp256k1Error> {
+        if amount == 0 {
+            return Ok(*self);
+        }
+        let amount = mul_g_amount(amount)?;
+        Ok(Ciphertext { commitment: add(&self.commitment, &amount.negate())?, handle: self.handle })
+    }
+
+    /// ### To Bytes
+    ///
+    /// Serializes the ciphertext for storage in an account.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[..64].copy_from_slice(&self.commitment.0);
+        bytes[64..].copy_from_slice(&self.handle.0);
+        bytes
+    }
+
+    /// ### From Bytes
+    ///
+    /// Deserializes a stored ciphertext, checking both points are on curve.
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, Secp256k1Error> {
+        let commitment = UncompressedPoint(bytes[..64].try_into().expect("64 bytes"));
+        let handle = UncompressedPoint(bytes[64..].try_into().expect("64 bytes"));
+        if !commitment.is_on_curve() || !handle.is_on_curve() {
+            return Err(Secp256k1Error::InvalidPublicKey);
+        }
+        Ok(Self { commitment, handle })
+    }
+}
+
+fn mul_g_amount(amount: u64) -> Result<UncompressedPoint, Secp256k1Error> {
+    let mut k = [0u8; 32];
+    k[24..].copy_from_slice(&amount.to_be_bytes());
+    Curve::mul_g(&k)
+}
+
+/// Adds two points, erroring with `IdentityPoint` at infinity.
+fn add(p: &UncompressedPoint, q: &UncompressedPoint) -> Result<UncompressedPoint, Secp256k1Error> {
+    arithmetic::affine_add_checked(&arithmetic::point_from_be_bytes(&p.0), &arithmetic::point_from_be_bytes(&q.0))
+        .map(|p| UncompressedPoint(arithmetic::point_to_be_bytes(&p)))
+        .ok_or(Secp256k1Error::IdentityPoint)
+}
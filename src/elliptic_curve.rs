@@ -0,0 +1,458 @@
+//! # Elliptic Curve
+//!
+//! RustCrypto `elliptic-curve` trait integration, so generic protocols written against
+//! `CurveArithmetic` and the `ff`/`group` traits can run on this crate's arithmetic when compiled
+//! for Solana.
+//!
+//! `FieldElement` and `Scalar` implement `PrimeField` on the fixed-width limb arithmetic behind
+//! `Curve`, with field square roots calculated by `Curve::mod_sqrt`, and so with the
+//! `sol_big_mod_exp` syscall under the `big-mod-exp` feature. The group law comes from
+//! `primeorder`'s complete projective formulas over these fields, as `AffinePoint` and
+//! `ProjectivePoint`. Their generic scalar multiplication is far more expensive on-chain than
+//! the recover tricks, so convert to an `UncompressedPoint` and multiply by a `Scalar` there
+//! for hot paths, which calls `Curve::ecmul`.
+//!
+//! Arithmetic is not constant time, so, as everywhere else in this crate, do not use it to
+//! handle secrets on shared hardware.
+
+use core::{
+    fmt::{Debug, Formatter},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, ShrAssign, Sub, SubAssign},
+};
+
+use ::elliptic_curve::{
+    bigint::{Encoding, U256},
+    consts::U32,
+    ff::{self, Field, PrimeField},
+    ops::{Invert, Reduce},
+    rand_core::RngCore,
+    scalar::{FromUintUnchecked, IsHigh},
+    sec1::{FromEncodedPoint, ToEncodedPoint},
+    subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
+    zeroize::DefaultIsZeroes,
+    CurveArithmetic, FieldBytes, FieldBytesEncoding, PrimeCurve, PrimeCurveArithmetic, ScalarPrimitive,
+};
+use primeorder::{point_arithmetic, PrimeCurveParams};
+
+use crate::{arithmetic, arithmetic::Limbs, hex, Curve, Secp256k1Error, SecretKey, UncompressedPoint};
+
+/// ### Secp256k1
+///
+/// The secp256k1 curve, as a RustCrypto `CurveArithmetic` implementation on this crate's field
+/// and scalar arithmetic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Secp256k1;
+
+/// A secp256k1 point in affine coordinates, which may be the point at infinity.
+pub type AffinePoint = primeorder::AffinePoint<Secp256k1>;
+
+/// A secp256k1 point in projective coordinates, which may be the point at infinity.
+pub type ProjectivePoint = primeorder::ProjectivePoint<Secp256k1>;
+
+impl ::elliptic_curve::Curve for Secp256k1 {
+    type FieldBytesSize = U32;
+    type Uint = U256;
+
+    const ORDER: U256 = U256::from_be_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+}
+
+impl PrimeCurve for Secp256k1 {}
+
+impl FieldBytesEncoding<Secp256k1> for U256 {}
+
+impl CurveArithmetic for Secp256k1 {
+    type AffinePoint = AffinePoint;
+    type ProjectivePoint = ProjectivePoint;
+    type Scalar = Scalar;
+}
+
+impl PrimeCurveArithmetic for Secp256k1 {
+    type CurveGroup = ProjectivePoint;
+}
+
+impl PrimeCurveParams for Secp256k1 {
+    type FieldElement = FieldElement;
+    type PointArithmetic = point_arithmetic::EquationAIsGeneric;
+
+    const EQUATION_A: FieldElement = FieldElement::ZERO;
+    const EQUATION_B: FieldElement = FieldElement([7, 0, 0, 0]);
+    const GENERATOR: (FieldElement, FieldElement) = (
+        FieldElement(arithmetic::from_be_bytes(&arithmetic::split(&Curve::G.0).0)),
+        FieldElement(arithmetic::from_be_bytes(&arithmetic::split(&Curve::G.0).1)),
+    );
+}
+
+/// Implements the `ff` traits for a prime field element held as canonical little-endian limbs
+/// modulo `$modulus`.
+macro_rules! prime_field {
+    (
+        $name:ident, $modulus:expr, $modulus_hex:literal, sqrt = $sqrt:expr,
+        generator = $generator:expr, s = $s:expr, root_of_unity = $root:expr,
+        root_of_unity_inv = $root_inv:expr, delta = $delta:expr
+    ) => {
+        impl $name {
+            /// Parses a big-endian integer, or `None` if it is not less than the modulus.
+            pub fn from_bytes(bytes: &[u8; 32]) -> CtOption<Self> {
+                let limbs = arithmetic::from_be_bytes(bytes);
+                let (_, borrow) = arithmetic::sbb(&limbs, &$modulus.m);
+                CtOption::new(Self(limbs), Choice::from(borrow as u8))
+            }
+
+            /// Serializes the element as a big-endian integer.
+            pub const fn to_bytes(&self) -> [u8; 32] {
+                arithmetic::to_be_bytes(&self.0)
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                f.write_str(concat!(stringify!($name), "(0x"))?;
+                f.write_str(hex::encode_to_slice(&self.to_bytes(), &mut [0u8; 64]).expect("64 hex digits"))?;
+                f.write_str(")")
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(n: u64) -> Self {
+                Self(arithmetic::reduce(&[n, 0, 0, 0], &$modulus))
+            }
+        }
+
+        impl ConditionallySelectable for $name {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                let mut limbs = [0u64; 4];
+                for (i, limb) in limbs.iter_mut().enumerate() {
+                    *limb = u64::conditional_select(&a.0[i], &b.0[i], choice);
+                }
+                Self(limbs)
+            }
+        }
+
+        impl ConstantTimeEq for $name {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0.ct_eq(&other.0)
+            }
+        }
+
+        impl DefaultIsZeroes for $name {}
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(arithmetic::neg_mod(&self.0, &$modulus))
+            }
+        }
+
+        impl Neg for &$name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                -*self
+            }
+        }
+
+        impl Add<&$name> for $name {
+            type Output = Self;
+
+            fn add(self, rhs: &Self) -> Self {
+                Self(arithmetic::add_mod(&self.0, &rhs.0, &$modulus))
+            }
+        }
+
+        impl Sub<&$name> for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: &Self) -> Self {
+                Self(arithmetic::sub_mod(&self.0, &rhs.0, &$modulus))
+            }
+        }
+
+        impl Mul<&$name> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: &Self) -> Self {
+                Self(arithmetic::mul_mod(&self.0, &rhs.0, &$modulus))
+            }
+        }
+
+        prime_field!(@ops $name, Add, add, AddAssign, add_assign, Sum, sum, Self::ZERO);
+        prime_field!(@ops $name, Sub, sub, SubAssign, sub_assign);
+        prime_field!(@ops $name, Mul, mul, MulAssign, mul_assign, Product, product, Self::ONE);
+
+        impl Invert for $name {
+            type Output = CtOption<Self>;
+
+            fn invert(&self) -> CtOption<Self> {
+                <Self as Field>::invert(self)
+            }
+        }
+
+        impl Field for $name {
+            const ZERO: Self = Self([0u64; 4]);
+            const ONE: Self = Self([1, 0, 0, 0]);
+
+            fn random(mut rng: impl RngCore) -> Self {
+                let mut bytes = [0u8; 32];
+                loop {
+                    rng.fill_bytes(&mut bytes);
+                    if let Some(k) = Self::from_bytes(&bytes).into() {
+                        return k;
+                    }
+                }
+            }
+
+            fn square(&self) -> Self {
+                *self * self
+            }
+
+            fn double(&self) -> Self {
+                *self + self
+            }
+
+            fn invert(&self) -> CtOption<Self> {
+                let inverse = arithmetic::inv_mod(&self.0, &$modulus);
+                CtOption::new(Self(inverse.unwrap_or_default()), Choice::from(inverse.is_some() as u8))
+            }
+
+            fn sqrt(&self) -> CtOption<Self> {
+                $sqrt(self)
+            }
+
+            fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+                ff::helpers::sqrt_ratio_generic(num, div)
+            }
+        }
+
+        impl PrimeField for $name {
+            type Repr = FieldBytes<Secp256k1>;
+
+            const MODULUS: &'static str = $modulus_hex;
+            const NUM_BITS: u32 = 256;
+            const CAPACITY: u32 = 255;
+            const TWO_INV: Self = Self(arithmetic::shr(&arithmetic::adc(&$modulus.m, &[1, 0, 0, 0]).0, 1));
+            const MULTIPLICATIVE_GENERATOR: Self = Self([$generator, 0, 0, 0]);
+            const S: u32 = $s;
+            const ROOT_OF_UNITY: Self = Self($root);
+            const ROOT_OF_UNITY_INV: Self = Self($root_inv);
+            const DELTA: Self = Self($delta);
+
+            fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+                Self::from_bytes(&repr.into())
+            }
+
+            fn to_repr(&self) -> Self::Repr {
+                self.to_bytes().into()
+            }
+
+            fn is_odd(&self) -> Choice {
+                Choice::from((self.0[0] & 1) as u8)
+            }
+        }
+    };
+    (@ops $name:ident, $op:ident, $method:ident, $assign:ident, $assign_method:ident $(, $fold:ident, $fold_method:ident, $identity:expr)?) => {
+        impl $op<$name> for $name {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $assign<$name> for $name {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = self.$method(&rhs);
+            }
+        }
+
+        impl $assign<&$name> for $name {
+            fn $assign_method(&mut self, rhs: &Self) {
+                *self = self.$method(rhs);
+            }
+        }
+
+        $(
+            impl $fold for $name {
+                fn $fold_method<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold($identity, |acc, k| acc.$method(&k))
+                }
+            }
+
+            impl<'a> $fold<&'a $name> for $name {
+                fn $fold_method<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                    iter.fold($identity, |acc, k| acc.$method(k))
+                }
+            }
+        )?
+    };
+}
+
+/// ### Field Element
+///
+/// An element of the secp256k1 base field, modulo 𝑃.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct FieldElement(Limbs);
+
+prime_field!(
+    FieldElement,
+    arithmetic::P,
+    "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+    sqrt = |a: &FieldElement| {
+        let root = Curve::mod_sqrt(&a.to_bytes());
+        CtOption::new(FieldElement(arithmetic::from_be_bytes(&root.unwrap_or_default())), Choice::from(root.is_some() as u8))
+    },
+    generator = 3,
+    s = 1,
+    // 𝑃 - 1, the only non-trivial square root of unity
+    root_of_unity = arithmetic::sbb(&arithmetic::P.m, &[1, 0, 0, 0]).0,
+    root_of_unity_inv = arithmetic::sbb(&arithmetic::P.m, &[1, 0, 0, 0]).0,
+    delta = [9, 0, 0, 0]
+);
+
+/// ### Scalar
+///
+/// An element of the secp256k1 scalar field, modulo 𝑁.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Scalar(Limbs);
+
+/// `(𝑡 - 1)/2` for `𝑁 - 1 = 2⁶·𝑡`, the exponent of Tonelli-Shanks square roots modulo 𝑁.
+const N_TM1D2: Limbs = [0x777fa4bd19a06c82, 0xfd755db9cd5e9140, 0xffffffffffffffff, 0x01ffffffffffffff];
+
+prime_field!(
+    Scalar,
+    arithmetic::N,
+    "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+    sqrt = |a: &Scalar| ff::helpers::sqrt_tonelli_shanks(a, N_TM1D2),
+    generator = 7,
+    s = 6,
+    root_of_unity = [0x992f4b5402b052f2, 0x98bdeab680756045, 0xdf9879a3fbc483a8, 0x0c1dc060e7a91986],
+    root_of_unity_inv = [0xb6fb30a0884f0d1c, 0x77a275910aa413c3, 0xefc7b0c75b8cbb72, 0xfd3ae181f12d7096],
+    delta = [0x199417c8c0bb7601, 0xd63b78e780e1341e, 0x000cbc21fe4561c8, 0]
+);
+
+impl AsRef<Scalar> for Scalar {
+    fn as_ref(&self) -> &Scalar {
+        self
+    }
+}
+
+impl IsHigh for Scalar {
+    fn is_high(&self) -> Choice {
+        Choice::from(Curve::is_high(&self.to_bytes()) as u8)
+    }
+}
+
+impl PartialOrd for Scalar {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.to_bytes().partial_cmp(&other.to_bytes())
+    }
+}
+
+impl ShrAssign<usize> for Scalar {
+    fn shr_assign(&mut self, n: usize) {
+        let (limbs, bits) = (n / 64, (n % 64) as u32);
+        let mut shifted = [0u64; 4];
+        shifted[..4usize.saturating_sub(limbs)].copy_from_slice(&self.0[limbs.min(4)..]);
+        self.0 = arithmetic::shr(&shifted, bits);
+    }
+}
+
+impl FromUintUnchecked for Scalar {
+    type Uint = U256;
+
+    fn from_uint_unchecked(uint: U256) -> Self {
+        Self(arithmetic::from_be_bytes(&uint.to_be_bytes()))
+    }
+}
+
+impl Reduce<U256> for Scalar {
+    type Bytes = FieldBytes<Secp256k1>;
+
+    fn reduce(uint: U256) -> Self {
+        Self(arithmetic::reduce(&arithmetic::from_be_bytes(&uint.to_be_bytes()), &arithmetic::N))
+    }
+
+    fn reduce_bytes(bytes: &FieldBytes<Secp256k1>) -> Self {
+        Self(arithmetic::reduce(&arithmetic::from_be_bytes(&(*bytes).into()), &arithmetic::N))
+    }
+}
+
+impl From<ScalarPrimitive<Secp256k1>> for Scalar {
+    fn from(k: ScalarPrimitive<Secp256k1>) -> Self {
+        Self::from_uint_unchecked(*k.as_uint())
+    }
+}
+
+impl From<Scalar> for ScalarPrimitive<Secp256k1> {
+    fn from(k: Scalar) -> Self {
+        ScalarPrimitive::new(k.into()).unwrap()
+    }
+}
+
+impl From<Scalar> for U256 {
+    fn from(k: Scalar) -> Self {
+        U256::from_be_bytes(k.to_bytes())
+    }
+}
+
+impl From<Scalar> for FieldBytes<Secp256k1> {
+    fn from(k: Scalar) -> Self {
+        k.to_bytes().into()
+    }
+}
+
+impl From<&SecretKey> for Scalar {
+    fn from(k: &SecretKey) -> Self {
+        Self(arithmetic::from_be_bytes(k.as_bytes()))
+    }
+}
+
+impl Mul<&Scalar> for UncompressedPoint {
+    type Output = Result<UncompressedPoint, Secp256k1Error>;
+
+    /// Multiplies with `Curve::ecmul`, the recover trick, rather than the generic group law.
+    fn mul(self, k: &Scalar) -> Self::Output {
+        Curve::ecmul(&self, &k.to_bytes())
+    }
+}
+
+impl TryFrom<UncompressedPoint> for AffinePoint {
+    type Error = Secp256k1Error;
+
+    /// Errors with `InvalidPublicKey` if the point is not on curve.
+    fn try_from(p: UncompressedPoint) -> Result<Self, Secp256k1Error> {
+        let encoded = ::elliptic_curve::sec1::EncodedPoint::<Secp256k1>::from_bytes(p.to_sec1_bytes())
+            .map_err(|_| Secp256k1Error::InvalidPublicKey)?;
+        Option::from(AffinePoint::from_encoded_point(&encoded)).ok_or(Secp256k1Error::InvalidPublicKey)
+    }
+}
+
+impl TryFrom<UncompressedPoint> for ProjectivePoint {
+    type Error = Secp256k1Error;
+
+    /// Errors with `InvalidPublicKey` if the point is not on curve.
+    fn try_from(p: UncompressedPoint) -> Result<Self, Secp256k1Error> {
+        AffinePoint::try_from(p).map(Into::into)
+    }
+}
+
+impl TryFrom<AffinePoint> for UncompressedPoint {
+    type Error = Secp256k1Error;
+
+    /// Errors with `IdentityPoint` on the point at infinity, which has no affine encoding.
+    fn try_from(p: AffinePoint) -> Result<Self, Secp256k1Error> {
+        let encoded = p.to_encoded_point(false);
+        let bytes = encoded.as_bytes().get(1..65).ok_or(Secp256k1Error::IdentityPoint)?;
+        Ok(UncompressedPoint(bytes.try_into().map_err(|_| Secp256k1Error::IdentityPoint)?))
+    }
+}
+
+impl TryFrom<ProjectivePoint> for UncompressedPoint {
+    type Error = Secp256k1Error;
+
+    /// Errors with `IdentityPoint` on the point at infinity, which has no affine encoding.
+    fn try_from(p: ProjectivePoint) -> Result<Self, Secp256k1Error> {
+        UncompressedPoint::try_from(AffinePoint::from(p))
+    }
+}
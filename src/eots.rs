@@ -0,0 +1,75 @@
+//! # EOTS
+//!
+//! Extractable one-time signatures, as used by Babylon-style restaking to slash finality
+//! providers that sign two blocks at the same height. A signer commits in advance to the
+//! x-only public randomness `𝑅ₓ` of a secret nonce `𝑘` for each slot, then signs that slot's
+//! 32-byte message with BIP-340 Schnorr using exactly that nonce, so a signature is `𝑠` alone.
+//!
+//! Signing two different messages in one slot leaks the key: `𝑠₁ - 𝑠₂ = (𝑒₁ - 𝑒₂)·𝒅`, so anyone
+//! holding both signatures, including a program checking slashing evidence, can extract `𝒅`.
+
+use crate::{arithmetic, bip340, Curve, Secp256k1Error, Secp256k1Point, SecretKey};
+
+/// ### Public Randomness
+///
+/// The x-only public randomness `(𝑘·𝐺)ₓ` a signer commits to for a slot signed with the secret
+/// nonce `𝑘`.
+pub fn public_randomness(nonce: &[u8; 32]) -> Result<[u8; 32], Secp256k1Error> {
+    Ok(Curve::mul_g(nonce)?.x())
+}
+
+/// ### Sign
+///
+/// Signs the 32-byte `message` of a slot with its secret `nonce`, returning `𝑠`. The full
+/// BIP-340 signature is `𝑅ₓ‖𝑠`. Errors with `ZeroScalar` if `nonce` is zero. Signing a second
+/// message with the same nonce reveals `key`.
+pub fn sign(key: &SecretKey, nonce: &[u8; 32], message: &[u8; 32]) -> Result<[u8; 32], Secp256k1Error> {
+    if Curve::is_zero(nonce) {
+        return Err(Secp256k1Error::ZeroScalar);
+    }
+    let (_, s) = arithmetic::split(&bip340::sign_with_nonce(key, nonce, message)?);
+    Ok(s)
+}
+
+/// ### Verify
+///
+/// Verifies `𝑠` over `message` by the x-only `pubkey_x` with the committed `public_randomness`,
+/// erroring with `InvalidSignature` on failure.
+pub fn verify(
+    pubkey_x: &[u8; 32],
+    public_randomness: &[u8; 32],
+    message: &[u8; 32],
+    s: &[u8; 32],
+) -> Result<(), Secp256k1Error> {
+    bip340::verify(pubkey_x, message, &arithmetic::concat(public_randomness, s))
+}
+
+/// ### Extract
+///
+/// Extracts the secret key behind `pubkey_x` from two signatures over different messages with
+/// the same `public_randomness`, as `𝒅 = (𝑠₁ - 𝑠₂)·(𝑒₁ - 𝑒₂)⁻¹ mod 𝑁`. Both signatures are
+/// verified first, so a successful extraction is proof of equivocation. The key returned is the
+/// one whose public key has an even 𝑌-coordinate, which is the signer's key or its negation.
+///
+/// Errors with `InvalidPayload` if the messages are equal and `InvalidSignature` if either
+/// signature does not verify.
+pub fn extract(
+    pubkey_x: &[u8; 32],
+    public_randomness: &[u8; 32],
+    messages: [&[u8; 32]; 2],
+    signatures: [&[u8; 32]; 2],
+) -> Result<SecretKey, Secp256k1Error> {
+    if messages[0] == messages[1] {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    verify(pubkey_x, public_randomness, messages[0], signatures[0])?;
+    verify(pubkey_x, public_randomness, messages[1], signatures[1])?;
+
+    // 𝑠₁ - 𝑠₂ = (𝑘 + 𝑒₁·𝒅) - (𝑘 + 𝑒₂·𝒅) = (𝑒₁ - 𝑒₂)·𝒅
+    let e1 = bip340::challenge(public_randomness, pubkey_x, messages[0]);
+    let e2 = bip340::challenge(public_randomness, pubkey_x, messages[1]);
+    let s = Curve::add_mod_n(signatures[0], &Curve::negate_n(signatures[1]));
+    let e = Curve::add_mod_n(&e1, &Curve::negate_n(&e2));
+    let d = Curve::mul_mod_n(&s, &Curve::mod_inv_n(&e).map_err(|_| Secp256k1Error::InvalidPayload)?);
+    SecretKey::from_bytes(d).map_err(|_| Secp256k1Error::InvalidSignature)
+}
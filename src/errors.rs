@@ -1,15 +1,143 @@
 use solana_nostd_secp256k1_recover::Secp256k1RecoverError;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// ### Secp256k1 Error
+///
+/// Errors returned by this crate. Each variant has a stable `u32` discriminant which is used as
+/// the `ProgramError::Custom` code when the `program-error` feature is enabled, so clients can
+/// decode failures from transaction logs:
+///
+/// | Code | Variant                    |
+/// |------|----------------------------|
+/// | 0    | `InvalidSecretKey`         |
+/// | 1    | `InvalidPublicKey`         |
+/// | 2    | `InvalidYCoordinate`       |
+/// | 3    | `ArithmeticOverflow`       |
+/// | 4    | `InvalidSignature`         |
+/// | 5    | `ZeroScalar`               |
+/// | 6    | `RecoverSignature`         |
+/// | 7    | `RecoverHash`              |
+/// | 8    | `RecoverFailed`            |
+/// | 9    | `InvalidSignatureEncoding` |
+/// | 10   | `ScalarOutOfRange`         |
+/// | 11   | `IdentityPoint`            |
+/// | 12   | `InvalidState`             |
+/// | 13   | `DuplicateSigner`          |
+/// | 14   | `UnknownSigner`            |
+/// | 15   | `ThresholdNotMet`          |
+/// | 16   | `InvalidPayload`           |
+/// | 17   | `InvalidAddress`           |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
 pub enum Secp256k1Error {
-    InvalidSecretKey,
-    InvalidPublicKey,
-    InvalidYCoordinate,
-    ArithmeticOverflow,
+    InvalidSecretKey = 0,
+    InvalidPublicKey = 1,
+    InvalidYCoordinate = 2,
+    ArithmeticOverflow = 3,
+    InvalidSignature = 4,
+    ZeroScalar = 5,
+    RecoverSignature = 6,
+    RecoverHash = 7,
+    RecoverFailed = 8,
+    InvalidSignatureEncoding = 9,
+    ScalarOutOfRange = 10,
+    IdentityPoint = 11,
+    InvalidState = 12,
+    DuplicateSigner = 13,
+    UnknownSigner = 14,
+    ThresholdNotMet = 15,
+    InvalidPayload = 16,
+    InvalidAddress = 17,
+}
+
+impl Secp256k1Error {
+    /// ### Recover Error
+    ///
+    /// Returns the underlying `Secp256k1RecoverError` if this error came from the recover
+    /// syscall.
+    pub fn recover_error(&self) -> Option<Secp256k1RecoverError> {
+        match self {
+            Secp256k1Error::RecoverSignature => Some(Secp256k1RecoverError::SignatureError),
+            Secp256k1Error::RecoverHash => Some(Secp256k1RecoverError::HashError),
+            Secp256k1Error::RecoverFailed => Some(Secp256k1RecoverError::RecoveryError),
+            _ => None,
+        }
+    }
 }
 
 impl From<Secp256k1RecoverError> for Secp256k1Error {
-    fn from(_: Secp256k1RecoverError) -> Self {
-        Secp256k1Error::InvalidPublicKey
+    fn from(e: Secp256k1RecoverError) -> Self {
+        match e {
+            Secp256k1RecoverError::SignatureError => Secp256k1Error::RecoverSignature,
+            Secp256k1RecoverError::HashError => Secp256k1Error::RecoverHash,
+            Secp256k1RecoverError::RecoveryError => Secp256k1Error::RecoverFailed,
+        }
+    }
+}
+
+impl core::fmt::Display for Secp256k1Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Secp256k1Error::InvalidSecretKey => "invalid secret key",
+            Secp256k1Error::InvalidPublicKey => "invalid public key",
+            Secp256k1Error::InvalidYCoordinate => "invalid y-coordinate",
+            Secp256k1Error::ArithmeticOverflow => "arithmetic overflow",
+            Secp256k1Error::InvalidSignature => "invalid signature",
+            Secp256k1Error::ZeroScalar => "scalar is zero",
+            Secp256k1Error::RecoverSignature => "recover rejected the signature",
+            Secp256k1Error::RecoverHash => "recover rejected the hash",
+            Secp256k1Error::RecoverFailed => "recover failed",
+            Secp256k1Error::InvalidSignatureEncoding => "invalid signature encoding",
+            Secp256k1Error::ScalarOutOfRange => "scalar out of range",
+            Secp256k1Error::IdentityPoint => "result is the point at infinity",
+            Secp256k1Error::InvalidState => "invalid or mismatched resumable state",
+            Secp256k1Error::DuplicateSigner => "duplicate signer",
+            Secp256k1Error::UnknownSigner => "signer is not in the signer set",
+            Secp256k1Error::ThresholdNotMet => "signature threshold not met",
+            Secp256k1Error::InvalidPayload => "invalid or unauthenticated payload",
+            Secp256k1Error::InvalidAddress => "invalid address or address checksum",
+        })
+    }
+}
+
+impl core::error::Error for Secp256k1Error {}
+
+impl From<Secp256k1Error> for u32 {
+    fn from(e: Secp256k1Error) -> Self {
+        e as u32
+    }
+}
+
+impl TryFrom<u32> for Secp256k1Error {
+    type Error = u32;
+
+    fn try_from(code: u32) -> Result<Self, u32> {
+        match code {
+            0 => Ok(Secp256k1Error::InvalidSecretKey),
+            1 => Ok(Secp256k1Error::InvalidPublicKey),
+            2 => Ok(Secp256k1Error::InvalidYCoordinate),
+            3 => Ok(Secp256k1Error::ArithmeticOverflow),
+            4 => Ok(Secp256k1Error::InvalidSignature),
+            5 => Ok(Secp256k1Error::ZeroScalar),
+            6 => Ok(Secp256k1Error::RecoverSignature),
+            7 => Ok(Secp256k1Error::RecoverHash),
+            8 => Ok(Secp256k1Error::RecoverFailed),
+            9 => Ok(Secp256k1Error::InvalidSignatureEncoding),
+            10 => Ok(Secp256k1Error::ScalarOutOfRange),
+            11 => Ok(Secp256k1Error::IdentityPoint),
+            12 => Ok(Secp256k1Error::InvalidState),
+            13 => Ok(Secp256k1Error::DuplicateSigner),
+            14 => Ok(Secp256k1Error::UnknownSigner),
+            15 => Ok(Secp256k1Error::ThresholdNotMet),
+            16 => Ok(Secp256k1Error::InvalidPayload),
+            17 => Ok(Secp256k1Error::InvalidAddress),
+            _ => Err(code),
+        }
+    }
+}
+
+#[cfg(feature = "program-error")]
+impl From<Secp256k1Error> for solana_program_error::ProgramError {
+    fn from(e: Secp256k1Error) -> Self {
+        solana_program_error::ProgramError::Custom(e.into())
     }
 }
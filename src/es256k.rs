@@ -0,0 +1,86 @@
+//! # ES256K
+//!
+//! JOSE `ES256K` (RFC 8812) compact JWS signing and verification, so token-based auth services
+//! can share secp256k1 keys with on-chain programs. A token is `B64(header) ‖ "." ‖
+//! B64(payload) ‖ "." ‖ B64(𝑟‖𝑠)` in unpadded base64url, signed with ECDSA over the SHA-256
+//! digest of everything before the last `.`.
+//!
+//! Headers and payloads are handled as raw JSON bytes and are not parsed. The algorithm is
+//! fixed by the caller rather than read from the header, so tokens cannot downgrade it.
+//! Tokens are signed low-𝑠, but high-𝑠 signatures from other JOSE libraries are accepted, as
+//! RFC 8812 does not require normalization.
+
+use crate::{arithmetic, backend, base64, signer::Secp256k1Signer, Curve, Secp256k1Error, Secp256k1Point};
+
+/// The JOSE `alg` of secp256k1 ECDSA with SHA-256.
+pub const ALG: &str = "ES256K";
+
+/// A minimal JWT header for `ES256K`.
+pub const JWT_HEADER: &str = r#"{"alg":"ES256K","typ":"JWT"}"#;
+
+/// The length of a base64url encoded signature.
+pub const SIGNATURE_LEN: usize = base64::encoded_len(64, false);
+
+/// ### Token Len
+///
+/// The length of a token over a `header_len` byte header and a `payload_len` byte payload.
+pub const fn token_len(header_len: usize, payload_len: usize) -> usize {
+    base64::encoded_len(header_len, false) + 1 + base64::encoded_len(payload_len, false) + 1 + SIGNATURE_LEN
+}
+
+/// ### Sign
+///
+/// Signs `header` and `payload` with `key`, writing the token to the start of `out` and
+/// returning it. Errors with `InvalidPayload` if `out` is shorter than
+/// `token_len(header.len(), payload.len())`.
+pub fn sign<'a, S: Secp256k1Signer + ?Sized>(key: &S, header: &[u8], payload: &[u8], out: &'a mut [u8]) -> Result<&'a str, Secp256k1Error> {
+    let len = token_len(header.len(), payload.len());
+    if out.len() < len {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    let header_len = base64::encoded_len(header.len(), false);
+    let signing_input_len = len - SIGNATURE_LEN - 1;
+    base64::encode_url_safe_to_slice(header, &mut out[..header_len])?;
+    out[header_len] = b'.';
+    base64::encode_url_safe_to_slice(payload, &mut out[header_len + 1..signing_input_len])?;
+    out[signing_input_len] = b'.';
+
+    let signature = key.sign_prehash(&backend::sha256(&[&out[..signing_input_len]]))?;
+    base64::encode_url_safe_to_slice(&arithmetic::concat(&signature.r, &signature.s), &mut out[signing_input_len + 1..len])?;
+    Ok(core::str::from_utf8(&out[..len]).expect("ASCII token"))
+}
+
+/// ### Verify
+///
+/// Verifies a token's signature against `pubkey` and decodes its payload into the start of
+/// `out`, returning it. Errors with `InvalidPayload` on a malformed token or if `out` is too
+/// short, `InvalidSignatureEncoding` on a malformed signature and `InvalidSignature` if it
+/// does not verify.
+pub fn verify<'a, T: Secp256k1Point>(token: &str, pubkey: &T, out: &'a mut [u8]) -> Result<&'a [u8], Secp256k1Error> {
+    let (signing_input, signature) = token.rsplit_once('.').ok_or(Secp256k1Error::InvalidPayload)?;
+    let (_, payload) = signing_input.split_once('.').ok_or(Secp256k1Error::InvalidPayload)?;
+    if payload.contains('.') {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+
+    let signature: [u8; 64] = base64::decode_url_safe(signature).map_err(|_| Secp256k1Error::InvalidSignatureEncoding)?;
+    let (r, mut s) = arithmetic::split(&signature);
+    if Curve::is_high(&s) {
+        s = Curve::negate_n(&s);
+    }
+    let hash = backend::sha256(&[signing_input.as_bytes()]);
+    Curve::verify_ecdsa(&hash, &arithmetic::concat(&r, &s), &pubkey.compress())?;
+
+    let len = base64::decode_url_safe_to_slice(payload, out)?;
+    Ok(&out[..len])
+}
+
+/// ### Decode Header
+///
+/// Decodes a token's header into the start of `out` without verifying it, eg: to read its
+/// `kid` and pick the public key to verify against.
+pub fn decode_header<'a>(token: &str, out: &'a mut [u8]) -> Result<&'a [u8], Secp256k1Error> {
+    let (header, _) = token.split_once('.').ok_or(Secp256k1Error::InvalidPayload)?;
+    let len = base64::decode_url_safe_to_slice(header, out)?;
+    Ok(&out[..len])
+}
@@ -0,0 +1,343 @@
+//! # Ethereum
+//!
+//! Ethereum-flavoured helpers built on top of `sol_secp256k1_recover` and `sol_keccak256`, for
+//! verifying EVM signers from SVM programs.
+
+use core::{fmt::{Debug, Display, Formatter}, str::FromStr};
+
+use crate::{arithmetic, backend, base64, der, hex, signer::Secp256k1Signer, Curve, Message, Secp256k1Error, UncompressedPoint};
+
+/// ### Eth Address
+///
+/// A 20-byte Ethereum address, the last 20 bytes of `keccak256(𝑋‖𝑌)` of a public key.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+#[repr(transparent)]
+pub struct EthAddress(pub [u8; 20]);
+
+impl From<UncompressedPoint> for EthAddress {
+    fn from(p: UncompressedPoint) -> Self {
+        let hash = backend::keccak256(&[&p.0]);
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hash[12..]);
+        EthAddress(address)
+    }
+}
+
+impl EthAddress {
+    /// ### To Checksum
+    ///
+    /// Encodes the address as `0x`-prefixed EIP-55 mixed-case hex, capitalizing each letter
+    /// whose nibble of `keccak256` of the lowercase hex is 8 or more.
+    pub fn to_checksum(&self) -> [u8; 42] {
+        let mut out = [0u8; 42];
+        out[..2].copy_from_slice(b"0x");
+        hex::encode_to_slice(&self.0, &mut out[2..]).expect("40 hex digits");
+        let hash = backend::keccak256(&[&out[2..]]);
+        for (i, c) in out[2..].iter_mut().enumerate() {
+            if (hash[i / 2] << (4 * (i % 2))) & 0x80 != 0 {
+                c.make_ascii_uppercase();
+            }
+        }
+        out
+    }
+
+    /// ### Check Pubkey
+    ///
+    /// Parses a raw 64-byte Ethereum public key with `UncompressedPoint::from_eth_pubkey`, and
+    /// checks it hashes to this address. Errors with `InvalidPublicKey` if the key is off curve,
+    /// and with `InvalidAddress` if it belongs to another address.
+    pub fn check_pubkey(&self, pubkey: [u8; 64]) -> Result<UncompressedPoint, Secp256k1Error> {
+        let pubkey = UncompressedPoint::from_eth_pubkey(pubkey)?;
+        if EthAddress::from(pubkey) != *self {
+            return Err(Secp256k1Error::InvalidAddress);
+        }
+        Ok(pubkey)
+    }
+}
+
+impl Display for EthAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(core::str::from_utf8(&self.to_checksum()).expect("ASCII hex"))
+    }
+}
+
+impl FromStr for EthAddress {
+    type Err = Secp256k1Error;
+
+    /// Parses a `0x`-prefixed EIP-55 address, erroring with `InvalidAddress` unless its case
+    /// matches the checksum exactly. All-lowercase input only parses if it has no letters that
+    /// should be capitalized, so a mistyped address is rejected rather than silently accepted.
+    fn from_str(s: &str) -> Result<Self, Secp256k1Error> {
+        let digits = s.strip_prefix("0x").ok_or(Secp256k1Error::InvalidAddress)?;
+        let address = EthAddress(hex::decode(digits).map_err(|_| Secp256k1Error::InvalidAddress)?);
+        if address.to_checksum()[2..] != *digits.as_bytes() {
+            return Err(Secp256k1Error::InvalidAddress);
+        }
+        Ok(address)
+    }
+}
+
+impl Debug for EthAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("0x")?;
+        f.write_str(hex::encode_to_slice(&self.0, &mut [0u8; 40]).expect("40 hex digits"))
+    }
+}
+
+/// ### Recoverable Signature
+///
+/// An ECDSA signature `(𝑟, 𝑠)` along with the recovery id `𝑣` (the parity of `𝑅.𝑌`), from which
+/// the signer's public key can be recovered. The layout is `#[repr(C)]` without padding, the
+/// same 65 bytes as `to_raw_bytes`, so it can be cast from zero-copy account data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+pub struct RecoverableSignature {
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+    pub recovery_id: u8,
+}
+
+impl RecoverableSignature {
+    /// ### From Bytes
+    ///
+    /// Parses a 65-byte `𝑟‖𝑠‖𝑣` signature, accepting `𝑣` as either `0`/`1` or `27`/`28`.
+    pub fn from_bytes(bytes: &[u8; 65]) -> Result<Self, Secp256k1Error> {
+        let recovery_id = match bytes[64] {
+            0 | 27 => 0,
+            1 | 28 => 1,
+            _ => return Err(Secp256k1Error::InvalidSignatureEncoding),
+        };
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&bytes[..32]);
+        s.copy_from_slice(&bytes[32..64]);
+        Ok(Self { r, s, recovery_id })
+    }
+
+    /// ### From EIP-155
+    ///
+    /// Builds a signature from `𝑟`, `𝑠` and a transaction `𝑣`, checking `𝑣` against `chain_id`.
+    /// Accepts EIP-155 `𝑣 = chain_id·2 + 35 + parity`, legacy pre-EIP-155 `27`/`28`, and the
+    /// bare `0`/`1` parity of typed transactions, which sign their chain id in the payload.
+    /// Errors with `InvalidSignatureEncoding` if `𝑣` is malformed or encodes another chain.
+    pub fn from_eip155(r: [u8; 32], s: [u8; 32], v: u64, chain_id: u64) -> Result<Self, Secp256k1Error> {
+        let recovery_id = match v {
+            0 | 1 => v as u8,
+            27 | 28 => (v - 27) as u8,
+            35.. if (v - 35) / 2 == chain_id => ((v - 35) & 1) as u8,
+            _ => return Err(Secp256k1Error::InvalidSignatureEncoding),
+        };
+        Ok(Self { r, s, recovery_id })
+    }
+
+    /// ### From KMS
+    ///
+    /// Builds an Ethereum-compatible signature from the DER `SubjectPublicKeyInfo` public key and
+    /// DER ECDSA signature over `hash` a cloud signer such as AWS KMS returns. High-𝑠 signatures
+    /// are normalized to low-𝑠, and the recovery id is found by recovering the signer with each
+    /// parity and comparing it to the public key. Errors with `InvalidPublicKey` or
+    /// `InvalidSignatureEncoding` on a malformed encoding, and with `InvalidSignature` if `𝑟`
+    /// or `𝑠` is out of range or the signature is not by the public key.
+    pub fn from_kms(hash: &[u8; 32], public_key: &[u8], signature: &[u8]) -> Result<Self, Secp256k1Error> {
+        let public_key = der::parse_spki(public_key)?;
+        let (r, s) = arithmetic::split(&der::parse_signature(signature)?);
+        if Curve::is_zero(&r) || Curve::is_zero(&s) || r >= Curve::N || s >= Curve::N {
+            return Err(Secp256k1Error::InvalidSignature);
+        }
+        let s = Curve::conditional_negate_n(&s, Curve::is_high(&s));
+        (0..2)
+            .map(|recovery_id| Self { r, s, recovery_id })
+            .find(|signature| signature.recover(hash) == Ok(public_key))
+            .ok_or(Secp256k1Error::InvalidSignature)
+    }
+
+    /// ### To Bytes
+    ///
+    /// Serializes the signature as `𝑟‖𝑠‖𝑣`, with `𝑣` in Ethereum's `27`/`28` form.
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..64].copy_from_slice(&self.s);
+        bytes[64] = self.recovery_id + 27;
+        bytes
+    }
+
+    /// ### From Raw Bytes
+    ///
+    /// Parses the fixed-size `𝑟‖𝑠‖𝑣` layout of the borsh, serde and `bytemuck` encodings, with
+    /// `𝑣` the bare recovery id.
+    /// Errors with `InvalidSignature` if `𝑟` or `𝑠` is zero or not less than 𝑁, and
+    /// `InvalidSignatureEncoding` if `𝑣` is above 3.
+    pub fn from_raw_bytes(bytes: &[u8; 65]) -> Result<Self, Secp256k1Error> {
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&bytes[..32]);
+        s.copy_from_slice(&bytes[32..64]);
+        if Curve::is_zero(&r) || Curve::is_zero(&s) || r >= Curve::N || s >= Curve::N {
+            return Err(Secp256k1Error::InvalidSignature);
+        }
+        if bytes[64] > 3 {
+            return Err(Secp256k1Error::InvalidSignatureEncoding);
+        }
+        Ok(Self { r, s, recovery_id: bytes[64] })
+    }
+
+    /// ### To Raw Bytes
+    ///
+    /// Serializes the signature as `𝑟‖𝑠‖𝑣` with `𝑣` the bare recovery id, as `from_raw_bytes`
+    /// parses.
+    pub fn to_raw_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..64].copy_from_slice(&self.s);
+        bytes[64] = self.recovery_id;
+        bytes
+    }
+
+    /// ### From Base64
+    ///
+    /// Parses a 65-byte `𝑟‖𝑠‖𝑣` signature from padded standard base64, as `from_bytes`.
+    pub fn from_base64(base64: &str) -> Result<Self, Secp256k1Error> {
+        Self::from_bytes(&base64::decode(base64).map_err(|_| Secp256k1Error::InvalidSignatureEncoding)?)
+    }
+
+    /// ### To Base64
+    ///
+    /// Encodes the signature as `to_bytes` in padded standard base64.
+    pub fn to_base64<'a>(&self, out: &'a mut [u8; 88]) -> &'a str {
+        base64::encode_to_slice(&self.to_bytes(), out).expect("88 base64 characters")
+    }
+
+    /// ### Recover
+    ///
+    /// Recovers the public key that signed `hash`. As on Ethereum, high-𝑠 signatures are
+    /// rejected to prevent malleability.
+    pub fn recover(&self, hash: &[u8; 32]) -> Result<UncompressedPoint, Secp256k1Error> {
+        if Curve::is_high(&self.s) {
+            return Err(Secp256k1Error::InvalidSignature);
+        }
+        self.recover_unchecked(hash)
+    }
+
+    /// ### Recover Unchecked
+    ///
+    /// As `recover`, without rejecting high-𝑠 signatures. Recovery ids of 2 and 3, for `𝑅ₓ ≥ 𝑁`,
+    /// can't be recovered by the syscall, so still error with `InvalidSignature` rather than
+    /// recovering the wrong key.
+    pub fn recover_unchecked(&self, hash: &[u8; 32]) -> Result<UncompressedPoint, Secp256k1Error> {
+        if self.recovery_id > 1 {
+            return Err(Secp256k1Error::InvalidSignature);
+        }
+        let signature = arithmetic::concat(&self.r, &self.s);
+        backend::secp256k1_recover(hash, self.recovery_id == 1, &signature)
+            .map(UncompressedPoint)
+            .map_err(Secp256k1Error::from)
+    }
+
+    /// ### Recover Message
+    ///
+    /// As `recover`, over a hashed `Message`.
+    pub fn recover_message(&self, message: &Message) -> Result<UncompressedPoint, Secp256k1Error> {
+        self.recover(message.as_bytes())
+    }
+
+    /// ### Recover Address
+    ///
+    /// Recovers the Ethereum address that signed `hash`.
+    pub fn recover_address(&self, hash: &[u8; 32]) -> Result<EthAddress, Secp256k1Error> {
+        self.recover(hash).map(EthAddress::from)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for RecoverableSignature {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        writer.write_all(&self.to_raw_bytes())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for RecoverableSignature {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let mut bytes = [0u8; 65];
+        reader.read_exact(&mut bytes)?;
+        Self::from_raw_bytes(&bytes).map_err(|_| borsh::io::Error::new(borsh::io::ErrorKind::InvalidData, "Invalid signature"))
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for RecoverableSignature {}
+
+// Three byte arrays with an alignment of 1, so there is no padding
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for RecoverableSignature {}
+
+/// ### Recover With Chain ID
+///
+/// Recovers the Ethereum address that signed `hash` with a transaction's `𝑣`, checking it was
+/// signed for `chain_id` as in `RecoverableSignature::from_eip155`.
+pub fn recover_with_chain_id(
+    hash: &[u8; 32],
+    v: u64,
+    r: [u8; 32],
+    s: [u8; 32],
+    chain_id: u64,
+) -> Result<EthAddress, Secp256k1Error> {
+    RecoverableSignature::from_eip155(r, s, v, chain_id)?.recover_address(hash)
+}
+
+/// ### Sign Tx Digest
+///
+/// Signs a transaction's signing `digest` with `secret_key`, returning `(𝑟, 𝑠, 𝑣)` with `𝑣` as
+/// the transaction encodes it. Legacy transactions, `tx_type` `0`, get the EIP-155 `𝑣 =
+/// chain_id·2 + 35 + parity`, or the pre-EIP-155 `27`/`28` with a `chain_id` of `0`. Typed
+/// EIP-2718 transactions, such as EIP-2930 `1` and EIP-1559 `2`, sign their chain id in the
+/// payload and get the bare `y_parity`. Errors with `InvalidPayload` on a `tx_type` above
+/// `0x7f` or a chain id too large to encode.
+pub fn sign_tx_digest<S: Secp256k1Signer + ?Sized>(digest: &[u8; 32], secret_key: &S, chain_id: u64, tx_type: u8) -> Result<([u8; 32], [u8; 32], u64), Secp256k1Error> {
+    let signature = secret_key.sign_prehash(digest)?;
+    let parity = (signature.recovery_id & 1) as u64;
+    let v = match (tx_type, chain_id) {
+        (0, 0) => 27 + parity,
+        (0, _) => chain_id.checked_mul(2).and_then(|v| v.checked_add(35 + parity)).ok_or(Secp256k1Error::InvalidPayload)?,
+        (1..=0x7f, _) => parity,
+        _ => return Err(Secp256k1Error::InvalidPayload),
+    };
+    Ok((signature.r, signature.s, v))
+}
+
+/// ### Recover Addresses
+///
+/// Recovers the Ethereum addresses that signed `hash`, eg: for a bridge's guardian or validator
+/// set. Errors with `DuplicateSigner` as soon as a signature or recovered address repeats, with
+/// repeated signatures caught before paying for their recover.
+///
+/// The addresses are written to the start of `out`, which is returned trimmed to one address
+/// per signature, so the signer set can be sized at runtime from instruction data. With
+/// `sorted`, they are in ascending order for comparing against a sorted signer set, otherwise
+/// in the same order as `signatures`. Errors with `InvalidPayload` if `out` is too short,
+/// before recovering anything.
+pub fn recover_addresses<'a>(
+    hash: &[u8; 32],
+    signatures: &[RecoverableSignature],
+    sorted: bool,
+    out: &'a mut [EthAddress],
+) -> Result<&'a [EthAddress], Secp256k1Error> {
+    let addresses = out.get_mut(..signatures.len()).ok_or(Secp256k1Error::InvalidPayload)?;
+    for (i, signature) in signatures.iter().enumerate() {
+        if signatures[..i].contains(signature) {
+            return Err(Secp256k1Error::DuplicateSigner);
+        }
+        let address = signature.recover_address(hash)?;
+        if addresses[..i].contains(&address) {
+            return Err(Secp256k1Error::DuplicateSigner);
+        }
+        addresses[i] = address;
+    }
+    if sorted {
+        addresses.sort_unstable();
+    }
+    Ok(addresses)
+}
@@ -0,0 +1,215 @@
+//! # Hash to Curve
+//!
+//! RFC 9380 hashing of arbitrary messages to secp256k1 points with the
+//! `secp256k1_XMD:SHA-256_SSWU_RO_` suite, for protocols such as OPRFs and VRFs which need a
+//! point with no known discrete logarithm.
+//!
+//! Messages are expanded with `expand_message_xmd` over `sol_sha256`, mapped to the 3-isogenous
+//! curve `𝐸': 𝑦² = 𝑥³ + 𝐴'𝑥 + 𝐵'` with the simplified SWU map, and carried back to secp256k1 by
+//! the isogeny. The map takes the same steps regardless of its input, computing both candidate
+//! square roots and choosing between them with masks rather than branches.
+//!
+//! The try-and-increment method is also available as `hash_to_point_tai`, for protocols which
+//! specify it.
+
+use crate::{
+    arithmetic::{self, Limbs},
+    backend, CompressedPoint, Secp256k1Error, Secp256k1Point, UncompressedPoint,
+};
+
+/// ### Suite ID
+///
+/// The RFC 9380 suite implemented by `hash_to_curve`.
+pub const SUITE_ID: &[u8] = b"secp256k1_XMD:SHA-256_SSWU_RO_";
+
+/// ### Encode Suite ID
+///
+/// The RFC 9380 suite implemented by `encode_to_curve`.
+pub const ENCODE_SUITE_ID: &[u8] = b"secp256k1_XMD:SHA-256_SSWU_NU_";
+
+/// 𝐴' of the isogenous curve 𝐸'
+const A: Limbs = [0x405447c01a444533, 0xe953d363cb6f0e5d, 0xa08a5558f0f5d272, 0x3f8731abdd661adc];
+
+/// 𝐵' of the isogenous curve 𝐸'
+const B: Limbs = [1771, 0, 0, 0];
+
+/// 𝑍 = -11, a non-square in 𝔽ₚ
+const Z: Limbs = arithmetic::neg_mod(&[11, 0, 0, 0], &arithmetic::P);
+
+/// -𝐵'/𝐴'
+const NEG_B_DIV_A: Limbs = arithmetic::mul_mod(&arithmetic::neg_mod(&B, &arithmetic::P), &inv0(&A), &arithmetic::P);
+
+/// 𝐵'/(𝑍𝐴'), the 𝑋-coordinate used when the SWU denominator vanishes
+const B_DIV_ZA: Limbs = arithmetic::mul_mod(&B, &inv0(&arithmetic::mul_mod(&Z, &A, &arithmetic::P)), &arithmetic::P);
+
+/// (𝑃+1)/4, the exponent of a square root modulo 𝑃
+const SQRT_EXP: Limbs = arithmetic::shr(&arithmetic::adc(&arithmetic::P.m, &[1, 0, 0, 0]).0, 2);
+
+/// Coefficients of the 3-isogeny's rational maps, highest degree first
+const X_NUM: [Limbs; 4] = [
+    [0x8e38e38daaaaa88c, 0x38e38e38e38e38e3, 0xe38e38e38e38e38e, 0x8e38e38e38e38e38],
+    [0x4ecbd0b53d9dd262, 0xe4506144037c4031, 0xe2a413deca25caec, 0x534c328d23f234e6],
+    [0xdfff1044f17c6581, 0xd595d2fc0bf63b92, 0xb9f315cea7fd44c5, 0x07d3d4c80bc321d5],
+    [0x8e38e38daaaaa8c7, 0x38e38e38e38e38e3, 0xe38e38e38e38e38e, 0x8e38e38e38e38e38],
+];
+const X_DEN: [Limbs; 3] = [
+    [1, 0, 0, 0],
+    [0xc52a56612a8c6d14, 0x06d36b641f5e41bb, 0xf7c4b2d51b542254, 0xedadc6f64383dc1d],
+    [0x9fe6b745781eb49b, 0x86cd409542f8487d, 0x9ca34ccbb7b640dd, 0xd35771193d94918a],
+];
+const Y_NUM: [Limbs; 4] = [
+    [0x84bda12f38e38d84, 0xbda12f684bda12f6, 0xa12f684bda12f684, 0x2f684bda12f684bd],
+    [0xa765e85a9ecee931, 0x722830a201be2018, 0x715209ef6512e576, 0x29a6194691f91a73],
+    [0xdffc90fc201d71a3, 0x647ab046d686da6f, 0xa9d0a54b12a0a6d5, 0xc75e0c32d5cb7c0f],
+    [0xa12f684b8e38e23c, 0x2f684bda12f684bd, 0x684bda12f684bda1, 0x4bda12f684bda12f],
+];
+const Y_DEN: [Limbs; 4] = [
+    [1, 0, 0, 0],
+    [0xa7bf8192bfd2a76f, 0x0a3d21162f0d6299, 0xf3a70c3fa8fe337e, 0x6484aa716545ca2c],
+    [0xdfb425d2685c2573, 0x9467c1bfc8e8d978, 0xd5e9e6632722c298, 0x7a06534bb8bdb49f],
+    [0xfffffffefffff93b, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff],
+];
+
+/// ### Hash to Curve
+///
+/// Hashes `msg` to a point, uniformly distributed and with no known discrete logarithm, under
+/// the domain separation tag `dst`. Errors with `IdentityPoint` in the negligible case that the
+/// two mapped points cancel out.
+pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Result<UncompressedPoint, Secp256k1Error> {
+    let uniform: [u8; 96] = expand_message_xmd(msg, dst);
+    let q0 = map_to_curve_limbs(&arithmetic::from_be_slice(&uniform[..48], &arithmetic::P));
+    let q1 = map_to_curve_limbs(&arithmetic::from_be_slice(&uniform[48..], &arithmetic::P));
+    arithmetic::affine_add_checked(&q0, &q1)
+        .map(|p| UncompressedPoint(arithmetic::point_to_be_bytes(&p)))
+        .ok_or(Secp256k1Error::IdentityPoint)
+}
+
+/// ### Encode to Curve
+///
+/// Hashes `msg` to a point with a single map, as in the `_NU_` suite. Cheaper than
+/// `hash_to_curve`, but the output is not uniformly distributed, so only use it where a
+/// protocol specifies it.
+pub fn encode_to_curve(msg: &[u8], dst: &[u8]) -> UncompressedPoint {
+    let uniform: [u8; 48] = expand_message_xmd(msg, dst);
+    UncompressedPoint(arithmetic::point_to_be_bytes(&map_to_curve_limbs(&arithmetic::from_be_slice(
+        &uniform,
+        &arithmetic::P,
+    ))))
+}
+
+/// ### Map to Curve
+///
+/// Maps a field element `𝑢`, reduced modulo 𝑃, to a point with the simplified SWU map and
+/// 3-isogeny.
+pub fn map_to_curve(u: &[u8; 32]) -> UncompressedPoint {
+    let u = arithmetic::reduce(&arithmetic::from_be_bytes(u), &arithmetic::P);
+    UncompressedPoint(arithmetic::point_to_be_bytes(&map_to_curve_limbs(&u)))
+}
+
+/// ### Hash to Point Try-and-Increment
+///
+/// Hashes `msg` to a point by trying `𝑋 = SHA-256(dst ‖ msg ‖ ctr)` for `ctr = 0, 1, ..` until
+/// it lifts to a point, taking the even 𝑌-coordinate. Only use this for compatibility with
+/// protocols that specify it, such as ECVRF-TAI or legacy Pedersen `𝐻` derivations, as the
+/// number of attempts, and so the cost, depends on the message.
+///
+/// Each attempt costs a recover, and succeeds about half the time. Errors with
+/// `InvalidYCoordinate` if all 256 counters fail, which happens with negligible probability.
+pub fn hash_to_point_tai(msg: &[u8], dst: &[u8]) -> Result<UncompressedPoint, Secp256k1Error> {
+    let mut point = [0x02; 33];
+    for ctr in 0..=u8::MAX {
+        point[1..].copy_from_slice(&backend::sha256(&[dst, msg, &[ctr]]));
+        if let Ok(p) = CompressedPoint(point).decompress() {
+            return Ok(p);
+        }
+    }
+    Err(Secp256k1Error::InvalidYCoordinate)
+}
+
+/// ### Expand Message XMD
+///
+/// Expands `msg` into `L` uniformly random bytes with SHA-256, per RFC 9380 section 5.3.1.
+/// Tags longer than 255 bytes are first hashed down as the RFC requires. `L` must be at most
+/// 8160 bytes.
+pub fn expand_message_xmd<const L: usize>(msg: &[u8], dst: &[u8]) -> [u8; L] {
+    const { assert!(L > 0 && L <= 255 * 32, "expand_message_xmd supports 1 to 8160 bytes") };
+    let oversize;
+    let dst = if dst.len() > 255 {
+        oversize = backend::sha256(&[b"H2C-OVERSIZE-DST-", dst]);
+        &oversize[..]
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+
+    let b0 = backend::sha256(&[&[0u8; 64], msg, &(L as u16).to_be_bytes(), &[0], dst, &dst_len]);
+    let mut b = backend::sha256(&[&b0, &[1], dst, &dst_len]);
+    let mut out = [0u8; L];
+    for (i, chunk) in out.chunks_mut(32).enumerate() {
+        if i > 0 {
+            let mut x = b0;
+            x.iter_mut().zip(&b).for_each(|(x, b)| *x ^= b);
+            b = backend::sha256(&[&x, &[i as u8 + 1], dst, &dst_len]);
+        }
+        chunk.copy_from_slice(&b[..chunk.len()]);
+    }
+    out
+}
+
+/// Maps `𝑢` to 𝐸' with the simplified SWU map, then to secp256k1 with the 3-isogeny.
+fn map_to_curve_limbs(u: &Limbs) -> [Limbs; 2] {
+    let p = &arithmetic::P;
+    let z_u2 = arithmetic::mul_mod(&Z, &arithmetic::mul_mod(u, u, p), p);
+    let tv1 = inv0(&arithmetic::add_mod(&arithmetic::mul_mod(&z_u2, &z_u2, p), &z_u2, p));
+
+    // 𝑥₁ = -𝐵'/𝐴'·(1 + 𝑡𝑣₁), or 𝐵'/(𝑍𝐴') when 𝑡𝑣₁ = 0
+    let x1 = arithmetic::mul_mod(&NEG_B_DIV_A, &arithmetic::add_mod(&[1, 0, 0, 0], &tv1, p), p);
+    let x1 = arithmetic::select(&x1, &B_DIV_ZA, is_zero(&tv1));
+    let x2 = arithmetic::mul_mod(&z_u2, &x1, p);
+    let (gx1, gx2) = (g(&x1), g(&x2));
+    let (y1, y2) = (arithmetic::pow_mod(&gx1, &SQRT_EXP, p), arithmetic::pow_mod(&gx2, &SQRT_EXP, p));
+
+    // Exactly one of 𝑔(𝑥₁) and 𝑔(𝑥₂) is square
+    let gx1_square = arithmetic::eq(&arithmetic::mul_mod(&y1, &y1, p), &gx1);
+    let x = arithmetic::select(&x2, &x1, gx1_square);
+    let y = arithmetic::select(&y2, &y1, gx1_square);
+    let y = arithmetic::select(&y, &arithmetic::neg_mod_ct(&y, p), (u[0] ^ y[0]) & 1 == 1);
+
+    iso_map(&x, &y)
+}
+
+/// Carries a point on 𝐸' to secp256k1 with the 3-isogeny of RFC 9380 appendix E.1.
+fn iso_map(x: &Limbs, y: &Limbs) -> [Limbs; 2] {
+    let p = &arithmetic::P;
+    let x_num = horner(&X_NUM, x);
+    let x_den = horner(&X_DEN, x);
+    let y_num = horner(&Y_NUM, x);
+    let y_den = horner(&Y_DEN, x);
+    [
+        arithmetic::mul_mod(&x_num, &inv0(&x_den), p),
+        arithmetic::mul_mod(y, &arithmetic::mul_mod(&y_num, &inv0(&y_den), p), p),
+    ]
+}
+
+/// Evaluates a polynomial with coefficients in descending order of degree.
+fn horner<const D: usize>(coefficients: &[Limbs; D], x: &Limbs) -> Limbs {
+    coefficients[1..].iter().fold(coefficients[0], |acc, c| {
+        arithmetic::add_mod(&arithmetic::mul_mod(&acc, x, &arithmetic::P), c, &arithmetic::P)
+    })
+}
+
+/// 𝑥³ + 𝐴'𝑥 + 𝐵', the right-hand side of 𝐸'.
+fn g(x: &Limbs) -> Limbs {
+    let p = &arithmetic::P;
+    let x2_a = arithmetic::add_mod(&arithmetic::mul_mod(x, x, p), &A, p);
+    arithmetic::add_mod(&arithmetic::mul_mod(&x2_a, x, p), &B, p)
+}
+
+/// `𝑥⁻¹`, or `0` when `𝑥 = 0`, by exponentiation so the cost does not depend on `𝑥`.
+const fn inv0(x: &Limbs) -> Limbs {
+    arithmetic::pow_mod(x, &arithmetic::sbb(&arithmetic::P.m, &[2, 0, 0, 0]).0, &arithmetic::P)
+}
+
+const fn is_zero(x: &Limbs) -> bool {
+    x[0] | x[1] | x[2] | x[3] == 0
+}
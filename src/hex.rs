@@ -0,0 +1,87 @@
+//! # Hex
+//!
+//! Allocation-free hex encoding into caller-provided buffers and strict decoding, used by the
+//! `Debug`, `Display` and `FromStr` implementations in this crate, so on-chain programs and wasm
+//! builds don't need the `hex` crate for them.
+//!
+//! Digits are converted with arithmetic rather than table lookups or early returns, so the time
+//! taken depends only on the length of the input, not on its bytes.
+
+use crate::Secp256k1Error;
+
+/// Encodes a nibble as an ASCII hex digit, using `alpha` (`b'a'` or `b'A'`) for 10 and above.
+const fn encode_nibble(n: u8, alpha: u8) -> u8 {
+    // 0xFF if 𝑛 > 9, otherwise 0
+    let letter = (9u16.wrapping_sub(n as u16) >> 8) as u8;
+    n + b'0' + (letter & (alpha - b'0' - 10))
+}
+
+/// Returns -1 if `lo ≤ 𝑐 ≤ hi`, otherwise 0.
+const fn in_range(c: i32, lo: u8, hi: u8) -> i32 {
+    ((lo as i32 - 1 - c) & (c - hi as i32 - 1)) >> 31
+}
+
+/// Decodes an ASCII hex digit of either case, returning a negative value if it is not one.
+const fn decode_nibble(c: u8) -> i32 {
+    let c = c as i32;
+    let digit = in_range(c, b'0', b'9') & (c - b'0' as i32 + 1);
+    let lower = in_range(c, b'a', b'f') & (c - b'a' as i32 + 11);
+    let upper = in_range(c, b'A', b'F') & (c - b'A' as i32 + 11);
+    (digit | lower | upper) - 1
+}
+
+fn encode_with<'a>(bytes: &[u8], out: &'a mut [u8], alpha: u8) -> Result<&'a str, Secp256k1Error> {
+    if out.len() != 2 * bytes.len() {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    for (pair, byte) in out.chunks_exact_mut(2).zip(bytes) {
+        pair[0] = encode_nibble(byte >> 4, alpha);
+        pair[1] = encode_nibble(byte & 0x0f, alpha);
+    }
+    Ok(core::str::from_utf8(out).expect("ASCII hex"))
+}
+
+/// ### Encode To Slice
+///
+/// Encodes `bytes` as lowercase hex into `out`, returning it as a `str`. Errors with
+/// `InvalidPayload` unless `out` is exactly twice the length of `bytes`.
+pub fn encode_to_slice<'a>(bytes: &[u8], out: &'a mut [u8]) -> Result<&'a str, Secp256k1Error> {
+    encode_with(bytes, out, b'a')
+}
+
+/// ### Encode Upper To Slice
+///
+/// As `encode_to_slice`, in uppercase.
+pub fn encode_upper_to_slice<'a>(bytes: &[u8], out: &'a mut [u8]) -> Result<&'a str, Secp256k1Error> {
+    encode_with(bytes, out, b'A')
+}
+
+/// ### Decode To Slice
+///
+/// Decodes hex digits of either case into `out`. Errors with `InvalidPayload` unless `hex` is
+/// exactly twice the length of `out` and every character is a hex digit, with no prefix,
+/// whitespace or separators.
+pub fn decode_to_slice(hex: &str, out: &mut [u8]) -> Result<(), Secp256k1Error> {
+    if hex.len() != 2 * out.len() {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    let mut invalid = 0;
+    for (byte, pair) in out.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        let (hi, lo) = (decode_nibble(pair[0]), decode_nibble(pair[1]));
+        invalid |= hi | lo;
+        *byte = (hi << 4 | lo) as u8;
+    }
+    if invalid < 0 {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    Ok(())
+}
+
+/// ### Decode
+///
+/// Decodes exactly `N` bytes of hex, as `decode_to_slice`.
+pub fn decode<const N: usize>(hex: &str) -> Result<[u8; N], Secp256k1Error> {
+    let mut out = [0u8; N];
+    decode_to_slice(hex, &mut out)?;
+    Ok(out)
+}
@@ -0,0 +1,148 @@
+//! # k256
+//!
+//! Conversions between this crate's types and their RustCrypto `k256` equivalents, so off-chain
+//! services and on-chain programs can share values without manual byte shuffling.
+
+use ::k256::{
+    ecdsa::{RecoveryId, Signature},
+    elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint},
+    AffinePoint, EncodedPoint, NonZeroScalar, PublicKey, Scalar,
+};
+
+use core::ops::Mul;
+
+use crate::{eth::RecoverableSignature, CompressedPoint, Curve, Secp256k1Error, Secp256k1Point, SecretKey, UncompressedPoint};
+
+impl TryFrom<UncompressedPoint> for AffinePoint {
+    type Error = Secp256k1Error;
+
+    fn try_from(p: UncompressedPoint) -> Result<Self, Secp256k1Error> {
+        let encoded = EncodedPoint::from_affine_coordinates(&p.x().into(), &p.y().into(), false);
+        Option::from(AffinePoint::from_encoded_point(&encoded)).ok_or(Secp256k1Error::InvalidPublicKey)
+    }
+}
+
+impl TryFrom<CompressedPoint> for AffinePoint {
+    type Error = Secp256k1Error;
+
+    fn try_from(p: CompressedPoint) -> Result<Self, Secp256k1Error> {
+        let encoded = EncodedPoint::from_bytes(p.0).map_err(|_| Secp256k1Error::InvalidPublicKey)?;
+        Option::from(AffinePoint::from_encoded_point(&encoded)).ok_or(Secp256k1Error::InvalidPublicKey)
+    }
+}
+
+impl TryFrom<AffinePoint> for UncompressedPoint {
+    type Error = Secp256k1Error;
+
+    /// Errors on the point at infinity, which has no affine encoding.
+    fn try_from(p: AffinePoint) -> Result<Self, Secp256k1Error> {
+        let encoded = p.to_encoded_point(false);
+        let bytes = encoded.as_bytes().get(1..65).ok_or(Secp256k1Error::InvalidPublicKey)?;
+        Ok(UncompressedPoint(bytes.try_into().map_err(|_| Secp256k1Error::InvalidPublicKey)?))
+    }
+}
+
+impl TryFrom<AffinePoint> for CompressedPoint {
+    type Error = Secp256k1Error;
+
+    /// Errors on the point at infinity, which has no affine encoding.
+    fn try_from(p: AffinePoint) -> Result<Self, Secp256k1Error> {
+        let encoded = p.to_encoded_point(true);
+        Ok(CompressedPoint(encoded.as_bytes().try_into().map_err(|_| Secp256k1Error::InvalidPublicKey)?))
+    }
+}
+
+impl TryFrom<UncompressedPoint> for PublicKey {
+    type Error = Secp256k1Error;
+
+    fn try_from(p: UncompressedPoint) -> Result<Self, Secp256k1Error> {
+        PublicKey::from_affine(AffinePoint::try_from(p)?).map_err(|_| Secp256k1Error::InvalidPublicKey)
+    }
+}
+
+impl TryFrom<CompressedPoint> for PublicKey {
+    type Error = Secp256k1Error;
+
+    fn try_from(p: CompressedPoint) -> Result<Self, Secp256k1Error> {
+        PublicKey::from_affine(AffinePoint::try_from(p)?).map_err(|_| Secp256k1Error::InvalidPublicKey)
+    }
+}
+
+impl From<PublicKey> for UncompressedPoint {
+    fn from(p: PublicKey) -> Self {
+        UncompressedPoint::try_from(*p.as_affine()).expect("public keys are never the identity")
+    }
+}
+
+impl From<PublicKey> for CompressedPoint {
+    fn from(p: PublicKey) -> Self {
+        CompressedPoint::try_from(*p.as_affine()).expect("public keys are never the identity")
+    }
+}
+
+impl From<&SecretKey> for NonZeroScalar {
+    fn from(k: &SecretKey) -> Self {
+        Option::from(NonZeroScalar::from_repr((*k.as_bytes()).into())).expect("secret keys are in 1..𝑁")
+    }
+}
+
+impl From<NonZeroScalar> for SecretKey {
+    fn from(k: NonZeroScalar) -> Self {
+        SecretKey::from_bytes(k.to_bytes().into()).expect("non-zero scalars are in 1..𝑁")
+    }
+}
+
+impl TryFrom<Scalar> for SecretKey {
+    type Error = Secp256k1Error;
+
+    fn try_from(k: Scalar) -> Result<Self, Secp256k1Error> {
+        SecretKey::from_bytes(k.to_bytes().into())
+    }
+}
+
+impl From<&SecretKey> for ::k256::SecretKey {
+    fn from(k: &SecretKey) -> Self {
+        ::k256::SecretKey::from(NonZeroScalar::from(k))
+    }
+}
+
+impl From<::k256::SecretKey> for SecretKey {
+    fn from(k: ::k256::SecretKey) -> Self {
+        SecretKey::from(k.to_nonzero_scalar())
+    }
+}
+
+impl TryFrom<RecoverableSignature> for (Signature, RecoveryId) {
+    type Error = Secp256k1Error;
+
+    /// Errors with `InvalidSignature` if `𝑟` or `𝑠` is zero or not less than 𝑁, or the recovery
+    /// id is above 3.
+    fn try_from(signature: RecoverableSignature) -> Result<Self, Secp256k1Error> {
+        let recovery_id = RecoveryId::from_byte(signature.recovery_id).ok_or(Secp256k1Error::InvalidSignature)?;
+        let signature = Signature::from_scalars(signature.r, signature.s).map_err(|_| Secp256k1Error::InvalidSignature)?;
+        Ok((signature, recovery_id))
+    }
+}
+
+impl From<(Signature, RecoveryId)> for RecoverableSignature {
+    fn from((signature, recovery_id): (Signature, RecoveryId)) -> Self {
+        let (r, s) = signature.split_bytes();
+        RecoverableSignature { r: r.into(), s: s.into(), recovery_id: recovery_id.to_byte() }
+    }
+}
+
+impl Mul<&Scalar> for UncompressedPoint {
+    type Output = Result<UncompressedPoint, Secp256k1Error>;
+
+    fn mul(self, k: &Scalar) -> Self::Output {
+        Curve::ecmul(&self, &k.to_bytes().into())
+    }
+}
+
+impl Mul<&Scalar> for CompressedPoint {
+    type Output = Result<UncompressedPoint, Secp256k1Error>;
+
+    fn mul(self, k: &Scalar) -> Self::Output {
+        Curve::ecmul(&self, &k.to_bytes().into())
+    }
+}
@@ -0,0 +1,44 @@
+//! # Key Aggregation
+//!
+//! Plain key aggregation `Σ𝑃ᵢ` for simple n-of-n schemes that don't need MuSig2 key
+//! coefficients. Keys are sorted lexicographically by their compressed encoding, as in BIP-67,
+//! and duplicates are dropped, so every party derives the same aggregate key from the same set
+//! of keys regardless of the order they were shared in.
+//!
+//! Without coefficients the aggregate is open to rogue key attacks, so each key should come
+//! with a proof of possession, eg: a signature over the key itself.
+
+use crate::{arithmetic, CompressedPoint, Secp256k1Error, Secp256k1Point, UncompressedPoint};
+
+/// ### Sort Keys
+///
+/// Sorts compressed keys lexicographically, per BIP-67.
+pub fn sort_keys<const M: usize>(keys: &[CompressedPoint; M]) -> [CompressedPoint; M] {
+    let mut sorted = *keys;
+    sorted.sort_unstable_by_key(|key| key.0);
+    sorted
+}
+
+/// ### Aggregate Keys
+///
+/// Sorts and deduplicates `keys`, then sums them with chained point additions. Errors with
+/// `InvalidPublicKey` if a key is not on the curve, and `IdentityPoint` if there are no keys or
+/// they sum to the point at infinity.
+pub fn aggregate_keys<const M: usize>(keys: &[CompressedPoint; M]) -> Result<UncompressedPoint, Secp256k1Error> {
+    let sorted = sort_keys(keys);
+    let mut acc: Option<[arithmetic::Limbs; 2]> = None;
+    for (i, key) in sorted.iter().enumerate() {
+        if i > 0 && sorted[i - 1] == *key {
+            continue;
+        }
+        let p = key.decompress().map_err(|_| Secp256k1Error::InvalidPublicKey)?;
+        let p = arithmetic::point_from_be_bytes(&p.0);
+        // A running sum at infinity is held as `None`
+        acc = match acc {
+            None => Some(p),
+            Some(acc) => arithmetic::affine_add_checked(&acc, &p),
+        };
+    }
+    acc.map(|p| UncompressedPoint(arithmetic::point_to_be_bytes(&p)))
+        .ok_or(Secp256k1Error::IdentityPoint)
+}
@@ -0,0 +1,92 @@
+//! # libp2p
+//!
+//! libp2p secp256k1 identities, so P2P networking layers colocated with Solana services can
+//! reuse the same keys and verification code. A public key is wrapped in the protobuf envelope
+//! `PublicKey { Type = Secp256k1, Data = compressed point }`, and as the envelope is under 42
+//! bytes, its PeerId is the identity multihash of the envelope itself, written in base58btc as
+//! `16Uiu2…`. The key can therefore always be recovered from the PeerId.
+//!
+//! libp2p signs with ECDSA over the SHA-256 digest of a message, with DER signatures, and
+//! rejects high-𝑠 signatures as libsecp256k1 does.
+
+use crate::{
+    arithmetic, backend, base58, der, signer::Secp256k1Signer, CompressedPoint, Curve, Secp256k1Error, Secp256k1Point,
+};
+
+/// The protobuf `KeyType` of a secp256k1 key.
+pub const KEY_TYPE_SECP256K1: u8 = 2;
+
+/// The multihash code of the identity hash.
+pub const IDENTITY_MULTIHASH: u8 = 0x00;
+
+/// The length of a base58btc PeerId.
+pub const PEER_ID_LEN: usize = 53;
+
+/// ### Encode Public Key
+///
+/// Encodes a public key in its protobuf envelope, `0x08 ‖ type ‖ 0x12 ‖ 33 ‖ point`.
+pub fn encode_public_key(pubkey: &CompressedPoint) -> [u8; 37] {
+    let mut bytes = [0u8; 37];
+    bytes[..4].copy_from_slice(&[0x08, KEY_TYPE_SECP256K1, 0x12, 33]);
+    bytes[4..].copy_from_slice(&pubkey.0);
+    bytes
+}
+
+/// ### Decode Public Key
+///
+/// Decodes a public key from its deterministic protobuf envelope. Errors with
+/// `InvalidPublicKey` on another key type, a non-canonical envelope or an off-curve point.
+pub fn decode_public_key(bytes: &[u8]) -> Result<CompressedPoint, Secp256k1Error> {
+    let pubkey = match bytes {
+        [0x08, KEY_TYPE_SECP256K1, 0x12, 33, key @ ..] => CompressedPoint(key.try_into().map_err(|_| Secp256k1Error::InvalidPublicKey)?),
+        _ => return Err(Secp256k1Error::InvalidPublicKey),
+    };
+    pubkey.decompress().map_err(|_| Secp256k1Error::InvalidPublicKey)?;
+    Ok(pubkey)
+}
+
+/// ### Peer ID
+///
+/// The binary PeerId of a public key, the identity multihash of its protobuf envelope.
+pub fn peer_id(pubkey: &CompressedPoint) -> [u8; 39] {
+    let mut bytes = [0u8; 39];
+    bytes[..2].copy_from_slice(&[IDENTITY_MULTIHASH, 37]);
+    bytes[2..].copy_from_slice(&encode_public_key(pubkey));
+    bytes
+}
+
+/// ### Encode Peer ID
+///
+/// Encodes the PeerId of a public key in base58btc.
+pub fn encode_peer_id<'a>(pubkey: &CompressedPoint, out: &'a mut [u8; PEER_ID_LEN]) -> &'a str {
+    let len = base58::encode(&peer_id(pubkey), out);
+    debug_assert_eq!(len, PEER_ID_LEN);
+    core::str::from_utf8(out).expect("ASCII PeerId")
+}
+
+/// ### Parse Peer ID
+///
+/// Parses a base58btc PeerId and extracts its public key. Errors with `InvalidPublicKey` if it
+/// is not the identity multihash of a secp256k1 key.
+pub fn parse_peer_id(peer_id: &str) -> Result<CompressedPoint, Secp256k1Error> {
+    match base58::decode(peer_id.as_bytes(), &mut [0u8; 39]) {
+        Some([IDENTITY_MULTIHASH, 37, envelope @ ..]) => decode_public_key(envelope),
+        _ => Err(Secp256k1Error::InvalidPublicKey),
+    }
+}
+
+/// ### Sign
+///
+/// Signs `msg` as libp2p does, writing the DER signature to `out` and returning it.
+pub fn sign<'a, S: Secp256k1Signer + ?Sized>(key: &S, msg: &[u8], out: &'a mut [u8; 72]) -> Result<&'a [u8], Secp256k1Error> {
+    let signature = key.sign_prehash(&backend::sha256(&[msg]))?;
+    Ok(der::encode_signature(&arithmetic::concat(&signature.r, &signature.s), out))
+}
+
+/// ### Verify
+///
+/// Verifies a libp2p DER signature over `msg`. Errors with `InvalidSignatureEncoding` on
+/// malformed DER and `InvalidSignature` if it does not verify.
+pub fn verify(pubkey: &CompressedPoint, msg: &[u8], signature: &[u8]) -> Result<(), Secp256k1Error> {
+    Curve::verify_ecdsa(&backend::sha256(&[msg]), &der::parse_signature(signature)?, pubkey)
+}
@@ -0,0 +1,53 @@
+//! # Light Client
+//!
+//! Validator set rotation for EVM and Cosmos light clients implemented as Solana programs. A
+//! light client tracks the current validator set and its voting power, and accepts the next
+//! set only once validators holding at least two thirds of the current set's power have signed
+//! its commitment.
+//!
+//! How a set is committed to is chain specific, so the caller hashes the new set and passes the
+//! hash in. Signatures must be ordered by strictly increasing signer, as in `multisig`.
+
+use crate::{eth::RecoverableSignature, multisig, multisig::Owner, Secp256k1Error};
+
+/// ### Validator
+///
+/// A validator's identity, eg: an `EthAddress` or a `CompressedPoint`, and its voting power.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Validator<T: Owner> {
+    pub owner: T,
+    pub power: u64,
+}
+
+/// ### Verify Rotation
+///
+/// Verifies that `signatures` over `commitment`, the hash of the next validator set, carry at
+/// least two thirds of the voting power of `validators`, the current set. Returns the signed
+/// power.
+///
+/// Errors as `multisig::verify_multisig` for signatures that do not recover, unknown signers or
+/// out of order signers, with `ArithmeticOverflow` if the total power overflows a `u64`, and
+/// with `ThresholdNotMet` if the signed power falls short or the set has no power at all.
+pub fn verify_rotation<T: Owner>(
+    commitment: &[u8; 32],
+    signatures: &[RecoverableSignature],
+    validators: &[Validator<T>],
+) -> Result<u64, Secp256k1Error> {
+    let total = validators
+        .iter()
+        .try_fold(0u64, |total, validator| total.checked_add(validator.power))
+        .ok_or(Secp256k1Error::ArithmeticOverflow)?;
+    if total == 0 {
+        return Err(Secp256k1Error::ThresholdNotMet);
+    }
+    // Signers are distinct, so their power sums to at most `total` and cannot overflow
+    let mut signed = 0u64;
+    multisig::for_each_signer(commitment, signatures, validators, |validator| validator.owner, |index| {
+        signed += validators[index].power
+    })?;
+    // 𝑠𝑖𝑔𝑛𝑒𝑑 ≥ ⅔·𝑡𝑜𝑡𝑎𝑙, widened so neither side overflows
+    if 3 * (signed as u128) < 2 * (total as u128) {
+        return Err(Secp256k1Error::ThresholdNotMet);
+    }
+    Ok(signed)
+}
@@ -0,0 +1,56 @@
+use crate::{backend, bip340};
+
+/// ### Message
+///
+/// A 32-byte message digest, ready to be signed or verified. A `Message` can only be built from
+/// a digest the caller already holds, or by hashing data with an explicit hash function, so
+/// unhashed or accidentally double-hashed data cannot be passed where a digest is expected.
+/// `SecretKey::sign_message`, `Curve::verify_ecdsa_message`, `RecoverableSignature::recover_message`
+/// and `bip340::{sign_message, verify_message}` take one directly; otherwise pass `as_bytes()` to
+/// any function taking a 32-byte `hash`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Message([u8; 32]);
+
+impl Message {
+    /// ### From Digest
+    ///
+    /// Wraps a digest computed elsewhere, eg: a transaction's sighash.
+    pub const fn from_digest(digest: [u8; 32]) -> Self {
+        Self(digest)
+    }
+
+    /// ### Keccak
+    ///
+    /// Hashes `data` with Keccak-256, as Ethereum signs.
+    pub fn keccak(data: &[u8]) -> Self {
+        Self(backend::keccak256(&[data]))
+    }
+
+    /// ### SHA-256
+    ///
+    /// Hashes `data` with SHA-256, as Cosmos signs.
+    pub fn sha256(data: &[u8]) -> Self {
+        Self(backend::sha256(&[data]))
+    }
+
+    /// ### Tagged
+    ///
+    /// Hashes `data` with the BIP-340 tagged hash `SHA-256(SHA-256(tag) ‖ SHA-256(tag) ‖ data)`.
+    pub fn tagged(tag: &[u8], data: &[u8]) -> Self {
+        Self(bip340::tagged_hash(tag, &[data]))
+    }
+
+    /// ### As Bytes
+    ///
+    /// Borrows the digest.
+    pub const fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<Message> for [u8; 32] {
+    fn from(message: Message) -> Self {
+        message.0
+    }
+}
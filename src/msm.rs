@@ -0,0 +1,363 @@
+//! # MSM
+//!
+//! Resumable multi-scalar multiplication `Σ 𝒌ᵢ·𝑃ᵢ`. Each term costs an `ecmul`, so large sums,
+//! such as batch verification equations or long point addition chains, can exceed the compute
+//! budget of a single instruction.
+//!
+//! `MsmState` holds the running sum and the index of the next term, and serializes to a fixed
+//! `MsmState::SIZE` bytes, so a program can store it in an account, process a few terms per
+//! instruction and pick up where it left off in a later instruction or transaction.
+//!
+//! `AddCursor` does the same for plain point sums `Σ 𝑃ᵢ`, without any `ecmul`s, stepping as far
+//! as a CU budget allows rather than by a fixed number of terms.
+//!
+//! Both commit to a SHA-256 hash of their full list of terms when started, and every step
+//! checks the list it is given against it, so a later instruction can't swap in other terms
+//! partway through a sum.
+
+use crate::{
+    arithmetic, backend,
+    cu::{cu_estimate, Operation},
+    Curve, Secp256k1Error, Secp256k1Point, UncompressedPoint,
+};
+
+const ONE: [u8; 32] = arithmetic::to_be_bytes(&[1, 0, 0, 0]);
+
+/// Terms hashed per `sol_sha256` call when committing to a list.
+const COMMIT_CHUNK: usize = 8;
+
+/// Commits to a list of points, each with an optional scalar, as a chain of SHA-256 hashes over
+/// chunks of `COMMIT_CHUNK` terms. Points are hashed in compressed form, which is all `ecmul`
+/// and decompression read of them.
+fn commit<'a, T: Secp256k1Point + 'a>(terms: impl Iterator<Item = (&'a T, &'a [u8])>) -> [u8; 32] {
+    let mut commitment = [0u8; 32];
+    let mut terms = terms.peekable();
+    while terms.peek().is_some() {
+        let prev = commitment;
+        let mut vals: [&[u8]; 1 + 3 * COMMIT_CHUNK] = [&[]; 1 + 3 * COMMIT_CHUNK];
+        vals[0] = &prev;
+        let mut len = 1;
+        for (point, k) in terms.by_ref().take(COMMIT_CHUNK) {
+            vals[len] = if point.is_odd() { &[0x03] } else { &[0x02] };
+            vals[len + 1] = point.x_ref();
+            vals[len + 2] = k;
+            len += 3;
+        }
+        commitment = backend::sha256(&vals[..len]);
+    }
+    commitment
+}
+
+/// Checks a list's length fits a `u32`, for the term counts stored in sums.
+fn term_count(len: usize) -> Result<u32, Secp256k1Error> {
+    u32::try_from(len).map_err(|_| Secp256k1Error::InvalidState)
+}
+
+/// ### MSM State
+///
+/// The intermediate state of `Σ 𝒌ᵢ·𝑃ᵢ` over a fixed list of terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+pub struct MsmState {
+    acc: Option<UncompressedPoint>,
+    next: u32,
+    len: u32,
+    commitment: [u8; 32],
+}
+
+impl MsmState {
+    /// ### Size
+    ///
+    /// Serialized size: `flag[1] ‖ acc[64] ‖ next[4] ‖ len[4] ‖ commitment[32]`, with integers
+    /// little-endian.
+    pub const SIZE: usize = 105;
+
+    /// ### New
+    ///
+    /// Starts a sum over `terms`, committing to them. Errors with `InvalidState` if there are
+    /// more than `u32::MAX` terms.
+    pub fn new<T: Secp256k1Point>(terms: &[(T, [u8; 32])]) -> Result<Self, Secp256k1Error> {
+        Ok(Self { acc: None, next: 0, len: term_count(terms.len())?, commitment: Self::commit(terms) })
+    }
+
+    /// ### Step
+    ///
+    /// Adds up to `max_terms` more terms to the sum, returning `true` once every term has been
+    /// processed. `terms` must be the same full list on every call, and is checked against the
+    /// length and commitment the sum was started with, erroring with `InvalidState` otherwise.
+    ///
+    /// Terms with a scalar of `1` skip the `ecmul`, so plain point additions are cheap, and terms
+    /// with a scalar of `0` are skipped entirely.
+    pub fn step<T: Secp256k1Point>(&mut self, terms: &[(T, [u8; 32])], max_terms: usize) -> Result<bool, Secp256k1Error> {
+        if terms.len() != self.len as usize || Self::commit(terms) != self.commitment {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        let end = terms.len().min((self.next as usize).saturating_add(max_terms));
+        let mut acc = self.acc;
+        for (point, k) in &terms[self.next as usize..end] {
+            let term = if *k == [0u8; 32] {
+                continue;
+            } else if *k == ONE {
+                point.decompress()?
+            } else {
+                Curve::ecmul(point, k)?
+            };
+            acc = match acc {
+                None => Some(term),
+                Some(acc) => arithmetic::affine_add_checked(
+                    &arithmetic::point_from_be_bytes(&acc.0),
+                    &arithmetic::point_from_be_bytes(&term.0),
+                )
+                .map(|p| UncompressedPoint(arithmetic::point_to_be_bytes(&p))),
+            };
+        }
+        // Only commit progress once every term in this step has succeeded
+        self.acc = acc;
+        self.next = end as u32;
+        Ok(self.is_done())
+    }
+
+    /// ### Is Done
+    ///
+    /// Returns true once every term has been processed.
+    pub const fn is_done(&self) -> bool {
+        self.next == self.len
+    }
+
+    /// ### Result
+    ///
+    /// Returns the finished sum. Errors with `InvalidState` if terms remain, or `IdentityPoint`
+    /// if the sum is the point at infinity.
+    pub fn result(&self) -> Result<UncompressedPoint, Secp256k1Error> {
+        if !self.is_done() {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        self.acc.ok_or(Secp256k1Error::IdentityPoint)
+    }
+
+    /// ### Commitment
+    ///
+    /// The hash of the terms the sum was started with.
+    pub const fn commitment(&self) -> &[u8; 32] {
+        &self.commitment
+    }
+
+    /// ### To Bytes
+    ///
+    /// Serializes the state for storage in an account.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        if let Some(acc) = self.acc {
+            bytes[0] = 1;
+            bytes[1..65].copy_from_slice(&acc.0);
+        }
+        bytes[65..69].copy_from_slice(&self.next.to_le_bytes());
+        bytes[69..73].copy_from_slice(&self.len.to_le_bytes());
+        bytes[73..].copy_from_slice(&self.commitment);
+        bytes
+    }
+
+    /// ### From Bytes
+    ///
+    /// Deserializes a stored state, checking the flag, that the running sum is on curve and
+    /// that progress does not exceed the number of terms.
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, Secp256k1Error> {
+        let point = UncompressedPoint(bytes[1..65].try_into().expect("64 bytes"));
+        let acc = match bytes[0] {
+            0 if point.0 == [0u8; 64] => None,
+            1 if point.is_on_curve() => Some(point),
+            _ => return Err(Secp256k1Error::InvalidState),
+        };
+        let next = u32::from_le_bytes(bytes[65..69].try_into().expect("4 bytes"));
+        let len = u32::from_le_bytes(bytes[69..73].try_into().expect("4 bytes"));
+        if next > len {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        Ok(Self { acc, next, len, commitment: bytes[73..].try_into().expect("32 bytes") })
+    }
+
+    fn commit<T: Secp256k1Point>(terms: &[(T, [u8; 32])]) -> [u8; 32] {
+        commit(terms.iter().map(|(point, k)| (point, k.as_slice())))
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for MsmState {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for MsmState {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let mut bytes = [0u8; Self::SIZE];
+        reader.read_exact(&mut bytes)?;
+        Self::from_bytes(&bytes).map_err(|_| borsh::io::Error::new(borsh::io::ErrorKind::InvalidData, "Invalid MSM state"))
+    }
+}
+
+/// ### Add Cursor
+///
+/// The intermediate state of `Σ 𝑃ᵢ` over a fixed list of points, as in `Curve::ecadd_many`.
+///
+/// Points are accumulated in Jacobian coordinates `(𝑋, 𝑌, 𝑍)`, so each costs a dozen field
+/// multiplications instead of the field inversion of an affine addition, and equal or opposite
+/// points double or cancel on the way. The single inversion back to affine happens in `result`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+pub struct AddCursor {
+    acc: [[u64; 4]; 3],
+    next: u32,
+    len: u32,
+    commitment: [u8; 32],
+}
+
+impl AddCursor {
+    /// ### Size
+    ///
+    /// Serialized size: `𝑋[32] ‖ 𝑌[32] ‖ 𝑍[32] ‖ next[4] ‖ len[4] ‖ commitment[32]`, with
+    /// coordinates big-endian and integers little-endian.
+    pub const SIZE: usize = 136;
+
+    /// ### New
+    ///
+    /// Starts a sum over `points`, committing to them. Errors with `InvalidState` if there are
+    /// more than `u32::MAX` points.
+    pub fn new<T: Secp256k1Point>(points: &[T]) -> Result<Self, Secp256k1Error> {
+        Ok(Self { acc: arithmetic::JACOBIAN_IDENTITY, next: 0, len: term_count(points.len())?, commitment: Self::commit(points) })
+    }
+
+    /// ### Step
+    ///
+    /// Adds as many more points as fit in `budget` CUs by `cu_estimate`, returning the
+    /// estimated CUs consumed. Stops before the point that would exceed the budget, so pass
+    /// `sol_remaining_compute_units` less a margin for the rest of the instruction, and store
+    /// the cursor to resume in a later instruction. `points` must be the same full list on
+    /// every call, and is checked against the length and commitment the sum was started with,
+    /// erroring with `InvalidState` otherwise.
+    ///
+    /// Hashing `points` for that check, a `sol_sha256` per 8 points, and the
+    /// `cu_estimate(Operation::ModInv)` of `result` are not counted here.
+    pub fn step<T: Secp256k1Point>(&mut self, points: &[T], budget: u64) -> Result<u64, Secp256k1Error> {
+        if points.len() != self.len as usize || Self::commit(points) != self.commitment {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        let cost = cu_estimate(Operation::AddStep { compressed: T::SIZE == 33 });
+        let mut acc = self.acc;
+        let mut next = self.next as usize;
+        let mut consumed = 0u64;
+        while next < points.len() && consumed.saturating_add(cost) <= budget {
+            let point = points[next].decompress()?;
+            acc = arithmetic::jacobian_add_affine(&acc, &arithmetic::point_from_be_bytes(&point.0));
+            next += 1;
+            consumed = consumed.saturating_add(cost);
+        }
+        // Only commit progress once every point in this step has succeeded
+        self.acc = acc;
+        self.next = next as u32;
+        Ok(consumed)
+    }
+
+    /// ### Is Done
+    ///
+    /// Returns true once every point has been added.
+    pub const fn is_done(&self) -> bool {
+        self.next == self.len
+    }
+
+    /// ### Next
+    ///
+    /// The index of the next point to add.
+    pub const fn next(&self) -> u32 {
+        self.next
+    }
+
+    /// ### Commitment
+    ///
+    /// The hash of the points the sum was started with.
+    pub const fn commitment(&self) -> &[u8; 32] {
+        &self.commitment
+    }
+
+    /// ### Result
+    ///
+    /// Returns the finished sum. Errors with `InvalidState` if points remain, or
+    /// `IdentityPoint` if the sum is the point at infinity.
+    pub fn result(&self) -> Result<UncompressedPoint, Secp256k1Error> {
+        if !self.is_done() {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        arithmetic::jacobian_to_affine(&self.acc)
+            .map(|p| UncompressedPoint(arithmetic::point_to_be_bytes(&p)))
+            .ok_or(Secp256k1Error::IdentityPoint)
+    }
+
+    /// ### To Bytes
+    ///
+    /// Serializes the cursor for storage in an account.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        for (chunk, coordinate) in bytes[..96].chunks_exact_mut(32).zip(&self.acc) {
+            chunk.copy_from_slice(&arithmetic::to_be_bytes(coordinate));
+        }
+        bytes[96..100].copy_from_slice(&self.next.to_le_bytes());
+        bytes[100..104].copy_from_slice(&self.len.to_le_bytes());
+        bytes[104..].copy_from_slice(&self.commitment);
+        bytes
+    }
+
+    /// ### From Bytes
+    ///
+    /// Deserializes a stored cursor, checking that the running sum is canonical and on curve,
+    /// `𝑌² = 𝑋³ + 7·𝑍⁶`, and that progress does not exceed the number of points.
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, Secp256k1Error> {
+        let mut acc = [[0u64; 4]; 3];
+        for (coordinate, chunk) in acc.iter_mut().zip(bytes[..96].chunks_exact(32)) {
+            let chunk: &[u8; 32] = chunk.try_into().expect("32 bytes");
+            if *chunk >= Curve::P {
+                return Err(Secp256k1Error::InvalidState);
+            }
+            *coordinate = arithmetic::from_be_bytes(chunk);
+        }
+        let [x, y, z] = &acc;
+        let on_curve = if arithmetic::eq(z, &[0; 4]) {
+            acc == arithmetic::JACOBIAN_IDENTITY
+        } else {
+            let z2 = arithmetic::mul_mod(z, z, &arithmetic::P);
+            let z6 = arithmetic::mul_mod(&arithmetic::mul_mod(&z2, &z2, &arithmetic::P), &z2, &arithmetic::P);
+            let rhs = arithmetic::add_mod(
+                &arithmetic::mul_mod(&arithmetic::mul_mod(x, x, &arithmetic::P), x, &arithmetic::P),
+                &arithmetic::mul_mod(&[7, 0, 0, 0], &z6, &arithmetic::P),
+                &arithmetic::P,
+            );
+            arithmetic::mul_mod(y, y, &arithmetic::P) == rhs
+        };
+        let next = u32::from_le_bytes(bytes[96..100].try_into().expect("4 bytes"));
+        let len = u32::from_le_bytes(bytes[100..104].try_into().expect("4 bytes"));
+        if !on_curve || next > len {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        Ok(Self { acc, next, len, commitment: bytes[104..].try_into().expect("32 bytes") })
+    }
+
+    fn commit<T: Secp256k1Point>(points: &[T]) -> [u8; 32] {
+        commit(points.iter().map(|point| (point, [].as_slice())))
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for AddCursor {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for AddCursor {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let mut bytes = [0u8; Self::SIZE];
+        reader.read_exact(&mut bytes)?;
+        Self::from_bytes(&bytes).map_err(|_| borsh::io::Error::new(borsh::io::ErrorKind::InvalidData, "Invalid add cursor"))
+    }
+}
@@ -0,0 +1,91 @@
+//! # Multisig
+//!
+//! Threshold verification of ECDSA signatures against a fixed owner set, the core loop of
+//! bridge, guardian and governance verifiers. Owners are either Ethereum addresses or
+//! compressed public keys.
+//!
+//! Signatures must be ordered by strictly increasing signer, as in Gnosis Safe, so duplicates
+//! are rejected with a single comparison per signature rather than a search of every signer
+//! seen so far.
+
+use crate::{eth::EthAddress, eth::RecoverableSignature, CompressedPoint, Secp256k1Error, Secp256k1Point, UncompressedPoint};
+
+/// ### Owner
+///
+/// An identity a recovered signer is matched against, ordered by its bytes.
+pub trait Owner: Copy + PartialEq {
+    /// Maps a recovered public key to its identity.
+    fn from_signer(signer: &UncompressedPoint) -> Self;
+
+    /// The bytes signers are ordered by.
+    fn as_bytes(&self) -> &[u8];
+}
+
+impl Owner for EthAddress {
+    fn from_signer(signer: &UncompressedPoint) -> Self {
+        EthAddress::from(*signer)
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Owner for CompressedPoint {
+    fn from_signer(signer: &UncompressedPoint) -> Self {
+        signer.compress()
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// ### Verify Multisig
+///
+/// Recovers the signer of each signature over `hash` and checks it is one of `owners`,
+/// returning which owners signed. Errors with:
+///
+/// - `InvalidSignature` or a recover error if a signature does not recover,
+/// - `UnknownSigner` if a signer is not an owner,
+/// - `DuplicateSigner` if signers are not in strictly increasing order of their bytes, which
+///   includes any signer repeating,
+/// - `ThresholdNotMet` if there are fewer than `threshold` signatures, before paying for any
+///   recovers. A threshold of zero is always rejected, as it would accept no signatures at all.
+///
+/// Every signer is a distinct owner, so the threshold is met once the loop completes.
+pub fn verify_multisig<T: Owner, const M: usize>(
+    hash: &[u8; 32],
+    signatures: &[RecoverableSignature],
+    owners: &[T; M],
+    threshold: usize,
+) -> Result<[bool; M], Secp256k1Error> {
+    if threshold == 0 || threshold > M || signatures.len() < threshold {
+        return Err(Secp256k1Error::ThresholdNotMet);
+    }
+    let mut signed = [false; M];
+    for_each_signer(hash, signatures, owners, |owner| *owner, |index| signed[index] = true)?;
+    Ok(signed)
+}
+
+/// Recovers each signer in turn, enforcing strictly increasing order, and calls `signed` with
+/// the index of the member of `set` whose `owner` it is.
+pub(crate) fn for_each_signer<T: Owner, E>(
+    hash: &[u8; 32],
+    signatures: &[RecoverableSignature],
+    set: &[E],
+    owner: impl Fn(&E) -> T,
+    mut signed: impl FnMut(usize),
+) -> Result<(), Secp256k1Error> {
+    let mut previous: Option<T> = None;
+    for signature in signatures {
+        let signer = T::from_signer(&signature.recover(hash)?);
+        if previous.is_some_and(|previous| signer.as_bytes() <= previous.as_bytes()) {
+            return Err(Secp256k1Error::DuplicateSigner);
+        }
+        let index = set.iter().position(|member| owner(member) == signer).ok_or(Secp256k1Error::UnknownSigner)?;
+        signed(index);
+        previous = Some(signer);
+    }
+    Ok(())
+}
@@ -0,0 +1,238 @@
+//! # Nostr
+//!
+//! NIP-44 v2 encryption between Nostr keys, so services built on this crate's key types can
+//! produce and consume encrypted direct message payloads, and NIP-26 delegation, so on-chain
+//! Nostr registries can accept events published on a delegator's behalf.
+//!
+//! Both parties derive the same conversation key, `HKDF-Extract("nip44-v2", (𝒂·𝐵)ₓ)`, from
+//! their secret key and the other's x-only public key. Each message then derives a ChaCha20
+//! key and nonce and an HMAC-SHA256 key from the conversation key and a random 32-byte nonce.
+//! Payloads are handled as raw bytes, `0x02 ‖ nonce ‖ ciphertext ‖ mac`, and base64 encoding
+//! them for an event's `content` is left to the caller.
+//!
+//! A NIP-26 delegation tag `["delegation", delegator, conditions, token]` carries a BIP-340
+//! signature by the delegator over `SHA-256("nostr:delegation:" ‖ delegatee ‖ ":" ‖
+//! conditions)`, with keys in lowercase hex. Conditions are `&`-separated `kind=𝑛`,
+//! `created_at<𝑡` and `created_at>𝑡` clauses, all of which an event must satisfy.
+
+use crate::{backend, bip340, chacha20, hex, rfc6979::hmac_sha256, CompressedPoint, Curve, Secp256k1Error, Secp256k1Point, SecretKey};
+
+/// The NIP-44 payload version implemented here.
+pub const NIP44_VERSION: u8 = 2;
+
+/// The longest plaintext a NIP-44 payload can carry.
+pub const NIP44_MAX_PLAINTEXT_LEN: usize = 65535;
+
+/// ### Message Keys
+///
+/// The per-message keys `HKDF-Expand(conversation_key, nonce, 76)` of a NIP-44 payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageKeys {
+    pub chacha_key: [u8; 32],
+    pub chacha_nonce: [u8; 12],
+    pub hmac_key: [u8; 32],
+}
+
+/// ### Conversation Key
+///
+/// Derives the NIP-44 conversation key between `key` and the x-only public key `pubkey_x`,
+/// the same from either side of the conversation. Errors with `InvalidPublicKey` if `pubkey_x`
+/// is not on curve.
+pub fn conversation_key(key: &SecretKey, pubkey_x: &[u8; 32]) -> Result<[u8; 32], Secp256k1Error> {
+    let mut pubkey = CompressedPoint([0x02; 33]);
+    pubkey.0[1..].copy_from_slice(pubkey_x);
+    let shared = Curve::ecmul(&pubkey, key.as_bytes())?;
+    Ok(hmac_sha256(b"nip44-v2", &[shared.x_ref()]))
+}
+
+/// ### Message Keys
+///
+/// Expands a conversation key and a message's nonce into its ChaCha20 and HMAC keys.
+pub fn message_keys(conversation_key: &[u8; 32], nonce: &[u8; 32]) -> MessageKeys {
+    let t1 = hmac_sha256(conversation_key, &[nonce, &[1]]);
+    let t2 = hmac_sha256(conversation_key, &[&t1, nonce, &[2]]);
+    let t3 = hmac_sha256(conversation_key, &[&t2, nonce, &[3]]);
+    let mut keys = MessageKeys { chacha_key: t1, chacha_nonce: [0u8; 12], hmac_key: [0u8; 32] };
+    keys.chacha_nonce.copy_from_slice(&t2[..12]);
+    keys.hmac_key[..20].copy_from_slice(&t2[12..]);
+    keys.hmac_key[20..].copy_from_slice(&t3[..12]);
+    keys
+}
+
+/// ### Padded Len
+///
+/// The length a plaintext of `len` bytes is padded to: at least 32 bytes, then in chunks of
+/// 32 bytes up to 256 and of an eighth of the next power of two above that.
+pub const fn padded_len(len: usize) -> usize {
+    if len <= 32 {
+        return 32;
+    }
+    let next_power = 1usize << (usize::BITS - (len - 1).leading_zeros());
+    let chunk = if next_power <= 256 { 32 } else { next_power / 8 };
+    chunk * ((len - 1) / chunk + 1)
+}
+
+/// ### Payload Len
+///
+/// The length of the raw payload encrypting a plaintext of `len` bytes.
+pub const fn payload_len(len: usize) -> usize {
+    1 + 32 + 2 + padded_len(len) + 32
+}
+
+/// ### Encrypt
+///
+/// Encrypts `plaintext` under a conversation key with a fresh random `nonce`, writing the raw
+/// payload to the start of `out` and returning its length, `payload_len(plaintext.len())`.
+/// Errors with `InvalidPayload` if the plaintext is empty or longer than 65535 bytes, or if
+/// `out` is too short.
+pub fn encrypt(
+    conversation_key: &[u8; 32],
+    nonce: &[u8; 32],
+    plaintext: &[u8],
+    out: &mut [u8],
+) -> Result<usize, Secp256k1Error> {
+    let len = plaintext.len();
+    if len == 0 || len > NIP44_MAX_PLAINTEXT_LEN || out.len() < payload_len(len) {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    let keys = message_keys(conversation_key, nonce);
+    let (header, rest) = out.split_at_mut(33);
+    header[0] = NIP44_VERSION;
+    header[1..].copy_from_slice(nonce);
+
+    // 𝑢𝑖𝑛𝑡16(𝑙𝑒𝑛) ‖ plaintext ‖ zeros, encrypted in place
+    let (ciphertext, rest) = rest.split_at_mut(2 + padded_len(len));
+    ciphertext[..2].copy_from_slice(&(len as u16).to_be_bytes());
+    ciphertext[2..2 + len].copy_from_slice(plaintext);
+    ciphertext[2 + len..].fill(0);
+    chacha20::apply_keystream(&keys.chacha_key, &keys.chacha_nonce, 0, ciphertext);
+
+    rest[..32].copy_from_slice(&hmac_sha256(&keys.hmac_key, &[nonce, ciphertext]));
+    Ok(payload_len(len))
+}
+
+/// ### Decrypt
+///
+/// Authenticates and decrypts a raw payload under a conversation key, using `out` as scratch
+/// space for the padded plaintext, and returns the plaintext within it. Errors with
+/// `InvalidPayload` on an unknown version, a bad length, MAC or padding, or if `out` is
+/// shorter than the ciphertext.
+pub fn decrypt<'a>(
+    conversation_key: &[u8; 32],
+    payload: &[u8],
+    out: &'a mut [u8],
+) -> Result<&'a [u8], Secp256k1Error> {
+    if payload.len() < payload_len(1) || payload.len() > payload_len(NIP44_MAX_PLAINTEXT_LEN) {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    let (header, rest) = payload.split_at(33);
+    let (ciphertext, mac) = rest.split_at(rest.len() - 32);
+    if header[0] != NIP44_VERSION || out.len() < ciphertext.len() {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    let nonce: &[u8; 32] = header[1..].try_into().expect("32 byte nonce");
+    let keys = message_keys(conversation_key, nonce);
+
+    // Compare every byte, so the time taken does not reveal how much of the MAC matched
+    let expected = hmac_sha256(&keys.hmac_key, &[nonce, ciphertext]);
+    if expected.iter().zip(mac).fold(0u8, |diff, (a, b)| diff | (a ^ b)) != 0 {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+
+    let padded = &mut out[..ciphertext.len()];
+    padded.copy_from_slice(ciphertext);
+    chacha20::apply_keystream(&keys.chacha_key, &keys.chacha_nonce, 0, padded);
+    let len = u16::from_be_bytes([padded[0], padded[1]]) as usize;
+    if len == 0 || 2 + padded_len(len) != padded.len() {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    Ok(&out[2..2 + len])
+}
+
+/// ### Delegation
+///
+/// A parsed NIP-26 delegation tag, borrowing its conditions from the tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delegation<'a> {
+    pub delegator: [u8; 32],
+    pub conditions: &'a str,
+    pub token: [u8; 64],
+}
+
+impl<'a> Delegation<'a> {
+    /// ### From Tag
+    ///
+    /// Parses the fields of a `["delegation", delegator, conditions, token]` event tag. Errors
+    /// with `InvalidPayload` if the tag is not a delegation tag, a field is not lowercase hex
+    /// of the right length, or the conditions are malformed.
+    pub fn from_tag(tag: &[&'a str]) -> Result<Self, Secp256k1Error> {
+        let [name, delegator, conditions, token] = tag else {
+            return Err(Secp256k1Error::InvalidPayload);
+        };
+        if *name != "delegation" {
+            return Err(Secp256k1Error::InvalidPayload);
+        }
+        let delegation = Self { delegator: decode_hex(delegator)?, conditions, token: decode_hex(token)? };
+        // Reject malformed conditions up front, rather than on the first event checked
+        delegation.allows(0, 0)?;
+        Ok(delegation)
+    }
+
+    /// ### Verify
+    ///
+    /// Verifies the token is the delegator's signature delegating to the x-only public key
+    /// `delegatee`, erroring with `InvalidSignature` if not.
+    pub fn verify(&self, delegatee: &[u8; 32]) -> Result<(), Secp256k1Error> {
+        let mut delegatee_hex = [0u8; 64];
+        hex::encode_to_slice(delegatee, &mut delegatee_hex).expect("64 hex digits");
+        let hash = backend::sha256(&[b"nostr:delegation:", &delegatee_hex, b":", self.conditions.as_bytes()]);
+        bip340::verify(&self.delegator, &hash, &self.token)
+    }
+
+    /// ### Allows
+    ///
+    /// Checks an event of `kind` published at `created_at` satisfies every condition. Errors
+    /// with `InvalidPayload` if the conditions are malformed or contain an unknown clause.
+    pub fn allows(&self, kind: u64, created_at: u64) -> Result<bool, Secp256k1Error> {
+        let mut allowed = true;
+        for clause in self.conditions.split('&').filter(|clause| !clause.is_empty()) {
+            allowed &= if let Some(value) = clause.strip_prefix("kind=") {
+                kind == parse_u64(value)?
+            } else if let Some(value) = clause.strip_prefix("created_at<") {
+                created_at < parse_u64(value)?
+            } else if let Some(value) = clause.strip_prefix("created_at>") {
+                created_at > parse_u64(value)?
+            } else {
+                return Err(Secp256k1Error::InvalidPayload);
+            };
+        }
+        Ok(allowed)
+    }
+
+    /// ### Verify Event
+    ///
+    /// Verifies the delegation authorizes `delegatee` to publish an event of `kind` at
+    /// `created_at` for the delegator, erroring with `InvalidSignature` if the token is invalid
+    /// or the event falls outside the conditions.
+    pub fn verify_event(&self, delegatee: &[u8; 32], kind: u64, created_at: u64) -> Result<(), Secp256k1Error> {
+        if !self.allows(kind, created_at)? {
+            return Err(Secp256k1Error::InvalidSignature);
+        }
+        self.verify(delegatee)
+    }
+}
+
+/// Decodes lowercase hex, as NIP-01 requires.
+fn decode_hex<const N: usize>(value: &str) -> Result<[u8; N], Secp256k1Error> {
+    if value.bytes().any(|c| c.is_ascii_uppercase()) {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    hex::decode(value)
+}
+
+fn parse_u64(value: &str) -> Result<u64, Secp256k1Error> {
+    if value.is_empty() || !value.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    value.parse().map_err(|_| Secp256k1Error::InvalidPayload)
+}
@@ -0,0 +1,206 @@
+//! # OPRF
+//!
+//! The 2HashDH oblivious pseudorandom function `𝐹(𝒌, 𝑥) = 𝐻₂(𝑥, 𝒌·𝐻₁(𝑥))`, for password-based
+//! and privacy-preserving login flows. A client blinds its input, the server evaluates it with
+//! its secret key without learning the input, and the client unblinds and finalizes the result.
+//!
+//! In verifiable mode (VOPRF) the server also proves, with a Chaum-Pedersen DLEQ proof, that it
+//! evaluated with the secret key behind its published public key.
+//!
+//! The protocol is structured after RFC 9497, with context strings for a `secp256k1-SHA256`
+//! suite and `hash_to_curve` as `𝐻₁`. secp256k1 is not a registered RFC 9497 suite, so outputs
+//! are only compatible with other implementations of this same scheme.
+
+use crate::{
+    arithmetic, backend,
+    challenge::{ChallengeHasher, Sha256},
+    hash_to_curve,
+    transcript::Transcript,
+    Curve, Secp256k1Error, Secp256k1Point, SecretKey,
+    UncompressedPoint,
+};
+
+/// ### Mode
+///
+/// Whether the server's evaluation is verifiable. The mode is bound into every hash, so the
+/// outputs of the two modes are unrelated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Mode {
+    Oprf = 0,
+    Voprf = 1,
+}
+
+impl Mode {
+    const fn hash_to_group_dst(self) -> &'static [u8] {
+        match self {
+            Mode::Oprf => b"HashToGroup-OPRFV1-\x00-secp256k1-SHA256",
+            Mode::Voprf => b"HashToGroup-OPRFV1-\x01-secp256k1-SHA256",
+        }
+    }
+}
+
+const CHALLENGE_DOMAIN: &[u8] = b"DLEQ-OPRFV1-\x01-secp256k1-SHA256";
+
+/// ### Proof
+///
+/// A DLEQ proof `(𝑐, 𝑠)` that `log_𝐺(𝑌) = log_𝐵(𝑍)` for the server's public key `𝑌`, a blinded
+/// element `𝐵` and its evaluation `𝑍`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Proof {
+    pub c: [u8; 32],
+    pub s: [u8; 32],
+}
+
+/// ### Blind
+///
+/// Client side: hashes `input` to a point and blinds it with the secret scalar `blind`, which
+/// must be freshly random for every evaluation and kept to unblind the result.
+pub fn blind(mode: Mode, input: &[u8], blind: &[u8; 32]) -> Result<UncompressedPoint, Secp256k1Error> {
+    Curve::ecmul(&hash_to_curve::hash_to_curve(input, mode.hash_to_group_dst())?, blind)
+}
+
+/// ### Evaluate
+///
+/// Server side: evaluates a blinded element `𝐵` as `𝒌·𝐵`, checking it is on curve.
+pub fn evaluate(key: &SecretKey, blinded: &UncompressedPoint) -> Result<UncompressedPoint, Secp256k1Error> {
+    if !blinded.is_on_curve() {
+        return Err(Secp256k1Error::InvalidPublicKey);
+    }
+    Curve::ecmul_unchecked(blinded, key.as_bytes())
+}
+
+/// ### Prove
+///
+/// Server side, in verifiable mode: proves `evaluated` is `𝒌·𝐵` for the key behind the
+/// server's public key, using the secret scalar `nonce`, which must be freshly random for every
+/// proof.
+pub fn prove(
+    key: &SecretKey,
+    blinded: &UncompressedPoint,
+    evaluated: &UncompressedPoint,
+    nonce: &[u8; 32],
+) -> Result<Proof, Secp256k1Error> {
+    prove_with(Sha256, key, blinded, evaluated, nonce)
+}
+
+/// ### Prove With
+///
+/// Proves an evaluation as `prove` does, deriving the challenge with `hasher`, eg:
+/// `challenge::Keccak256` for proofs checked by an EVM contract.
+pub fn prove_with<H: ChallengeHasher>(
+    hasher: H,
+    key: &SecretKey,
+    blinded: &UncompressedPoint,
+    evaluated: &UncompressedPoint,
+    nonce: &[u8; 32],
+) -> Result<Proof, Secp256k1Error> {
+    let t2 = Curve::mul_g(nonce)?;
+    let t3 = Curve::ecmul(blinded, nonce)?;
+    let c = challenge(hasher, &key.public_key()?, blinded, evaluated, &t2, &t3);
+    let s = Curve::add_mod_n(nonce, &Curve::negate_n(&Curve::mul_mod_n(&c, key.as_bytes())));
+    Ok(Proof { c, s })
+}
+
+/// ### Verify Proof
+///
+/// Client side, in verifiable mode: checks `evaluated` is the evaluation of `blinded` under
+/// the secret key of `pubkey`, by recomputing `𝑡₂ = 𝑠·𝐺 + 𝑐·𝑌` and `𝑡₃ = 𝑠·𝐵 + 𝑐·𝑍` and
+/// checking they hash to `𝑐`. `𝑡₂` is calculated with a single recover.
+pub fn verify_proof(
+    pubkey: &UncompressedPoint,
+    blinded: &UncompressedPoint,
+    evaluated: &UncompressedPoint,
+    proof: &Proof,
+) -> Result<(), Secp256k1Error> {
+    verify_proof_with(Sha256, pubkey, blinded, evaluated, proof)
+}
+
+/// ### Verify Proof With
+///
+/// Verifies a proof made by `prove_with` with the same `hasher`.
+pub fn verify_proof_with<H: ChallengeHasher>(
+    hasher: H,
+    pubkey: &UncompressedPoint,
+    blinded: &UncompressedPoint,
+    evaluated: &UncompressedPoint,
+    proof: &Proof,
+) -> Result<(), Secp256k1Error> {
+    if proof.c >= Curve::N
+        || proof.s >= Curve::N
+        || !pubkey.is_on_curve()
+        || !blinded.is_on_curve()
+        || !evaluated.is_on_curve()
+    {
+        return Err(Secp256k1Error::InvalidSignature);
+    }
+    // 𝑌ₓ⁻¹(𝑐𝑌ₓ·𝑌 + 𝑠𝑌ₓ·𝐺) = 𝑠·𝐺 + 𝑐·𝑌
+    let x = pubkey.x_ref();
+    let t2 = backend::secp256k1_recover(
+        &Curve::negate_n(&Curve::mul_mod_n(&proof.s, x)),
+        pubkey.is_odd(),
+        &arithmetic::concat(x, &Curve::mul_mod_n(&proof.c, x)),
+    )
+    .map_err(|_| Secp256k1Error::InvalidSignature)?;
+    let t3 = arithmetic::affine_add_checked(
+        &arithmetic::point_from_be_bytes(&Curve::ecmul(blinded, &proof.s)?.0),
+        &arithmetic::point_from_be_bytes(&Curve::ecmul(evaluated, &proof.c)?.0),
+    )
+    .ok_or(Secp256k1Error::InvalidSignature)?;
+    let t3 = UncompressedPoint(arithmetic::point_to_be_bytes(&t3));
+    if challenge(hasher, pubkey, blinded, evaluated, &UncompressedPoint(t2), &t3) != proof.c {
+        return Err(Secp256k1Error::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// ### Unblind
+///
+/// Client side: removes the blind from the server's evaluation, giving `𝒌·𝐻₁(𝑥)`.
+pub fn unblind(blind: &[u8; 32], evaluated: &UncompressedPoint) -> Result<UncompressedPoint, Secp256k1Error> {
+    Curve::ecmul(evaluated, &Curve::mod_inv_n(blind)?)
+}
+
+/// ### Finalize
+///
+/// Client side: hashes the input and unblinded element into the PRF output.
+pub fn finalize(input: &[u8], unblinded: &UncompressedPoint) -> [u8; 32] {
+    let unblinded = unblinded.compress();
+    backend::sha256(&[
+        &(input.len() as u16).to_be_bytes(),
+        input,
+        &(unblinded.0.len() as u16).to_be_bytes(),
+        &unblinded.0,
+        b"Finalize",
+    ])
+}
+
+/// ### Evaluate Input
+///
+/// Computes the PRF output directly from the secret key and input, without blinding, eg: for a
+/// server checking an output presented to it.
+pub fn evaluate_input(mode: Mode, key: &SecretKey, input: &[u8]) -> Result<[u8; 32], Secp256k1Error> {
+    let element = Curve::ecmul_unchecked(
+        &hash_to_curve::hash_to_curve(input, mode.hash_to_group_dst())?,
+        key.as_bytes(),
+    )?;
+    Ok(finalize(input, &element))
+}
+
+/// Derives the proof challenge from a transcript of the statement and commitments.
+fn challenge<H: ChallengeHasher>(
+    hasher: H,
+    pubkey: &UncompressedPoint,
+    blinded: &UncompressedPoint,
+    evaluated: &UncompressedPoint,
+    t2: &UncompressedPoint,
+    t3: &UncompressedPoint,
+) -> [u8; 32] {
+    let mut transcript = Transcript::with_hash(CHALLENGE_DOMAIN, hasher);
+    transcript.append_point(b"Y", pubkey);
+    transcript.append_point(b"B", blinded);
+    transcript.append_point(b"Z", evaluated);
+    transcript.append_point(b"t2", t2);
+    transcript.append_point(b"t3", t3);
+    transcript.challenge_scalar(b"c")
+}
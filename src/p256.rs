@@ -0,0 +1,103 @@
+//! # P-256
+//!
+//! The `WeierstrassCurve` implementation for NIST P-256 (secp256r1), the curve behind passkeys
+//! and WebAuthn. It covers the curve's parameters and its scalar and field arithmetic, modular
+//! inversion and square roots, on the same fixed-width limb arithmetic as secp256k1, so
+//! programs can decompress keys and prepare or check values around a signature.
+//!
+//! There is no recover syscall for P-256, so none of `Curve`'s point multiplication applies.
+//! Signature verification goes through the secp256r1 precompile, which a program checks by
+//! inspecting the precompile instruction in its transaction.
+
+use crate::{arithmetic, Secp256k1Error, WeierstrassCurve};
+
+/// ### P-256
+///
+/// The NIST P-256 (secp256r1) curve used by passkeys and WebAuthn. Signature verification on
+/// SVM goes through the secp256r1 precompile, while this provides the scalar and field
+/// arithmetic and point decompression needed around it.
+pub struct P256;
+
+impl WeierstrassCurve for P256 {
+    /// 0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551
+    const N: [u8; 32] = arithmetic::to_be_bytes(&arithmetic::P256_N.m);
+
+    /// 0xffffffff00000001000000000000000000000000ffffffffffffffffffffffff
+    const P: [u8; 32] = arithmetic::to_be_bytes(&arithmetic::P256_P.m);
+
+    /// 𝑃 - 3
+    const A: [u8; 32] = arithmetic::to_be_bytes(&arithmetic::sbb(&arithmetic::P256_P.m, &[3, 0, 0, 0]).0);
+
+    /// 0x5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b
+    const B: [u8; 32] = [
+        0x5a, 0xc6, 0x35, 0xd8, 0xaa, 0x3a, 0x93, 0xe7, 0xb3, 0xeb, 0xbd, 0x55, 0x76, 0x98, 0x86,
+        0xbc, 0x65, 0x1d, 0x06, 0xb0, 0xcc, 0x53, 0xb0, 0xf6, 0x3b, 0xce, 0x3c, 0x3e, 0x27, 0xd2,
+        0x60, 0x4b,
+    ];
+
+    /// 𝐺.𝑋: 0x6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296
+    /// 𝐺.𝑌: 0x4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5
+    const G: [u8; 64] = [
+        0x6b, 0x17, 0xd1, 0xf2, 0xe1, 0x2c, 0x42, 0x47, 0xf8, 0xbc, 0xe6, 0xe5, 0x63, 0xa4, 0x40,
+        0xf2, 0x77, 0x03, 0x7d, 0x81, 0x2d, 0xeb, 0x33, 0xa0, 0xf4, 0xa1, 0x39, 0x45, 0xd8, 0x98,
+        0xc2, 0x96, 0x4f, 0xe3, 0x42, 0xe2, 0xfe, 0x1a, 0x7f, 0x9b, 0x8e, 0xe7, 0xeb, 0x4a, 0x7c,
+        0x0f, 0x9e, 0x16, 0x2b, 0xce, 0x33, 0x57, 0x6b, 0x31, 0x5e, 0xce, 0xcb, 0xb6, 0x40, 0x68,
+        0x37, 0xbf, 0x51, 0xf5,
+    ];
+
+    fn add_mod_n(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        arithmetic::to_be_bytes(&arithmetic::add_mod(
+            &arithmetic::from_be_bytes(a),
+            &arithmetic::from_be_bytes(b),
+            &arithmetic::P256_N,
+        ))
+    }
+
+    fn mul_mod_n(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        arithmetic::to_be_bytes(&arithmetic::mul_mod(
+            &arithmetic::from_be_bytes(a),
+            &arithmetic::from_be_bytes(b),
+            &arithmetic::P256_N,
+        ))
+    }
+
+    fn negate_n(k: &[u8; 32]) -> [u8; 32] {
+        arithmetic::to_be_bytes(&arithmetic::neg_mod(&arithmetic::from_be_bytes(k), &arithmetic::P256_N))
+    }
+
+    fn mod_inv_n(k: &[u8]) -> Result<[u8; 32], Secp256k1Error> {
+        let k = arithmetic::from_be_slice(k, &arithmetic::P256_N);
+        let inv_k = arithmetic::inv_mod(&k, &arithmetic::P256_N).ok_or(Secp256k1Error::ArithmeticOverflow)?;
+        Ok(arithmetic::to_be_bytes(&inv_k))
+    }
+
+    fn add_mod_p(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        arithmetic::to_be_bytes(&arithmetic::add_mod(
+            &arithmetic::from_be_bytes(a),
+            &arithmetic::from_be_bytes(b),
+            &arithmetic::P256_P,
+        ))
+    }
+
+    fn mul_mod_p(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        arithmetic::to_be_bytes(&arithmetic::mul_mod(
+            &arithmetic::from_be_bytes(a),
+            &arithmetic::from_be_bytes(b),
+            &arithmetic::P256_P,
+        ))
+    }
+
+    fn negate_p(k: &[u8; 32]) -> [u8; 32] {
+        arithmetic::to_be_bytes(&arithmetic::neg_mod(&arithmetic::from_be_bytes(k), &arithmetic::P256_P))
+    }
+
+    fn mod_inv_p(k: &[u8]) -> Result<[u8; 32], Secp256k1Error> {
+        let k = arithmetic::from_be_slice(k, &arithmetic::P256_P);
+        let inv_k = arithmetic::inv_mod(&k, &arithmetic::P256_P).ok_or(Secp256k1Error::ArithmeticOverflow)?;
+        Ok(arithmetic::to_be_bytes(&inv_k))
+    }
+
+    fn sqrt_mod_p(a: &[u8; 32]) -> Option<[u8; 32]> {
+        arithmetic::sqrt_mod(&arithmetic::from_be_bytes(a), &arithmetic::P256_P).map(|r| arithmetic::to_be_bytes(&r))
+    }
+}
@@ -0,0 +1,46 @@
+//! # Parallel
+//!
+//! Rayon-parallel batch operations for the host-side backend, so indexers verifying thousands
+//! of signatures per second are not bottlenecked on a single thread. Each function spreads
+//! independent work across rayon's global thread pool and matches its single-threaded
+//! counterpart item for item. SBF programs are single-threaded, so this module only exists
+//! off-chain.
+
+use std::vec::Vec;
+
+use rayon::prelude::*;
+
+use crate::{CompressedPoint, Curve, Secp256k1Error, Secp256k1Point, UncompressedPoint};
+
+/// ### Verify ECDSA Batch
+///
+/// Verifies each `(hash, 𝑟‖𝑠, pubkey)` as `Curve::verify_ecdsa`, returning a result per item in
+/// the same order.
+pub fn verify_ecdsa_batch(items: &[([u8; 32], [u8; 64], CompressedPoint)]) -> Vec<Result<(), Secp256k1Error>> {
+    items.par_iter().map(|(hash, signature, pubkey)| Curve::verify_ecdsa(hash, signature, pubkey)).collect()
+}
+
+/// ### Decompress Batch
+///
+/// Decompresses every point, checking each is on curve. Errors with the first failure in
+/// input order.
+pub fn decompress_batch<T: Secp256k1Point + Sync>(points: &[T]) -> Result<Vec<UncompressedPoint>, Secp256k1Error> {
+    // Collecting straight into a `Result` would return whichever error a thread hit first
+    let results: Vec<_> = points.par_iter().map(|point| point.decompress()).collect();
+    results.into_iter().collect()
+}
+
+/// ### MSM
+///
+/// Calculates `Σ 𝒌ᵢ·𝑃ᵢ`, as `msm::MsmState` does in one go, with the `ecmul`s spread across
+/// threads and the products summed with `Curve::ecadd_many`. Terms with a scalar of `0` are
+/// skipped. Errors with the first failing term in input order, or `IdentityPoint` if the sum is
+/// the point at infinity.
+pub fn msm<T: Secp256k1Point + Sync>(terms: &[(T, [u8; 32])]) -> Result<UncompressedPoint, Secp256k1Error> {
+    let products: Vec<_> = terms
+        .par_iter()
+        .filter(|(_, k)| *k != [0u8; 32])
+        .map(|(point, k)| Curve::ecmul(point, k))
+        .collect();
+    Curve::ecadd_many(&products.into_iter().collect::<Result<Vec<_>, _>>()?)
+}
@@ -0,0 +1,106 @@
+//! # PDA
+//!
+//! Solana program derived address seeds for secp256k1 keys, so programs indexing state by a
+//! foreign key (an Ethereum address, a compressed public key or a BIP-340 x-only key) all
+//! derive the same address for the same key.
+//!
+//! Every key maps to exactly two seeds, a tag naming the key type followed by the raw key:
+//!
+//! | Key                  | Tag                      | Key seed                   |
+//! |----------------------|--------------------------|----------------------------|
+//! | `EthAddress`         | `"eth"`                  | 20-byte address            |
+//! | `CompressedPoint`    | `"secp256k1-even"`/`-odd`| 32-byte 𝑋-coordinate        |
+//! | x-only               | `"secp256k1-xonly"`      | 32-byte 𝑋-coordinate        |
+//!
+//! A compressed point is 33 bytes, one more than a seed may hold, so its parity is folded into
+//! the tag. The tags keep an x-only key from colliding with the compressed key it lifts to.
+//!
+//! Keys can also be mapped straight to a 32-byte Solana pubkey with `to_solana_pubkey`, as
+//! `SHA-256("solana-secp256k1-pubkey" ‖ 𝑆𝐸𝐶1)` of the compressed key. Unlike a PDA this needs
+//! no program id or bump search, so every program derives the same identity for the same key,
+//! eg: to use as an account owner or a map key. Nobody holds an ed25519 secret key for it, so
+//! it can never sign a transaction.
+
+use crate::{backend, eth::EthAddress, CompressedPoint, Secp256k1Point};
+
+pub const ETH_ADDRESS_TAG: &[u8] = b"eth";
+pub const EVEN_POINT_TAG: &[u8] = b"secp256k1-even";
+pub const ODD_POINT_TAG: &[u8] = b"secp256k1-odd";
+pub const X_ONLY_TAG: &[u8] = b"secp256k1-xonly";
+pub const SOLANA_PUBKEY_DOMAIN: &[u8] = b"solana-secp256k1-pubkey";
+
+/// ### Eth Address Seeds
+///
+/// The seeds `["eth", address]` of an Ethereum address.
+pub fn eth_address_seeds(address: &EthAddress) -> [&[u8]; 2] {
+    [ETH_ADDRESS_TAG, &address.0]
+}
+
+/// ### Compressed Point Seeds
+///
+/// The seeds `["secp256k1-even" | "secp256k1-odd", 𝑋]` of a compressed public key.
+pub fn compressed_point_seeds(pubkey: &CompressedPoint) -> [&[u8]; 2] {
+    let tag = if pubkey.is_odd() { ODD_POINT_TAG } else { EVEN_POINT_TAG };
+    [tag, pubkey.x_ref()]
+}
+
+/// ### X-Only Seeds
+///
+/// The seeds `["secp256k1-xonly", 𝑋]` of a BIP-340 x-only public key.
+pub fn x_only_seeds(x: &[u8; 32]) -> [&[u8]; 2] {
+    [X_ONLY_TAG, x]
+}
+
+/// ### Find Program Address
+///
+/// Finds the program derived address of `seeds` under `program_id` and its bump seed, or
+/// `None` if there are too many seeds, a seed is longer than 32 bytes, or no bump gives an
+/// address off the ed25519 curve.
+pub fn find_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+    backend::try_find_program_address(seeds, program_id)
+}
+
+/// ### Find Eth Address PDA
+///
+/// Finds the program derived address and bump seed of an Ethereum address.
+pub fn find_eth_address_pda(address: &EthAddress, program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+    find_program_address(&eth_address_seeds(address), program_id)
+}
+
+/// ### Find Compressed Point PDA
+///
+/// Finds the program derived address and bump seed of a compressed public key.
+pub fn find_compressed_point_pda(pubkey: &CompressedPoint, program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+    find_program_address(&compressed_point_seeds(pubkey), program_id)
+}
+
+/// ### Find X-Only PDA
+///
+/// Finds the program derived address and bump seed of a BIP-340 x-only public key.
+pub fn find_x_only_pda(x: &[u8; 32], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+    find_program_address(&x_only_seeds(x), program_id)
+}
+
+/// ### To Solana Pubkey
+///
+/// The 32-byte Solana identity `SHA-256("solana-secp256k1-pubkey" ‖ 𝑆𝐸𝐶1)` of a public key,
+/// hashing its compressed form so both encodings of a point map to the same identity.
+pub fn to_solana_pubkey<T: Secp256k1Point>(pubkey: &T) -> [u8; 32] {
+    backend::sha256(&[SOLANA_PUBKEY_DOMAIN, &pubkey.compress().0])
+}
+
+/// ### Is Solana Pubkey Of
+///
+/// Checks that `solana_pubkey` is the identity of `pubkey`. The mapping is a hash, so this is
+/// how a program checks an account key against a public key supplied in instruction data.
+pub fn is_solana_pubkey_of<T: Secp256k1Point>(solana_pubkey: &[u8; 32], pubkey: &T) -> bool {
+    to_solana_pubkey(pubkey) == *solana_pubkey
+}
+
+/// ### Find Solana Pubkey
+///
+/// Finds the index of the candidate public key whose identity is `solana_pubkey`, eg: to map
+/// an account key back to a member of a known signer set.
+pub fn find_solana_pubkey<T: Secp256k1Point>(solana_pubkey: &[u8; 32], candidates: &[T]) -> Option<usize> {
+    candidates.iter().position(|pubkey| is_solana_pubkey_of(solana_pubkey, pubkey))
+}
@@ -0,0 +1,147 @@
+//! # PEM
+//!
+//! RFC 7468 PEM armor for the DER encodings in `der`, so keys and signatures can be exchanged
+//! with OpenSSL and ops tooling as text without pre-converting them. Encoding writes 64
+//! character lines with `\n` endings into a caller-provided buffer. Parsing accepts `\r\n`
+//! endings and surrounding whitespace, but otherwise only strict base64 lines.
+//!
+//! Private keys are written as PKCS#8 `PRIVATE KEY`, and either that or SEC1 `EC PRIVATE KEY`
+//! is read. Public keys are `PUBLIC KEY` SPKI. There is no standard label for a bare ECDSA
+//! signature, so `ECDSA SIGNATURE` is used for its DER encoding.
+
+use crate::{base64, der, Secp256k1Error, SecretKey, UncompressedPoint};
+
+/// The label of a PKCS#8 private key.
+pub const PRIVATE_KEY_LABEL: &str = "PRIVATE KEY";
+
+/// The label of a SEC1 `ECPrivateKey`.
+pub const EC_PRIVATE_KEY_LABEL: &str = "EC PRIVATE KEY";
+
+/// The label of an SPKI public key.
+pub const PUBLIC_KEY_LABEL: &str = "PUBLIC KEY";
+
+/// The label of a DER ECDSA signature.
+pub const SIGNATURE_LABEL: &str = "ECDSA SIGNATURE";
+
+/// The length of the PEM encoding of a PKCS#8 private key.
+pub const PRIVATE_KEY_PEM_LEN: usize = encoded_len(PRIVATE_KEY_LABEL.len(), 135);
+
+/// The length of the PEM encoding of an uncompressed SPKI public key.
+pub const PUBLIC_KEY_PEM_LEN: usize = encoded_len(PUBLIC_KEY_LABEL.len(), 88);
+
+/// The longest PEM encoding of a DER ECDSA signature.
+pub const SIGNATURE_PEM_MAX_LEN: usize = encoded_len(SIGNATURE_LABEL.len(), 72);
+
+/// ### Encoded Len
+///
+/// The length of the PEM encoding of `len` bytes under a label of `label_len` bytes.
+pub const fn encoded_len(label_len: usize, len: usize) -> usize {
+    let base64_len = base64::encoded_len(len, true);
+    "-----BEGIN -----\n".len() + base64_len + base64_len.div_ceil(64) + "-----END -----\n".len() + 2 * label_len
+}
+
+/// ### Encode
+///
+/// Encodes `der` under `label` into the start of `out`, returning it as a `str`. Errors with
+/// `InvalidPayload` if `out` is shorter than `encoded_len(label.len(), der.len())`.
+pub fn encode<'a>(label: &str, der: &[u8], out: &'a mut [u8]) -> Result<&'a str, Secp256k1Error> {
+    let len = encoded_len(label.len(), der.len());
+    if out.len() < len {
+        return Err(Secp256k1Error::InvalidPayload);
+    }
+    let mut pos = 0;
+    let mut write = |bytes: &[u8]| {
+        out[pos..pos + bytes.len()].copy_from_slice(bytes);
+        pos += bytes.len();
+    };
+    write(b"-----BEGIN ");
+    write(label.as_bytes());
+    write(b"-----\n");
+    // 48 bytes encode to exactly one 64 character line, so only the last line is padded
+    for chunk in der.chunks(48) {
+        let mut line = [0u8; 64];
+        let line_len = base64::encoded_len(chunk.len(), true);
+        base64::encode_to_slice(chunk, &mut line[..line_len])?;
+        write(&line[..line_len]);
+        write(b"\n");
+    }
+    write(b"-----END ");
+    write(label.as_bytes());
+    write(b"-----\n");
+    Ok(core::str::from_utf8(&out[..len]).expect("ASCII PEM"))
+}
+
+/// ### Decode
+///
+/// Decodes the body of a PEM block with the given `label` into the start of `out`, returning
+/// it. Errors with `InvalidPayload` on another label, malformed armor or base64, or if `out` is
+/// too short.
+pub fn decode<'a>(pem: &str, label: &str, out: &'a mut [u8]) -> Result<&'a [u8], Secp256k1Error> {
+    let body = pem
+        .trim()
+        .strip_prefix("-----BEGIN ")
+        .and_then(|pem| pem.strip_prefix(label))
+        .and_then(|pem| pem.strip_prefix("-----"))
+        .and_then(|pem| pem.strip_suffix("-----"))
+        .and_then(|pem| pem.strip_suffix(label))
+        .and_then(|pem| pem.strip_suffix("-----END "))
+        .ok_or(Secp256k1Error::InvalidPayload)?;
+    let mut len = 0;
+    let mut padded = false;
+    for line in body.lines().map(str::trim_end).filter(|line| !line.is_empty()) {
+        // Padding ends the encoding, so it may only appear on the last line
+        if padded || line.len() % 4 != 0 {
+            return Err(Secp256k1Error::InvalidPayload);
+        }
+        len += base64::decode_to_slice(line, out.get_mut(len..).ok_or(Secp256k1Error::InvalidPayload)?)?;
+        padded = line.ends_with('=');
+    }
+    Ok(&out[..len])
+}
+
+/// ### Encode Private Key
+///
+/// Encodes a secret key as a PKCS#8 `PRIVATE KEY`, byte for byte as OpenSSL does.
+pub fn encode_private_key<'a>(key: &SecretKey, out: &'a mut [u8; PRIVATE_KEY_PEM_LEN]) -> Result<&'a str, Secp256k1Error> {
+    encode(PRIVATE_KEY_LABEL, &der::encode_pkcs8(key)?, out)
+}
+
+/// ### Parse Private Key
+///
+/// Parses a PKCS#8 `PRIVATE KEY` or a SEC1 `EC PRIVATE KEY`, as in `der::parse_pkcs8` and
+/// `der::parse_ec_private_key`.
+pub fn parse_private_key(pem: &str) -> Result<SecretKey, Secp256k1Error> {
+    let mut out = [0u8; 160];
+    match decode(pem, PRIVATE_KEY_LABEL, &mut out) {
+        Ok(der) => der::parse_pkcs8(der),
+        Err(_) => der::parse_ec_private_key(decode(pem, EC_PRIVATE_KEY_LABEL, &mut out)?),
+    }
+}
+
+/// ### Encode Public Key
+///
+/// Encodes a public key as an uncompressed SPKI `PUBLIC KEY`, as OpenSSL does.
+pub fn encode_public_key<'a>(pubkey: &UncompressedPoint, out: &'a mut [u8; PUBLIC_KEY_PEM_LEN]) -> Result<&'a str, Secp256k1Error> {
+    encode(PUBLIC_KEY_LABEL, &der::encode_spki(pubkey), out)
+}
+
+/// ### Parse Public Key
+///
+/// Parses an SPKI `PUBLIC KEY` in either SEC1 encoding, as in `der::parse_spki`.
+pub fn parse_public_key(pem: &str) -> Result<UncompressedPoint, Secp256k1Error> {
+    der::parse_spki(decode(pem, PUBLIC_KEY_LABEL, &mut [0u8; 88])?)
+}
+
+/// ### Encode Signature
+///
+/// Encodes an `𝑟‖𝑠` signature as a DER `ECDSA SIGNATURE` into the start of `out`.
+pub fn encode_signature<'a>(signature: &[u8; 64], out: &'a mut [u8; SIGNATURE_PEM_MAX_LEN]) -> Result<&'a str, Secp256k1Error> {
+    encode(SIGNATURE_LABEL, der::encode_signature(signature, &mut [0u8; 72]), out)
+}
+
+/// ### Parse Signature
+///
+/// Parses a DER `ECDSA SIGNATURE` into `𝑟‖𝑠`, as in `der::parse_signature`.
+pub fn parse_signature(pem: &str) -> Result<[u8; 64], Secp256k1Error> {
+    der::parse_signature(decode(pem, SIGNATURE_LABEL, &mut [0u8; 72])?)
+}
@@ -0,0 +1,131 @@
+//! # Proofs
+//!
+//! Kani proof harnesses for the fixed-width limb arithmetic. Each harness checks a primitive
+//! against a `u128` schoolbook oracle for every possible input, not just sampled ones. Run with
+//! `cargo kani`, which sets `cfg(kani)`; they are not compiled otherwise.
+
+use crate::{arithmetic::*, Curve};
+
+/// Adds two integers into five limbs, one limb at a time through `u128`.
+fn wide_add(a: &Limbs, b: &Limbs) -> [u64; 5] {
+    let mut r = [0u64; 5];
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let s = a[i] as u128 + b[i] as u128 + carry;
+        r[i] = s as u64;
+        carry = s >> 64;
+    }
+    r[4] = carry as u64;
+    r
+}
+
+fn widen(a: &Limbs, top: bool) -> [u64; 5] {
+    [a[0], a[1], a[2], a[3], top as u64]
+}
+
+fn lt(a: &Limbs, b: &Limbs) -> bool {
+    for i in (0..4).rev() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+    }
+    false
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn adc_carries() {
+    let (a, b): (Limbs, Limbs) = (kani::any(), kani::any());
+    let (s, carry) = adc(&a, &b);
+    assert_eq!(widen(&s, carry), wide_add(&a, &b));
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn sbb_borrows() {
+    let (a, b): (Limbs, Limbs) = (kani::any(), kani::any());
+    let (d, borrow) = sbb(&a, &b);
+    // 𝑑 - 𝑏𝑜𝑟𝑟𝑜𝑤·2²⁵⁶ = 𝑎 - 𝑏, so 𝑑 + 𝑏 = 𝑎 + 𝑏𝑜𝑟𝑟𝑜𝑤·2²⁵⁶
+    assert_eq!(wide_add(&d, &b), widen(&a, borrow));
+}
+
+#[kani::proof]
+#[kani::unwind(33)]
+fn be_bytes_roundtrip() {
+    let a: Limbs = kani::any();
+    assert_eq!(from_be_bytes(&to_be_bytes(&a)), a);
+}
+
+/// `fast_mod_p` and `fast_mod_n` return the unique `𝑟 < 𝑚` with `𝑟 ≡ 𝑎`, which for `𝑎 < 2𝑚` is
+/// either `𝑎` or `𝑎 - 𝑚`.
+fn check_fast_mod(fast_mod: fn(&mut [u8; 32]), m: &Modulus) {
+    let a: Limbs = kani::any();
+    let mut bytes = to_be_bytes(&a);
+    fast_mod(&mut bytes);
+    let r = from_be_bytes(&bytes);
+    assert!(lt(&r, &m.m));
+    assert!(r == a || wide_add(&r, &m.m) == widen(&a, false));
+}
+
+#[kani::proof]
+#[kani::unwind(33)]
+fn fast_mod_p_reduces() {
+    check_fast_mod(Curve::fast_mod_p, &P);
+}
+
+#[kani::proof]
+#[kani::unwind(33)]
+fn fast_mod_n_reduces() {
+    check_fast_mod(Curve::fast_mod_n, &N);
+}
+
+/// For `𝑎, 𝑏 < 𝑚`, `sub_mod` returns `𝑟 < 𝑚` with `𝑟 + 𝑏` equal to `𝑎` or `𝑎 + 𝑚`.
+fn check_sub_mod(m: &Modulus) {
+    let (a, b): (Limbs, Limbs) = (kani::any(), kani::any());
+    kani::assume(lt(&a, &m.m) && lt(&b, &m.m));
+    let r = sub_mod(&a, &b, m);
+    assert!(lt(&r, &m.m));
+    let sum = wide_add(&r, &b);
+    assert!(sum == widen(&a, false) || sum == wide_add(&a, &m.m));
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn sub_mod_p_is_fixed_width() {
+    check_sub_mod(&P);
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn sub_mod_n_is_fixed_width() {
+    check_sub_mod(&N);
+}
+
+#[kani::proof]
+#[kani::unwind(33)]
+fn negate_n_complements() {
+    let k: Limbs = kani::any();
+    kani::assume(lt(&k, &N.m));
+    let r = from_be_bytes(&Curve::negate_n(&to_be_bytes(&k)));
+    if k == [0u64; 4] {
+        assert_eq!(r, [0u64; 4]);
+    } else {
+        assert_eq!(wide_add(&r, &k), widen(&N.m, false));
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(33)]
+fn is_high_matches_comparison() {
+    let k: [u8; 32] = kani::any();
+    assert_eq!(Curve::is_high(&k), k > Curve::N_DIV_2);
+    assert_eq!(Curve::is_zero(&k), k == [0u8; 32]);
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn neg_mod_ct_matches_neg_mod() {
+    let a: Limbs = kani::any();
+    assert_eq!(neg_mod_ct(&a, &P), neg_mod(&a, &P));
+    assert_eq!(neg_mod_ct(&a, &N), neg_mod(&a, &N));
+}
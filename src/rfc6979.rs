@@ -0,0 +1,45 @@
+//! # RFC 6979
+//!
+//! Deterministic ECDSA nonces, `𝒌 = HMAC-DRBG(𝒅, H(𝑚))` over HMAC-SHA256, so signing needs no
+//! randomness and a signature can never leak the secret key through a repeated or biased nonce.
+//! Nonces match `rust-secp256k1` without extra data, so signatures are byte for byte identical
+//! to its own. RustCrypto's `k256` feeds the hash to HMAC-DRBG unreduced, so its signatures only
+//! match for hashes below 𝑁.
+
+use crate::{backend, Curve, SecretKey};
+
+/// ### Nonce
+///
+/// Derives the RFC 6979 nonce in `1..𝑁` for signing the 32-byte `hash` with `key`, with the
+/// hash reduced modulo 𝑁 as `bits2octets` requires.
+pub fn nonce(key: &SecretKey, hash: &[u8; 32]) -> [u8; 32] {
+    let h1 = Curve::add_mod_n(hash, &[0u8; 32]);
+    let (mut k, mut v) = ([0u8; 32], [1u8; 32]);
+    k = hmac_sha256(&k, &[&v, &[0], key.as_bytes(), &h1]);
+    v = hmac_sha256(&k, &[&v]);
+    k = hmac_sha256(&k, &[&v, &[1], key.as_bytes(), &h1]);
+    v = hmac_sha256(&k, &[&v]);
+    loop {
+        v = hmac_sha256(&k, &[&v]);
+        if !Curve::is_zero(&v) && v < Curve::N {
+            return v;
+        }
+        k = hmac_sha256(&k, &[&v, &[0]]);
+        v = hmac_sha256(&k, &[&v]);
+    }
+}
+
+/// Calculates `HMAC-SHA256(key, data)` for a key of at most 64 bytes, over the concatenation of
+/// at most 4 slices, which is checked at compile time.
+pub(crate) fn hmac_sha256<const M: usize>(key: &[u8], data: &[&[u8]; M]) -> [u8; 32] {
+    const { assert!(M <= 4, "at most 4 data slices") };
+    let (mut ipad, mut opad) = ([0x36u8; 64], [0x5cu8; 64]);
+    for ((i, o), k) in ipad.iter_mut().zip(opad.iter_mut()).zip(key) {
+        *i ^= k;
+        *o ^= k;
+    }
+    let mut vals: [&[u8]; 5] = [&ipad, &[], &[], &[], &[]];
+    vals[1..1 + M].copy_from_slice(data);
+    let inner = backend::sha256(&vals[..1 + M]);
+    backend::sha256(&[&opad, &inner])
+}
@@ -0,0 +1,158 @@
+//! # Ring Signatures
+//!
+//! Linkable spontaneous anonymous group (LSAG) signatures, the single-key form of MLSAG. A
+//! signature proves the signer holds the secret key of one of the public keys in a ring,
+//! without revealing which, and carries a key image `𝐼 = 𝒙·𝐻ₚ(𝑃)` that is the same for every
+//! signature by the same key. Programs enforce one vote per key, or spend-once semantics, by
+//! recording key images and rejecting any they have seen before.
+//!
+//! Each ring member costs a recover for `𝑠ᵢ·𝐺 + 𝑐ᵢ·𝑃ᵢ`, two ecmuls for `𝑠ᵢ·𝐻ₚ(𝑃ᵢ) + 𝑐ᵢ·𝐼` and a
+//! hash to curve, so rings of more than a handful of keys will not fit in one instruction.
+
+use crate::{
+    arithmetic, backend, hash_to_curve, transcript::Transcript, Curve, Secp256k1Error, Secp256k1Point, SecretKey,
+    UncompressedPoint,
+};
+
+const HASH_TO_POINT_DST: &[u8] = b"SOLANA-SECP256K1-LSAG-V1_XMD:SHA-256_SSWU_RO_";
+const CHALLENGE_DOMAIN: &[u8] = b"SOLANA-SECP256K1-LSAG-V1";
+
+/// ### Ring Signature
+///
+/// An LSAG signature over a ring of `M` keys: the initial challenge `𝑐₀`, a response `𝑠ᵢ` for
+/// each ring member and the signer's key image `𝐼`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct RingSignature<const M: usize> {
+    pub c0: [u8; 32],
+    pub s: [[u8; 32]; M],
+    pub key_image: UncompressedPoint,
+}
+
+/// ### Hash Point
+///
+/// Hashes a public key to the point `𝐻ₚ(𝑃)` its key image is based on.
+pub fn hash_point(pubkey: &UncompressedPoint) -> Result<UncompressedPoint, Secp256k1Error> {
+    hash_to_curve::hash_to_curve(&pubkey.compress().0, HASH_TO_POINT_DST)
+}
+
+/// ### Key Image
+///
+/// Calculates the key image `𝐼 = 𝒙·𝐻ₚ(𝒙·𝐺)` of a secret key.
+pub fn key_image(key: &SecretKey) -> Result<UncompressedPoint, Secp256k1Error> {
+    Curve::ecmul(&hash_point(&key.public_key()?)?, key.as_bytes())
+}
+
+/// ### Sign
+///
+/// Signs `message` with the secret key of `ring[index]`. `randomness` must be `M` freshly
+/// random scalars: `randomness[index]` is the signer's nonce and the rest become the decoy
+/// responses. Errors with `InvalidSecretKey` if the key is not at `index`.
+pub fn sign<const M: usize>(
+    message: &[u8],
+    ring: &[UncompressedPoint; M],
+    index: usize,
+    key: &SecretKey,
+    randomness: &[[u8; 32]; M],
+) -> Result<RingSignature<M>, Secp256k1Error> {
+    if ring.get(index) != Some(&key.public_key()?) {
+        return Err(Secp256k1Error::InvalidSecretKey);
+    }
+    let key_image = key_image(key)?;
+    let transcript = transcript(ring, &key_image, message);
+
+    let alpha = &randomness[index];
+    let l = Curve::mul_g(alpha)?;
+    let r = Curve::ecmul(&hash_point(&ring[index])?, alpha)?;
+    let mut c = challenge(&transcript, &l, &r);
+
+    let mut s = *randomness;
+    let mut c0 = [0u8; 32];
+    for offset in 1..=M {
+        let i = (index + offset) % M;
+        if i == 0 {
+            c0 = c;
+        }
+        if i == index {
+            // 𝑠ₚ = 𝛼 - 𝑐ₚ·𝒙 closes the ring
+            s[i] = Curve::add_mod_n(alpha, &Curve::negate_n(&Curve::mul_mod_n(&c, key.as_bytes())));
+            break;
+        }
+        let (l, r) = commitments(&ring[i], &key_image, &s[i], &c)?;
+        c = challenge(&transcript, &l, &r);
+    }
+    Ok(RingSignature { c0, s, key_image })
+}
+
+/// ### Verify
+///
+/// Verifies a ring signature over `message`, recomputing each `𝑐ᵢ₊₁ = 𝐻(𝑠ᵢ·𝐺 + 𝑐ᵢ·𝑃ᵢ,
+/// 𝑠ᵢ·𝐻ₚ(𝑃ᵢ) + 𝑐ᵢ·𝐼)` around the ring and checking it closes on `𝑐₀`. Errors with
+/// `InvalidSignature` on failure. The caller must still check the key image is unused.
+pub fn verify<const M: usize>(
+    message: &[u8],
+    ring: &[UncompressedPoint; M],
+    signature: &RingSignature<M>,
+) -> Result<(), Secp256k1Error> {
+    if M == 0
+        || signature.c0 >= Curve::N
+        || signature.s.iter().any(|s| *s >= Curve::N)
+        || !signature.key_image.is_on_curve()
+        || ring.iter().any(|p| !p.is_on_curve())
+    {
+        return Err(Secp256k1Error::InvalidSignature);
+    }
+    let transcript = transcript(ring, &signature.key_image, message);
+    let mut c = signature.c0;
+    for (pubkey, s) in ring.iter().zip(signature.s.iter()) {
+        let (l, r) =
+            commitments(pubkey, &signature.key_image, s, &c).map_err(|_| Secp256k1Error::InvalidSignature)?;
+        c = challenge(&transcript, &l, &r);
+    }
+    if c != signature.c0 {
+        return Err(Secp256k1Error::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// Calculates `𝑠·𝐺 + 𝑐·𝑃` and `𝑠·𝐻ₚ(𝑃) + 𝑐·𝐼`, the first with a single recover.
+fn commitments(
+    pubkey: &UncompressedPoint,
+    key_image: &UncompressedPoint,
+    s: &[u8; 32],
+    c: &[u8; 32],
+) -> Result<(UncompressedPoint, UncompressedPoint), Secp256k1Error> {
+    // 𝑃ₓ⁻¹(𝑐𝑃ₓ·𝑃 + 𝑠𝑃ₓ·𝐺) = 𝑠·𝐺 + 𝑐·𝑃
+    let x = pubkey.x_ref();
+    let l = backend::secp256k1_recover(
+        &Curve::negate_n(&Curve::mul_mod_n(s, x)),
+        pubkey.is_odd(),
+        &arithmetic::concat(x, &Curve::mul_mod_n(c, x)),
+    )?;
+    let r = arithmetic::affine_add_checked(
+        &arithmetic::point_from_be_bytes(&Curve::ecmul(&hash_point(pubkey)?, s)?.0),
+        &arithmetic::point_from_be_bytes(&Curve::ecmul(key_image, c)?.0),
+    )
+    .ok_or(Secp256k1Error::IdentityPoint)?;
+    Ok((UncompressedPoint(l), UncompressedPoint(arithmetic::point_to_be_bytes(&r))))
+}
+
+/// Appends the ring, key image and message once, so each challenge binds them without
+/// rehashing every member.
+fn transcript(ring: &[UncompressedPoint], key_image: &UncompressedPoint, message: &[u8]) -> Transcript {
+    let mut transcript = Transcript::new(CHALLENGE_DOMAIN);
+    transcript.append_u64(b"ring-size", ring.len() as u64);
+    for pubkey in ring {
+        transcript.append_point(b"P", pubkey);
+    }
+    transcript.append_point(b"I", key_image);
+    transcript.append_message(b"message", message);
+    transcript
+}
+
+fn challenge(transcript: &Transcript, l: &UncompressedPoint, r: &UncompressedPoint) -> [u8; 32] {
+    let mut transcript = *transcript;
+    transcript.append_point(b"L", l);
+    transcript.append_point(b"R", r);
+    transcript.challenge_scalar(b"c")
+}
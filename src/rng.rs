@@ -0,0 +1,69 @@
+//! # RNG
+//!
+//! Secret key and nonce generation from any `rand_core` CSPRNG, so integrators with
+//! HSM-seeded or deterministic RNGs can plug them in. With the `getrandom` feature, `generate`
+//! variants draw from the operating system's RNG instead. Neither is available on-chain, where
+//! there is no source of randomness.
+//!
+//! Scalars are drawn by rejection sampling, so they are uniform in `1..𝑁`.
+
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{CompressedPoint, Curve, Secp256k1Error, Secp256k1Point, SecretKey};
+
+/// ### Random Scalar
+///
+/// Draws a uniformly random scalar in the range `1..𝑁`, eg: for a nonce or blinding factor.
+pub fn random_scalar<R: RngCore + CryptoRng>(rng: &mut R) -> [u8; 32] {
+    loop {
+        let mut k = [0u8; 32];
+        rng.fill_bytes(&mut k);
+        if k != [0u8; 32] && k < Curve::N {
+            return k;
+        }
+    }
+}
+
+/// ### Random Scalars
+///
+/// Draws `M` independent random scalars, eg: the randomness of a ring signature.
+pub fn random_scalars<R: RngCore + CryptoRng, const M: usize>(rng: &mut R) -> [[u8; 32]; M] {
+    core::array::from_fn(|_| random_scalar(rng))
+}
+
+/// ### Nonce Pair
+///
+/// Draws a MuSig2 secret nonce pair `(𝑘₁, 𝑘₂)` along with the public nonces `(𝑘₁·𝐺, 𝑘₂·𝐺)` a
+/// signer publishes to a `session::Session`.
+pub fn nonce_pair<R: RngCore + CryptoRng>(
+    rng: &mut R,
+) -> Result<([[u8; 32]; 2], [CompressedPoint; 2]), Secp256k1Error> {
+    let secret: [[u8; 32]; 2] = random_scalars(rng);
+    let public = [Curve::mul_g(&secret[0])?.compress(), Curve::mul_g(&secret[1])?.compress()];
+    Ok((secret, public))
+}
+
+impl SecretKey {
+    /// ### Random
+    ///
+    /// Generates a uniformly random secret key.
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self::from_bytes(random_scalar(rng)).expect("scalar in range")
+    }
+
+    /// ### Generate
+    ///
+    /// Generates a uniformly random secret key from the operating system's RNG.
+    #[cfg(feature = "getrandom")]
+    pub fn generate() -> Self {
+        Self::random(&mut rand_core::OsRng)
+    }
+}
+
+/// ### Generate Nonce Pair
+///
+/// As `nonce_pair`, drawing from the operating system's RNG.
+#[cfg(feature = "getrandom")]
+pub fn generate_nonce_pair() -> Result<([[u8; 32]; 2], [CompressedPoint; 2]), Secp256k1Error> {
+    nonce_pair(&mut rand_core::OsRng)
+}
@@ -0,0 +1,156 @@
+//! # secp256k1
+//!
+//! Conversions between this crate's types and their `rust-secp256k1` equivalents, so Bitcoin
+//! tooling and on-chain programs can share values without manual byte shuffling.
+
+use ::secp256k1::{
+    ecdsa::{self, RecoveryId},
+    schnorr, Parity, PublicKey, XOnlyPublicKey,
+};
+
+use crate::{arithmetic, bip340, eth::RecoverableSignature, CompressedPoint, Curve, Secp256k1Error, Secp256k1Point, SecretKey, UncompressedPoint};
+
+impl TryFrom<UncompressedPoint> for PublicKey {
+    type Error = Secp256k1Error;
+
+    fn try_from(p: UncompressedPoint) -> Result<Self, Secp256k1Error> {
+        PublicKey::from_slice(&p.to_sec1_bytes()).map_err(|_| Secp256k1Error::InvalidPublicKey)
+    }
+}
+
+impl TryFrom<CompressedPoint> for PublicKey {
+    type Error = Secp256k1Error;
+
+    fn try_from(p: CompressedPoint) -> Result<Self, Secp256k1Error> {
+        PublicKey::from_slice(&p.0).map_err(|_| Secp256k1Error::InvalidPublicKey)
+    }
+}
+
+impl From<PublicKey> for UncompressedPoint {
+    fn from(p: PublicKey) -> Self {
+        UncompressedPoint::from_sec1_unchecked(&p.serialize_uncompressed())
+    }
+}
+
+impl From<PublicKey> for CompressedPoint {
+    fn from(p: PublicKey) -> Self {
+        CompressedPoint(p.serialize())
+    }
+}
+
+impl TryFrom<UncompressedPoint> for XOnlyPublicKey {
+    type Error = Secp256k1Error;
+
+    /// Drops the parity of the point, keeping only its 𝑋-coordinate.
+    fn try_from(p: UncompressedPoint) -> Result<Self, Secp256k1Error> {
+        XOnlyPublicKey::from_slice(p.x_ref()).map_err(|_| Secp256k1Error::InvalidPublicKey)
+    }
+}
+
+impl TryFrom<CompressedPoint> for XOnlyPublicKey {
+    type Error = Secp256k1Error;
+
+    /// Drops the parity of the point, keeping only its 𝑋-coordinate.
+    fn try_from(p: CompressedPoint) -> Result<Self, Secp256k1Error> {
+        XOnlyPublicKey::from_slice(p.x_ref()).map_err(|_| Secp256k1Error::InvalidPublicKey)
+    }
+}
+
+impl From<XOnlyPublicKey> for UncompressedPoint {
+    /// Lifts the 𝑋-coordinate to the point with an even 𝑌-coordinate, as in BIP-340.
+    fn from(p: XOnlyPublicKey) -> Self {
+        UncompressedPoint::from(p.public_key(Parity::Even))
+    }
+}
+
+impl From<XOnlyPublicKey> for CompressedPoint {
+    /// Lifts the 𝑋-coordinate to the point with an even 𝑌-coordinate, as in BIP-340.
+    fn from(p: XOnlyPublicKey) -> Self {
+        CompressedPoint::from(p.public_key(Parity::Even))
+    }
+}
+
+impl From<crate::Parity> for Parity {
+    fn from(p: crate::Parity) -> Self {
+        match p {
+            crate::Parity::Even => Parity::Even,
+            crate::Parity::Odd => Parity::Odd,
+        }
+    }
+}
+
+impl From<Parity> for crate::Parity {
+    fn from(p: Parity) -> Self {
+        match p {
+            Parity::Even => crate::Parity::Even,
+            Parity::Odd => crate::Parity::Odd,
+        }
+    }
+}
+
+impl From<&SecretKey> for ::secp256k1::SecretKey {
+    fn from(k: &SecretKey) -> Self {
+        ::secp256k1::SecretKey::from_byte_array(k.as_bytes()).expect("secret keys are in 1..𝑁")
+    }
+}
+
+impl From<::secp256k1::SecretKey> for SecretKey {
+    fn from(k: ::secp256k1::SecretKey) -> Self {
+        SecretKey::from_bytes(k.secret_bytes()).expect("secret keys are in 1..𝑁")
+    }
+}
+
+impl TryFrom<RecoverableSignature> for ecdsa::RecoverableSignature {
+    type Error = Secp256k1Error;
+
+    /// Errors with `InvalidSignature` if `𝑟` or `𝑠` is zero or not less than 𝑁, or the recovery
+    /// id is above 3.
+    fn try_from(signature: RecoverableSignature) -> Result<Self, Secp256k1Error> {
+        let (signature, recovery_id) = <(ecdsa::Signature, RecoveryId)>::try_from(signature)?;
+        ecdsa::RecoverableSignature::from_compact(&signature.serialize_compact(), recovery_id)
+            .map_err(|_| Secp256k1Error::InvalidSignature)
+    }
+}
+
+impl From<ecdsa::RecoverableSignature> for RecoverableSignature {
+    fn from(signature: ecdsa::RecoverableSignature) -> Self {
+        let (recovery_id, bytes) = signature.serialize_compact();
+        let (r, s) = arithmetic::split(&bytes);
+        RecoverableSignature { r, s, recovery_id: i32::from(recovery_id) as u8 }
+    }
+}
+
+impl TryFrom<RecoverableSignature> for (ecdsa::Signature, RecoveryId) {
+    type Error = Secp256k1Error;
+
+    /// Errors with `InvalidSignature` if `𝑟` or `𝑠` is zero or not less than 𝑁, or the recovery
+    /// id is above 3.
+    fn try_from(signature: RecoverableSignature) -> Result<Self, Secp256k1Error> {
+        let RecoverableSignature { r, s, recovery_id } = signature;
+        if Curve::is_zero(&r) || Curve::is_zero(&s) || r >= Curve::N || s >= Curve::N {
+            return Err(Secp256k1Error::InvalidSignature);
+        }
+        let recovery_id = RecoveryId::try_from(recovery_id as i32).map_err(|_| Secp256k1Error::InvalidSignature)?;
+        let signature = ecdsa::Signature::from_compact(&arithmetic::concat(&r, &s)).map_err(|_| Secp256k1Error::InvalidSignature)?;
+        Ok((signature, recovery_id))
+    }
+}
+
+impl From<(ecdsa::Signature, RecoveryId)> for RecoverableSignature {
+    fn from((signature, recovery_id): (ecdsa::Signature, RecoveryId)) -> Self {
+        let (r, s) = arithmetic::split(&signature.serialize_compact());
+        RecoverableSignature { r, s, recovery_id: i32::from(recovery_id) as u8 }
+    }
+}
+
+impl From<bip340::Signature> for schnorr::Signature {
+    fn from(signature: bip340::Signature) -> Self {
+        schnorr::Signature::from_byte_array(signature.0)
+    }
+}
+
+impl From<schnorr::Signature> for bip340::Signature {
+    fn from(signature: schnorr::Signature) -> Self {
+        bip340::Signature(signature.to_byte_array())
+    }
+}
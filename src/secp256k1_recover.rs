@@ -0,0 +1,42 @@
+//! # solana-secp256k1-recover
+//!
+//! Conversions between points and the `Secp256k1Pubkey` returned by the Solana SDK's
+//! `secp256k1_recover`, so programs already calling it can hand its output straight to this
+//! crate. Both are the raw 64-byte `𝑋‖𝑌`, so only the conversions into points need checking.
+
+use solana_secp256k1_recover::Secp256k1Pubkey;
+
+use crate::{CompressedPoint, Secp256k1Error, Secp256k1Point, UncompressedPoint};
+
+impl From<UncompressedPoint> for Secp256k1Pubkey {
+    fn from(p: UncompressedPoint) -> Self {
+        Secp256k1Pubkey(p.0)
+    }
+}
+
+impl TryFrom<CompressedPoint> for Secp256k1Pubkey {
+    type Error = Secp256k1Error;
+
+    /// Decompresses the point, erroring as `decompress` does if it is not on curve.
+    fn try_from(p: CompressedPoint) -> Result<Self, Secp256k1Error> {
+        Ok(Secp256k1Pubkey(p.decompress()?.0))
+    }
+}
+
+impl TryFrom<Secp256k1Pubkey> for UncompressedPoint {
+    type Error = Secp256k1Error;
+
+    /// Checks the key is on curve, erroring with `InvalidPublicKey` otherwise.
+    fn try_from(p: Secp256k1Pubkey) -> Result<Self, Secp256k1Error> {
+        UncompressedPoint::from_eth_pubkey(p.0)
+    }
+}
+
+impl TryFrom<Secp256k1Pubkey> for CompressedPoint {
+    type Error = Secp256k1Error;
+
+    /// Checks the key is on curve, erroring with `InvalidPublicKey` otherwise.
+    fn try_from(p: Secp256k1Pubkey) -> Result<Self, Secp256k1Error> {
+        Ok(UncompressedPoint::try_from(p)?.compress())
+    }
+}
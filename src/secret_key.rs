@@ -0,0 +1,139 @@
+use core::fmt::{Debug, Formatter};
+
+use crate::{eth::RecoverableSignature, rfc6979, Curve, Message, Secp256k1Error, Secp256k1Point, UncompressedPoint};
+
+/// ### Secret Key
+///
+/// A secp256k1 secret scalar `𝒌` in the range `1..𝑁`. The underlying bytes are never printed by
+/// `Debug`, and with the `zeroize` feature enabled they are wiped from memory on drop.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey([u8; 32]);
+
+impl SecretKey {
+    /// ### From Bytes
+    ///
+    /// Creates a secret key from a big-endian scalar, checking that it is non-zero and less
+    /// than 𝑁.
+    pub fn from_bytes(bytes: [u8; 32]) -> Result<Self, Secp256k1Error> {
+        if bytes == [0u8; 32] || bytes >= Curve::N {
+            return Err(Secp256k1Error::InvalidSecretKey);
+        }
+        Ok(Self(bytes))
+    }
+
+    /// ### As Bytes
+    ///
+    /// Borrows the big-endian scalar bytes of the secret key.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// ### Public Key
+    ///
+    /// Calculates the public key point `𝒌·𝐺`.
+    pub fn public_key(&self) -> Result<UncompressedPoint, Secp256k1Error> {
+        Curve::mul_g_unchecked(&self.0)
+    }
+
+    /// ### Tweak Add
+    ///
+    /// Calculates `𝒌 + 𝒕 mod 𝑁`, the secret key of the point tweak `𝑃 + 𝒕·𝐺`, eg: for BIP-32
+    /// child keys and taproot output keys. Returns `ScalarOutOfRange` if `𝒕 ≥ 𝑁`, and
+    /// `InvalidSecretKey` if the result is zero.
+    pub fn tweak_add(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error> {
+        if tweak >= Curve::N {
+            return Err(Secp256k1Error::ScalarOutOfRange);
+        }
+        Self::from_bytes(Curve::add_mod_n(&self.0, &tweak))
+    }
+
+    /// ### Tweak Mul
+    ///
+    /// Calculates `𝒌·𝒕 mod 𝑁`, the secret key of `𝒕·𝑃`. Returns `ScalarOutOfRange` if `𝒕 ≥ 𝑁`,
+    /// and `InvalidSecretKey` if `𝒕` is zero.
+    pub fn tweak_mul(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error> {
+        if tweak >= Curve::N {
+            return Err(Secp256k1Error::ScalarOutOfRange);
+        }
+        Self::from_bytes(Curve::mul_mod_n(&self.0, &tweak))
+    }
+
+    /// ### Sign Prehash
+    ///
+    /// Signs a 32-byte `hash` with ECDSA, using the deterministic RFC 6979 nonce `𝒏`:
+    /// `𝑟 = (𝒏·𝐺)ₓ mod 𝑁` and `𝑠 = 𝒏⁻¹(𝑧 + 𝑟·𝒌) mod 𝑁`. The signature is normalized to low-𝑠,
+    /// with the recovery id flipped to match, so it is accepted by Ethereum and
+    /// `Curve::verify_ecdsa`. Errors with `InvalidSignature` in the negligible case that `𝑟` or
+    /// `𝑠` is zero.
+    pub fn sign_prehash(&self, hash: &[u8; 32]) -> Result<RecoverableSignature, Secp256k1Error> {
+        let nonce = rfc6979::nonce(self, hash);
+        let big_r = Curve::mul_g_unchecked(&nonce)?;
+        let r = Curve::add_mod_n(big_r.x_ref(), &[0u8; 32]);
+        // 𝑠 = 𝒏⁻¹(𝑧 + 𝑟·𝒌) mod 𝑁
+        let z = Curve::add_mod_n(hash, &Curve::mul_mod_n(&r, &self.0));
+        let s = Curve::mul_mod_n(&Curve::mod_inv_n(&nonce)?, &z);
+        if Curve::is_zero(&r) || Curve::is_zero(&s) {
+            return Err(Secp256k1Error::InvalidSignature);
+        }
+        let high = Curve::is_high(&s);
+        Ok(RecoverableSignature {
+            r,
+            s: Curve::conditional_negate_n(&s, high),
+            recovery_id: (big_r.is_odd() != high) as u8 | ((*big_r.x_ref() >= Curve::N) as u8) << 1,
+        })
+    }
+
+    /// ### Sign Message
+    ///
+    /// As `sign_prehash`, over a hashed `Message`.
+    pub fn sign_message(&self, message: &Message) -> Result<RecoverableSignature, Secp256k1Error> {
+        self.sign_prehash(message.as_bytes())
+    }
+
+    /// ### Diffie Hellman
+    ///
+    /// Calculates the ECDH shared secret with `pubkey`, the 𝑋-coordinate of `𝒌·𝑃` as in SEC1 and
+    /// Wycheproof. Errors with `InvalidPublicKey` if `pubkey` is not on curve.
+    pub fn diffie_hellman<T: Secp256k1Point>(&self, pubkey: &T) -> Result<[u8; 32], Secp256k1Error> {
+        Ok(Curve::ecmul(pubkey, &self.0)?.x())
+    }
+
+    /// ### Negate
+    ///
+    /// Calculates `𝑁 - 𝒌`, the secret key of `-𝑃`, eg: to match a BIP-340 public key with an
+    /// even 𝑌-coordinate.
+    pub fn negate(&self) -> Self {
+        Self(Curve::negate_n(&self.0))
+    }
+}
+
+impl TryFrom<[u8; 32]> for SecretKey {
+    type Error = Secp256k1Error;
+
+    fn try_from(bytes: [u8; 32]) -> Result<Self, Secp256k1Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl Debug for SecretKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SecretKey {}
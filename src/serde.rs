@@ -0,0 +1,118 @@
+//! # Serde
+//!
+//! Points, recoverable signatures and secret keys are serialized as lowercase hex strings in
+//! human-readable formats such as JSON, and as fixed-size byte tuples (no length prefix) in
+//! binary formats. Signatures use the 65-byte `𝑟‖𝑠‖𝑣` layout of
+//! `RecoverableSignature::to_raw_bytes`, and everything is validated on deserialization.
+
+use core::{fmt, marker::PhantomData};
+
+use ::serde::{
+    de::{Error, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{eth::RecoverableSignature, hex, CompressedPoint, SecretKey, UncompressedPoint};
+
+fn serialize_bytes<S: Serializer, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        let mut buf = [0u8; 130];
+        serializer.serialize_str(hex::encode_to_slice(bytes, &mut buf[..N * 2]).map_err(::serde::ser::Error::custom)?)
+    } else {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for b in bytes {
+            tuple.serialize_element(b)?;
+        }
+        tuple.end()
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error> {
+    struct BytesVisitor<const N: usize>(PhantomData<[u8; N]>);
+
+    impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+        type Value = [u8; N];
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{} bytes or a hex string of {} characters", N, N * 2)
+        }
+
+        fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+            if v.len() != N * 2 {
+                return Err(E::invalid_length(v.len(), &self));
+            }
+            hex::decode(v).map_err(|_| E::custom("invalid hex character"))
+        }
+
+        fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut out = [0u8; N];
+            for (i, b) in out.iter_mut().enumerate() {
+                *b = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(i, &self))?;
+            }
+            Ok(out)
+        }
+    }
+
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor::<N>(PhantomData))
+    } else {
+        deserializer.deserialize_tuple(N, BytesVisitor::<N>(PhantomData))
+    }
+}
+
+impl Serialize for CompressedPoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CompressedPoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        CompressedPoint::from_sec1(&deserialize_bytes(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for UncompressedPoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UncompressedPoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let p = UncompressedPoint(deserialize_bytes(deserializer)?);
+        if !p.is_on_curve() {
+            return Err(D::Error::custom("point off curve"));
+        }
+        Ok(p)
+    }
+}
+
+impl Serialize for RecoverableSignature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_raw_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RecoverableSignature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RecoverableSignature::from_raw_bytes(&deserialize_bytes(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for SecretKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(self.as_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SecretKey::from_bytes(deserialize_bytes(deserializer)?).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,256 @@
+//! # Session
+//!
+//! Multi-party signing protocols such as MuSig2, FROST and adaptor signatures take several
+//! rounds, which on Solana usually means several transactions. `Session` holds the state of
+//! one signing session between rounds: the message, each signer's public nonce commitments and
+//! the running sum of partial signatures, along with the current round.
+//!
+//! Sessions are stored in account data with a versioned, fixed-size encoding. Reading a session
+//! back re-validates all of it, so a corrupted or foreign account can't be resumed, and every
+//! transition checks the round it expects so rounds can't be replayed or skipped.
+
+use crate::{CompressedPoint, Curve, Secp256k1Error, Secp256k1Point, UncompressedPoint};
+
+/// ### Protocol
+///
+/// The protocol a session belongs to, stored so a session can't be resumed by the wrong one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+#[repr(u8)]
+pub enum Protocol {
+    MuSig2 = 0,
+    Frost = 1,
+    Adaptor = 2,
+}
+
+impl TryFrom<u8> for Protocol {
+    type Error = Secp256k1Error;
+
+    fn try_from(tag: u8) -> Result<Self, Secp256k1Error> {
+        match tag {
+            0 => Ok(Protocol::MuSig2),
+            1 => Ok(Protocol::Frost),
+            2 => Ok(Protocol::Adaptor),
+            _ => Err(Secp256k1Error::InvalidState),
+        }
+    }
+}
+
+/// ### Round
+///
+/// Sessions move from collecting nonces, to collecting partial signatures, to complete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+#[repr(u8)]
+pub enum Round {
+    Nonces = 0,
+    Partials = 1,
+    Complete = 2,
+}
+
+/// ### Session
+///
+/// The state of a signing session between `N` signers, each contributing a pair of public
+/// nonces `(𝑅₁, 𝑅₂)` and then a partial signature `𝑠ᵢ`. `N` may be at most 64.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq)))]
+pub struct Session<const N: usize> {
+    protocol: Protocol,
+    round: Round,
+    message: [u8; 32],
+    nonces: [[CompressedPoint; 2]; N],
+    nonces_received: u64,
+    partials_received: u64,
+    partial_sum: [u8; 32],
+}
+
+impl<const N: usize> Session<N> {
+    /// ### Version
+    ///
+    /// The encoding version written as the first byte of every session.
+    pub const VERSION: u8 = 1;
+
+    /// ### Size
+    ///
+    /// Encoded size: `version[1] ‖ protocol[1] ‖ round[1] ‖ message[32] ‖ nonces[66·N] ‖
+    /// nonces_received[8] ‖ partials_received[8] ‖ partial_sum[32]`, with integers little-endian.
+    pub const SIZE: usize = 3 + 32 + 66 * N + 8 + 8 + 32;
+
+    const ALL: u64 = u64::MAX >> (64 - N);
+
+    /// ### New
+    ///
+    /// Starts a session signing `message`, collecting nonces first.
+    pub const fn new(protocol: Protocol, message: [u8; 32]) -> Self {
+        assert!(N > 0 && N <= 64, "Sessions support 1 to 64 signers");
+        Self {
+            protocol,
+            round: Round::Nonces,
+            message,
+            nonces: [[CompressedPoint([0u8; 33]); 2]; N],
+            nonces_received: 0,
+            partials_received: 0,
+            partial_sum: [0u8; 32],
+        }
+    }
+
+    pub const fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    pub const fn round(&self) -> Round {
+        self.round
+    }
+
+    pub const fn message(&self) -> &[u8; 32] {
+        &self.message
+    }
+
+    /// ### Add Nonces
+    ///
+    /// Records the public nonces of `signer`, checking they are valid points. Moves the session
+    /// on to collecting partial signatures once every signer's nonces are in.
+    pub fn add_nonces(&mut self, signer: usize, nonces: [CompressedPoint; 2]) -> Result<Round, Secp256k1Error> {
+        self.check(Round::Nonces, signer, self.nonces_received)?;
+        for nonce in &nonces {
+            nonce.decompress()?;
+        }
+        self.nonces[signer] = nonces;
+        self.nonces_received |= 1 << signer;
+        if self.nonces_received == Self::ALL {
+            self.round = Round::Partials;
+        }
+        Ok(self.round)
+    }
+
+    /// ### Nonces
+    ///
+    /// Returns the public nonces of `signer`, once every signer's nonces are in.
+    pub fn nonces(&self, signer: usize) -> Result<&[CompressedPoint; 2], Secp256k1Error> {
+        if self.round == Round::Nonces {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        self.nonces.get(signer).ok_or(Secp256k1Error::InvalidState)
+    }
+
+    /// ### Aggregate Nonces
+    ///
+    /// Sums the first and second nonces of every signer into `(Σ𝑅₁, Σ𝑅₂)`, ready to be
+    /// combined with the protocol's nonce coefficient.
+    pub fn aggregate_nonces(&self) -> Result<[UncompressedPoint; 2], Secp256k1Error> {
+        if self.round == Round::Nonces {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let mut out = [Curve::G; 2];
+        for (i, out) in out.iter_mut().enumerate() {
+            let mut terms = [(CompressedPoint([0u8; 33]), one); N];
+            for (term, nonces) in terms.iter_mut().zip(&self.nonces) {
+                term.0 = nonces[i];
+            }
+            let mut sum = crate::msm::MsmState::new(&terms)?;
+            sum.step(&terms, N)?;
+            *out = sum.result()?;
+        }
+        Ok(out)
+    }
+
+    /// ### Add Partial
+    ///
+    /// Adds the partial signature `𝑠ᵢ` of `signer` to the running sum modulo 𝑁, completing the
+    /// session once every signer's partial signature is in. Verifying each partial signature
+    /// is left to the protocol.
+    pub fn add_partial(&mut self, signer: usize, s: [u8; 32]) -> Result<Round, Secp256k1Error> {
+        self.check(Round::Partials, signer, self.partials_received)?;
+        if s >= Curve::N {
+            return Err(Secp256k1Error::ScalarOutOfRange);
+        }
+        self.partial_sum = Curve::add_mod_n(&self.partial_sum, &s);
+        self.partials_received |= 1 << signer;
+        if self.partials_received == Self::ALL {
+            self.round = Round::Complete;
+        }
+        Ok(self.round)
+    }
+
+    /// ### Partial Sum
+    ///
+    /// Returns `Σ𝑠ᵢ mod 𝑁` once every partial signature is in.
+    pub fn partial_sum(&self) -> Result<[u8; 32], Secp256k1Error> {
+        if self.round != Round::Complete {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        Ok(self.partial_sum)
+    }
+
+    fn check(&self, round: Round, signer: usize, received: u64) -> Result<(), Secp256k1Error> {
+        if self.round != round || signer >= N || received & (1 << signer) != 0 {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        Ok(())
+    }
+
+    /// ### Write
+    ///
+    /// Encodes the session into the start of `out`, eg: account data. Errors if `out` is
+    /// shorter than `Session::SIZE`.
+    pub fn write(&self, out: &mut [u8]) -> Result<(), Secp256k1Error> {
+        let out = out.get_mut(..Self::SIZE).ok_or(Secp256k1Error::InvalidState)?;
+        out[0] = Self::VERSION;
+        out[1] = self.protocol as u8;
+        out[2] = self.round as u8;
+        out[3..35].copy_from_slice(&self.message);
+        let (nonces, rest) = out[35..].split_at_mut(66 * N);
+        for (chunk, [r1, r2]) in nonces.chunks_exact_mut(66).zip(&self.nonces) {
+            chunk[..33].copy_from_slice(&r1.0);
+            chunk[33..].copy_from_slice(&r2.0);
+        }
+        rest[..8].copy_from_slice(&self.nonces_received.to_le_bytes());
+        rest[8..16].copy_from_slice(&self.partials_received.to_le_bytes());
+        rest[16..].copy_from_slice(&self.partial_sum);
+        Ok(())
+    }
+
+    /// ### Read
+    ///
+    /// Decodes a session written by `write`, checking the version, protocol and round, that the
+    /// received signers are consistent with the round, that every received nonce is a valid
+    /// point and that the partial sum is less than 𝑁.
+    pub fn read(bytes: &[u8]) -> Result<Self, Secp256k1Error> {
+        let bytes = bytes.get(..Self::SIZE).ok_or(Secp256k1Error::InvalidState)?;
+        if bytes[0] != Self::VERSION {
+            return Err(Secp256k1Error::InvalidState);
+        }
+        let mut session = Self::new(Protocol::try_from(bytes[1])?, bytes[3..35].try_into().expect("32 bytes"));
+        let (nonces, rest) = bytes[35..].split_at(66 * N);
+        session.nonces_received = u64::from_le_bytes(rest[..8].try_into().expect("8 bytes"));
+        session.partials_received = u64::from_le_bytes(rest[8..16].try_into().expect("8 bytes"));
+        session.partial_sum = rest[16..].try_into().expect("32 bytes");
+
+        let (nonces_received, partials_received) = (session.nonces_received, session.partials_received);
+        session.round = match bytes[2] {
+            0 if nonces_received & !Self::ALL == 0 && nonces_received != Self::ALL && partials_received == 0 => Round::Nonces,
+            1 if nonces_received == Self::ALL && partials_received & !Self::ALL == 0 && partials_received != Self::ALL => Round::Partials,
+            2 if nonces_received == Self::ALL && partials_received == Self::ALL => Round::Complete,
+            _ => return Err(Secp256k1Error::InvalidState),
+        };
+        if session.partial_sum >= Curve::N || (partials_received == 0 && session.partial_sum != [0u8; 32]) {
+            return Err(Secp256k1Error::InvalidState);
+        }
+
+        for (i, (chunk, pair)) in nonces.chunks_exact(66).zip(session.nonces.iter_mut()).enumerate() {
+            let r1 = CompressedPoint(chunk[..33].try_into().expect("33 bytes"));
+            let r2 = CompressedPoint(chunk[33..].try_into().expect("33 bytes"));
+            if nonces_received & (1 << i) != 0 {
+                if r1.decompress().is_err() || r2.decompress().is_err() {
+                    return Err(Secp256k1Error::InvalidState);
+                }
+            } else if r1.0 != [0u8; 33] || r2.0 != [0u8; 33] {
+                return Err(Secp256k1Error::InvalidState);
+            }
+            *pair = [r1, r2];
+        }
+        Ok(session)
+    }
+}
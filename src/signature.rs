@@ -0,0 +1,76 @@
+//! # Signature
+//!
+//! RustCrypto `signature` trait implementations, so this crate's keys can be dropped into generic
+//! code such as JWT (`ES256K`) libraries and agent-style signing services.
+//!
+//! `Signer` and `Verifier` hash messages with SHA-256, as RustCrypto's `k256` does, while
+//! `DigestSigner` and `DigestVerifier` take any 32-byte digest, eg: `Keccak256` for Ethereum.
+//! Signatures are always produced low-𝑠 and high-𝑠 signatures are rejected.
+
+use ::signature::{
+    digest::{consts::U32, Digest},
+    DigestSigner, DigestVerifier, Error, SignatureEncoding, Signer, Verifier,
+};
+
+use crate::{arithmetic, backend, eth::RecoverableSignature, CompressedPoint, Curve, Secp256k1Point, SecretKey, UncompressedPoint};
+
+impl Signer<RecoverableSignature> for SecretKey {
+    fn try_sign(&self, msg: &[u8]) -> Result<RecoverableSignature, Error> {
+        self.sign_prehash(&backend::sha256(&[msg])).map_err(|_| Error::new())
+    }
+}
+
+impl<D: Digest<OutputSize = U32>> DigestSigner<D, RecoverableSignature> for SecretKey {
+    fn try_sign_digest(&self, digest: D) -> Result<RecoverableSignature, Error> {
+        self.sign_prehash(&digest.finalize().into()).map_err(|_| Error::new())
+    }
+}
+
+impl Verifier<RecoverableSignature> for CompressedPoint {
+    fn verify(&self, msg: &[u8], signature: &RecoverableSignature) -> Result<(), Error> {
+        verify_prehash(&backend::sha256(&[msg]), signature, self)
+    }
+}
+
+impl<D: Digest<OutputSize = U32>> DigestVerifier<D, RecoverableSignature> for CompressedPoint {
+    fn verify_digest(&self, digest: D, signature: &RecoverableSignature) -> Result<(), Error> {
+        verify_prehash(&digest.finalize().into(), signature, self)
+    }
+}
+
+impl Verifier<RecoverableSignature> for UncompressedPoint {
+    fn verify(&self, msg: &[u8], signature: &RecoverableSignature) -> Result<(), Error> {
+        verify_prehash(&backend::sha256(&[msg]), signature, &self.compress())
+    }
+}
+
+impl<D: Digest<OutputSize = U32>> DigestVerifier<D, RecoverableSignature> for UncompressedPoint {
+    fn verify_digest(&self, digest: D, signature: &RecoverableSignature) -> Result<(), Error> {
+        verify_prehash(&digest.finalize().into(), signature, &self.compress())
+    }
+}
+
+impl SignatureEncoding for RecoverableSignature {
+    type Repr = [u8; 65];
+}
+
+impl TryFrom<&[u8]> for RecoverableSignature {
+    type Error = Error;
+
+    /// Parses a 65-byte `𝑟‖𝑠‖𝑣` signature, as `from_bytes`.
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes: &[u8; 65] = bytes.try_into().map_err(|_| Error::new())?;
+        Self::from_bytes(bytes).map_err(|_| Error::new())
+    }
+}
+
+impl From<RecoverableSignature> for [u8; 65] {
+    fn from(signature: RecoverableSignature) -> Self {
+        signature.to_bytes()
+    }
+}
+
+/// Verifies the `𝑟‖𝑠` of a signature, ignoring its recovery id as ECDSA verification does.
+fn verify_prehash(hash: &[u8; 32], signature: &RecoverableSignature, pubkey: &CompressedPoint) -> Result<(), Error> {
+    Curve::verify_ecdsa(hash, &arithmetic::concat(&signature.r, &signature.s), pubkey).map_err(|_| Error::new())
+}
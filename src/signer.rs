@@ -0,0 +1,84 @@
+//! # Signer
+//!
+//! Pluggable signers, so keys held by a hardware wallet, HSM or cloud KMS can drive this
+//! crate's protocols without being exported. `Secp256k1Signer` covers ECDSA over a prehashed
+//! message and BIP-340 Schnorr, and is implemented for the in-memory `SecretKey`. Functions
+//! that sign, such as `es256k::sign`, `libp2p::sign` and `eth::sign_tx_digest`, accept any
+//! signer.
+//!
+//! Remote signers are usually asynchronous, so `AsyncSecp256k1Signer` mirrors the trait with
+//! futures. Every `Secp256k1Signer` is also an `AsyncSecp256k1Signer` whose futures are
+//! immediately ready.
+
+use core::future::{ready, Future};
+
+use crate::{bip340, eth::RecoverableSignature, Secp256k1Error, SecretKey, UncompressedPoint};
+
+/// ### Secp256k1 Signer
+///
+/// A holder of a secp256k1 secret key that signs without revealing it.
+pub trait Secp256k1Signer {
+    /// ### Public Key
+    ///
+    /// The public key `𝒌·𝐺` of the signer.
+    fn public_key(&self) -> Result<UncompressedPoint, Secp256k1Error>;
+
+    /// ### Sign Prehash
+    ///
+    /// Signs a 32-byte `hash` with low-𝑠 ECDSA, returning the signature and its recovery id.
+    fn sign_prehash(&self, hash: &[u8; 32]) -> Result<RecoverableSignature, Secp256k1Error>;
+
+    /// ### Sign Schnorr
+    ///
+    /// Signs `message` with BIP-340 Schnorr, using the auxiliary randomness `aux_rand`, and
+    /// returns `𝑟‖𝑠` for the signer's even-𝑌 public key.
+    fn sign_schnorr(&self, message: &[u8], aux_rand: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error>;
+}
+
+impl Secp256k1Signer for SecretKey {
+    fn public_key(&self) -> Result<UncompressedPoint, Secp256k1Error> {
+        SecretKey::public_key(self)
+    }
+
+    fn sign_prehash(&self, hash: &[u8; 32]) -> Result<RecoverableSignature, Secp256k1Error> {
+        SecretKey::sign_prehash(self, hash)
+    }
+
+    fn sign_schnorr(&self, message: &[u8], aux_rand: &[u8; 32]) -> Result<[u8; 64], Secp256k1Error> {
+        bip340::sign(self, message, aux_rand)
+    }
+}
+
+/// ### Async Secp256k1 Signer
+///
+/// `Secp256k1Signer` for signers that answer asynchronously, eg: over the network.
+pub trait AsyncSecp256k1Signer {
+    /// ### Public Key
+    ///
+    /// The public key `𝒌·𝐺` of the signer.
+    fn public_key(&self) -> impl Future<Output = Result<UncompressedPoint, Secp256k1Error>>;
+
+    /// ### Sign Prehash
+    ///
+    /// Signs a 32-byte `hash` with low-𝑠 ECDSA, returning the signature and its recovery id.
+    fn sign_prehash(&self, hash: &[u8; 32]) -> impl Future<Output = Result<RecoverableSignature, Secp256k1Error>>;
+
+    /// ### Sign Schnorr
+    ///
+    /// Signs `message` with BIP-340 Schnorr, using the auxiliary randomness `aux_rand`.
+    fn sign_schnorr(&self, message: &[u8], aux_rand: &[u8; 32]) -> impl Future<Output = Result<[u8; 64], Secp256k1Error>>;
+}
+
+impl<S: Secp256k1Signer> AsyncSecp256k1Signer for S {
+    fn public_key(&self) -> impl Future<Output = Result<UncompressedPoint, Secp256k1Error>> {
+        ready(Secp256k1Signer::public_key(self))
+    }
+
+    fn sign_prehash(&self, hash: &[u8; 32]) -> impl Future<Output = Result<RecoverableSignature, Secp256k1Error>> {
+        ready(Secp256k1Signer::sign_prehash(self, hash))
+    }
+
+    fn sign_schnorr(&self, message: &[u8], aux_rand: &[u8; 32]) -> impl Future<Output = Result<[u8; 64], Secp256k1Error>> {
+        ready(Secp256k1Signer::sign_schnorr(self, message, aux_rand))
+    }
+}
@@ -0,0 +1,39 @@
+//! # Strategies
+//!
+//! `proptest` strategies generating valid secret keys, points and signatures, for property
+//! testing code built on this crate.
+
+use ::proptest::prelude::*;
+
+use crate::{eth::RecoverableSignature, CompressedPoint, Curve, SecretKey, UncompressedPoint};
+
+/// ### Secret Key
+///
+/// Generates secret keys uniformly in `1..𝑁`.
+pub fn secret_key() -> impl Strategy<Value = SecretKey> {
+    any::<[u8; 32]>().prop_filter_map("scalar out of range", |k| SecretKey::from_bytes(k).ok())
+}
+
+/// ### Uncompressed Point
+///
+/// Generates public keys `𝒌·𝐺` for random secret keys `𝒌`.
+pub fn uncompressed_point() -> impl Strategy<Value = UncompressedPoint> {
+    secret_key().prop_map(|k| k.public_key().expect("secret keys have public keys"))
+}
+
+/// ### Compressed Point
+///
+/// Generates compressed public keys `𝒌·𝐺` for random secret keys `𝒌`.
+pub fn compressed_point() -> impl Strategy<Value = CompressedPoint> {
+    uncompressed_point().prop_map(CompressedPoint::from)
+}
+
+/// ### Recoverable Signature
+///
+/// Generates well-formed low-𝑠 signatures. They are not guaranteed to recover to a public key.
+pub fn recoverable_signature() -> impl Strategy<Value = RecoverableSignature> {
+    (secret_key(), secret_key(), any::<bool>()).prop_map(|(r, s, odd)| {
+        let s = Curve::conditional_negate_n(s.as_bytes(), Curve::is_high(s.as_bytes()));
+        RecoverableSignature { r: *r.as_bytes(), s, recovery_id: odd as u8 }
+    })
+}
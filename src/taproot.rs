@@ -0,0 +1,50 @@
+//! # Taproot
+//!
+//! BIP-341 key path spends, so Bitcoin bridge programs can check a Taproot input was signed by
+//! the key it commits to. The output key is `𝑄 = 𝑃 + 𝒕·𝐺` for the even-𝑌 internal key `𝑃` and
+//! `𝒕 = tagged_hash("TapTweak", 𝑃ₓ ‖ merkle_root)`, calculated with a single recover, and the
+//! spend's BIP-340 signature is then verified against `𝑄ₓ` with a second.
+//!
+//! Sighashes are chain data the caller computes, so this module takes them as given.
+
+use crate::{backend, bip340, Curve, Secp256k1Error, Secp256k1Point, UncompressedPoint};
+
+/// ### Tap Tweak
+///
+/// The tweak `tagged_hash("TapTweak", 𝑃ₓ ‖ merkle_root)` of an internal key, committing to the
+/// script tree with the given merkle root, or to no script tree at all with `None`.
+pub fn tap_tweak(internal_key: &[u8; 32], merkle_root: Option<&[u8; 32]>) -> [u8; 32] {
+    match merkle_root {
+        Some(root) => bip340::tagged_hash(b"TapTweak", &[internal_key, root]),
+        None => bip340::tagged_hash(b"TapTweak", &[internal_key]),
+    }
+}
+
+/// ### Output Key
+///
+/// Calculates the output key `𝑄` of an internal key, whose 𝑋-coordinate is the witness
+/// program of a P2TR output, and whose parity goes in script path control blocks. Errors with
+/// `InvalidPublicKey` if the internal key is not on curve, and with `ScalarOutOfRange` or
+/// `IdentityPoint` in the negligible cases BIP-341 also fails.
+pub fn output_key(internal_key: &[u8; 32], merkle_root: Option<&[u8; 32]>) -> Result<UncompressedPoint, Secp256k1Error> {
+    let t = tap_tweak(internal_key, merkle_root);
+    if t >= Curve::N {
+        return Err(Secp256k1Error::ScalarOutOfRange);
+    }
+    Ok(UncompressedPoint(backend::tweak(internal_key, false, &t)?))
+}
+
+/// ### Verify Key Path
+///
+/// Verifies the 64-byte BIP-340 signature of a key path spend over its BIP-341 `sighash`,
+/// against the output key of `internal_key` and `merkle_root`. A 65-byte witness signature's
+/// trailing sighash type only changes how `sighash` is computed, so drop it before calling.
+pub fn verify_key_path(
+    sighash: &[u8; 32],
+    internal_key: &[u8; 32],
+    merkle_root: Option<&[u8; 32]>,
+    signature: &[u8; 64],
+) -> Result<(), Secp256k1Error> {
+    let output_key = output_key(internal_key, merkle_root)?;
+    bip340::verify(output_key.x_ref(), sighash, signature)
+}
@@ -0,0 +1,131 @@
+//! # Threshold ECDSA
+//!
+//! Building blocks for auditing GG20 and CGGMP style threshold ECDSA signing sessions, where
+//! each party `𝑖` holds additive shares `𝒌ᵢ`, `𝛾ᵢ`, `𝛿ᵢ` of `𝒌`, `𝛾` and `𝛿 = 𝒌𝛾`, and `𝜎ᵢ` of `𝒌𝑥`
+//! for the secret key `𝑥`.
+//!
+//! Given the public values broadcast during signing, these check that the presignature point
+//! `𝑅 = 𝒌⁻¹·𝐺` was reconstructed honestly, that each party's partial signature is consistent
+//! with its commitments, and combine the partial signatures into a standard ECDSA signature.
+
+use crate::{arithmetic, eth::RecoverableSignature, Curve, Secp256k1Error, Secp256k1Point, UncompressedPoint};
+
+/// ### Sum Points
+///
+/// Sums the points `Σ𝑃ᵢ`, erroring with `InvalidPublicKey` if any point is not on curve and
+/// `IdentityPoint` if the sum is the point at infinity.
+pub fn sum_points(points: &[UncompressedPoint]) -> Result<UncompressedPoint, Secp256k1Error> {
+    let mut acc: Option<[arithmetic::Limbs; 2]> = None;
+    for point in points {
+        let p = arithmetic::point_from_be_bytes(&on_curve(point)?.0);
+        acc = match acc {
+            None => Some(p),
+            Some(acc) => arithmetic::affine_add_checked(&acc, &p),
+        };
+    }
+    acc.map(|p| UncompressedPoint(arithmetic::point_to_be_bytes(&p)))
+        .ok_or(Secp256k1Error::IdentityPoint)
+}
+
+/// ### Sum Scalars
+///
+/// Sums additive scalar shares `Σ𝒌ᵢ mod 𝑁`, eg: the `𝛿ᵢ` broadcast by each party. Errors with
+/// `ScalarOutOfRange` if any share is not less than 𝑁.
+pub fn sum_scalars(shares: &[[u8; 32]]) -> Result<[u8; 32], Secp256k1Error> {
+    shares.iter().try_fold([0u8; 32], |acc, share| match *share < Curve::N {
+        true => Ok(Curve::add_mod_n(&acc, share)),
+        false => Err(Secp256k1Error::ScalarOutOfRange),
+    })
+}
+
+/// ### Reconstruct 𝑅
+///
+/// Reconstructs the presignature point `𝑅 = 𝛿⁻¹·Σ𝛤ᵢ` from each party's `𝛤ᵢ = 𝛾ᵢ·𝐺` and their
+/// `𝛿ᵢ` shares.
+pub fn reconstruct_r(gammas: &[UncompressedPoint], deltas: &[[u8; 32]]) -> Result<UncompressedPoint, Secp256k1Error> {
+    if gammas.len() != deltas.len() {
+        return Err(Secp256k1Error::InvalidState);
+    }
+    let delta_inv = Curve::mod_inv_n(&sum_scalars(deltas)?)?;
+    Curve::ecmul(&sum_points(gammas)?, &delta_inv)
+}
+
+/// ### Check 𝑅 Shares
+///
+/// Checks each party's `𝑅̄ᵢ = 𝒌ᵢ·𝑅` sum to `𝐺`, which holds only if `𝑅 = 𝒌⁻¹·𝐺`. Errors with
+/// `InvalidPublicKey` if any share is not on curve.
+pub fn check_r_shares(r_shares: &[UncompressedPoint]) -> Result<(), Secp256k1Error> {
+    if sum_points(r_shares)? != Curve::G {
+        return Err(Secp256k1Error::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// ### Check 𝑆 Shares
+///
+/// Checks each party's `𝑆ᵢ = 𝜎ᵢ·𝑅` sum to the public key `𝑌 = 𝑥·𝐺`, which holds only if the
+/// `𝜎ᵢ` are shares of `𝒌𝑥`. Errors with `InvalidPublicKey` if any share or `pubkey` is not on
+/// curve.
+pub fn check_s_shares(s_shares: &[UncompressedPoint], pubkey: &UncompressedPoint) -> Result<(), Secp256k1Error> {
+    if sum_points(s_shares)? != *on_curve(pubkey)? {
+        return Err(Secp256k1Error::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// ### Verify Partial
+///
+/// Checks a party's partial signature `𝑠ᵢ = 𝑚𝒌ᵢ + 𝑟𝜎ᵢ` against its commitments `𝑅̄ᵢ` and `𝑆ᵢ`,
+/// as `𝑠ᵢ·𝑅 = 𝑚·𝑅̄ᵢ + 𝑟·𝑆ᵢ`, where `𝑟` is the 𝑋-coordinate of `𝑅`. This identifies the party
+/// responsible when the combined signature fails to verify. Errors with `InvalidPublicKey` if
+/// any point is not on curve.
+pub fn verify_partial(
+    r: &UncompressedPoint,
+    r_share: &UncompressedPoint,
+    s_share: &UncompressedPoint,
+    hash: &[u8; 32],
+    partial: &[u8; 32],
+) -> Result<(), Secp256k1Error> {
+    // Multiplication only reads the 𝑋-coordinate and parity, so check the full points
+    let (r, r_share, s_share) = (on_curve(r)?, on_curve(r_share)?, on_curve(s_share)?);
+    let lhs = Curve::ecmul(r, partial)?;
+    let rhs = arithmetic::affine_add_checked(
+        &arithmetic::point_from_be_bytes(&Curve::ecmul(r_share, hash)?.0),
+        &arithmetic::point_from_be_bytes(&Curve::ecmul(s_share, r.x_ref())?.0),
+    );
+    if rhs.map(|p| arithmetic::point_to_be_bytes(&p)) != Some(lhs.0) {
+        return Err(Secp256k1Error::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// ### Combine
+///
+/// Combines the partial signatures into `(𝑟, 𝑠 = Σ𝑠ᵢ)`, normalized to low-𝑠 with the recovery id
+/// adjusted to match, ready to be checked with `RecoverableSignature::recover`.
+///
+/// Errors with `ScalarOutOfRange` if any partial is not less than 𝑁, or in the negligible case
+/// that `𝑅.𝑋 ≥ 𝑁`, which can't be represented by a recovery id the recover syscall accepts.
+pub fn combine(r: &UncompressedPoint, partials: &[[u8; 32]]) -> Result<RecoverableSignature, Secp256k1Error> {
+    if *r.x_ref() >= Curve::N {
+        return Err(Secp256k1Error::ScalarOutOfRange);
+    }
+    let s = sum_scalars(partials)?;
+    if Curve::is_zero(&s) {
+        return Err(Secp256k1Error::ZeroScalar);
+    }
+    let high = Curve::is_high(&s);
+    Ok(RecoverableSignature {
+        r: r.x(),
+        s: Curve::conditional_negate_n(&s, high),
+        recovery_id: (r.is_odd() != high) as u8,
+    })
+}
+
+/// Errors with `InvalidPublicKey` if `point` is not on curve.
+fn on_curve(point: &UncompressedPoint) -> Result<&UncompressedPoint, Secp256k1Error> {
+    match point.is_on_curve() {
+        true => Ok(point),
+        false => Err(Secp256k1Error::InvalidPublicKey),
+    }
+}
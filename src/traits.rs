@@ -14,8 +14,16 @@ use crate::*;
 /// s = (𝑟∗𝑝𝑟𝑖𝑣𝐾𝑒𝑦) modulus 𝑁
 ///
 /// By using the precomputed r value of k=1, also known as the generator point G of the curve, and that and R.Y will always be even, we can create a valid ecdsa signature onchain, enabling us to use ecrecover to efficiently recover what its public key would have been. This allows us to generate uncompressed points from scalars onchain at a discount of ~4 million CUs compared to a naive implementation.
+///
+/// ### Checked and Unchecked
+///
+/// Every constructor and operation validates its inputs by default, and has an explicit
+/// `_unchecked` variant which skips validation for callers that have already done it. Operators
+/// return a `Result` and validate like the named methods: `Mul` checks its scalar as
+/// `Curve::ecmul` does, and `Add` decompresses compressed operands as `decompress` does and
+/// returns `IdentityPoint` for a point and its inverse, as `Curve::ecadd` does.
 pub trait Secp256k1Point:
-    TryFrom<[u8; 32]> + From<[u8; 65]> + Clone + PartialEq + Eq + Copy
+    TryFrom<[u8; 32]> + TryFrom<[u8; 65]> + Clone + PartialEq + Eq + Copy
 {
     /// ### Size
     /// 
@@ -32,17 +40,23 @@ pub trait Secp256k1Point:
 
     /// ### 𝑋
     /// Returns the 𝑋 coordinate of the underlying point  
-    fn x(&self) -> [u8; 32];
+    fn x(&self) -> [u8; 32] {
+        *self.x_ref()
+    }
 
-    /// ### 𝑋
-    /// Returns the 𝑋 coordinate of the underlying point  
+    /// ### 𝑋 Ref
+    /// Borrows the 𝑋 coordinate of the underlying point without copying it
+    fn x_ref(&self) -> &[u8; 32];
+
+    /// ### 𝑌
+    /// Returns the 𝑌 coordinate of the underlying point  
     fn y(&self) -> [u8; 32];
 
     /// ### Lift 𝑋 Unchecked
     /// 
     /// Finds the corresponding 𝑌-coordinate of a given 𝑋-coordinate. This does not guarantee the
     /// returned point is on curve. Only use this when you know you are dealing with a valid point. 
-    /// Otherwise, consider lift_x_checked
+    /// Otherwise, consider lift_x
     fn lift_x_unchecked(x: &[u8; 32]) -> Self;
 
     /// ### Lift 𝑋
@@ -57,15 +71,199 @@ pub trait Secp256k1Point:
     /// Inverts the parity of the 𝑌-coordinate of a point.
     fn invert(&mut self);  
 
+    /// ### Negate
+    /// 
+    /// Returns `-𝑃`, the point with the same 𝑋-coordinate and the opposite 𝑌-coordinate, as
+    /// `invert` without mutating the point.
+    fn negate(&self) -> Self {
+        let mut point = *self;
+        point.invert();
+        point
+    }
+
+    /// ### To Even 𝑌
+    /// 
+    /// Returns the point with the same 𝑋-coordinate and an even 𝑌-coordinate, as BIP-340 keys
+    /// and nonces require, along with the parity of the original point. `Parity::Odd` means the
+    /// point was negated, so the caller must negate its secret scalar to match, eg: with
+    /// `Curve::conditional_negate_n(k, parity.is_odd())`.
+    fn to_even_y(&self) -> (Self, Parity) {
+        match self.is_odd() {
+            true => (self.negate(), Parity::Odd),
+            false => (*self, Parity::Even),
+        }
+    }
+
     /// ### Compress
     /// 
     /// Compress a point into a CompressedPoint
     fn compress(&self) -> CompressedPoint;
 
-    /// ### Invert
+    /// ### Decompress
+    /// 
+    /// Decompress a point into an UncompressedPoint, checking that it is on curve
+    fn decompress(&self) -> Result<UncompressedPoint, Secp256k1Error>;
+
+    /// ### Decompress Unchecked
+    /// 
+    /// Decompress a point into an UncompressedPoint without checking that it is on curve. Only
+    /// use this when you know you are dealing with a valid point.
+    fn decompress_unchecked(&self) -> UncompressedPoint;
+
+    /// ### To Solana Pubkey
+    ///
+    /// Maps the point to its 32-byte Solana identity, as `pda::to_solana_pubkey`.
+    fn to_solana_pubkey(&self) -> [u8; 32] {
+        pda::to_solana_pubkey(self)
+    }
+
+    /// ### Tweak
+    /// 
+    /// Calculates `𝑃 + 𝒕·𝐺` for a tweak `𝒕`, returning `ScalarOutOfRange` if `𝒕 ≥ 𝑁`.
+    fn tweak(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error> {
+        if tweak >= Curve::N {
+            return Err(Secp256k1Error::ScalarOutOfRange);
+        }
+        self.tweak_unchecked(tweak)
+    }
+
+    /// ### Tweak Unchecked
     /// 
-    /// Decompress a point into an UncompressedPoint
-    fn decompress(&self) -> UncompressedPoint;
+    /// As `tweak`, without checking that `𝒕` is less than 𝑁. Tweaks `≥ 𝑁` are implicitly reduced.
+    fn tweak_unchecked(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error>;
+
+    /// ### Tweak Mul
+    /// 
+    /// Calculates `𝒕·𝑃` for a multiplicative tweak `𝒕`, eg: for blinding, returning `ZeroScalar`
+    /// if `𝒕` is zero and `ScalarOutOfRange` if `𝒕 ≥ 𝑁`.
+    fn tweak_mul(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error> {
+        if tweak == [0u8; 32] {
+            return Err(Secp256k1Error::ZeroScalar);
+        }
+        if tweak >= Curve::N {
+            return Err(Secp256k1Error::ScalarOutOfRange);
+        }
+        self.tweak_mul_unchecked(tweak)
+    }
+
+    /// ### Tweak Mul Unchecked
+    /// 
+    /// As `tweak_mul`, without checking that `𝒕` is in the range `1..𝑁`. Tweaks `≥ 𝑁` are
+    /// implicitly reduced.
+    fn tweak_mul_unchecked(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error>;
+}
+
+/// ### Parity
+///
+/// The parity of a point's 𝑌-coordinate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+impl Parity {
+    /// Returns true for `Parity::Odd`.
+    pub const fn is_odd(self) -> bool {
+        matches!(self, Parity::Odd)
+    }
+}
+
+impl From<bool> for Parity {
+    /// Maps `is_odd` to its parity.
+    fn from(is_odd: bool) -> Self {
+        if is_odd { Parity::Odd } else { Parity::Even }
+    }
+}
+
+/// ### Weierstrass Curve
+///
+/// A short Weierstrass curve `𝑦² = 𝑥³ + 𝑎𝑥 + 𝑏` over a 256-bit prime field, with its parameters as
+/// associated constants. All values are big-endian, and points are 64-byte `𝑋‖𝑌` pairs.
+///
+/// This lets code such as passkey (secp256r1) integrations share the same scalar and field
+/// arithmetic as secp256k1. The recover-based tricks behind `mul_g` and `ecmul` only exist
+/// for secp256k1, so they remain on `Curve`.
+pub trait WeierstrassCurve {
+    /// ### Curve order 𝑁
+    const N: [u8; 32];
+
+    /// ### Field Prime Modulus 𝑃
+    const P: [u8; 32];
+
+    /// ### Coefficient 𝑎
+    const A: [u8; 32];
+
+    /// ### Coefficient 𝑏
+    const B: [u8; 32];
+
+    /// ### Generator Point 𝐺
+    const G: [u8; 64];
+
+    /// ### Add Mod 𝑁
+    fn add_mod_n(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32];
+
+    /// ### Mul Mod 𝑁
+    fn mul_mod_n(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32];
+
+    /// ### Negate 𝑁
+    fn negate_n(k: &[u8; 32]) -> [u8; 32];
+
+    /// ### Modular Inverse 𝑁
+    ///
+    /// Errors with `ArithmeticOverflow` if `𝒌 ≡ 0`.
+    fn mod_inv_n(k: &[u8]) -> Result<[u8; 32], Secp256k1Error>;
+
+    /// ### Add Mod 𝑃
+    fn add_mod_p(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32];
+
+    /// ### Mul Mod 𝑃
+    fn mul_mod_p(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32];
+
+    /// ### Negate 𝑃
+    fn negate_p(k: &[u8; 32]) -> [u8; 32];
+
+    /// ### Modular Inverse 𝑃
+    ///
+    /// Errors with `ArithmeticOverflow` if `𝒌 ≡ 0`.
+    fn mod_inv_p(k: &[u8]) -> Result<[u8; 32], Secp256k1Error>;
+
+    /// ### Square Root Mod 𝑃
+    ///
+    /// Returns a square root of `𝒂` modulo 𝑃, or `None` if `𝒂` is not a quadratic residue.
+    fn sqrt_mod_p(a: &[u8; 32]) -> Option<[u8; 32]>;
+
+    /// ### Curve Equation
+    ///
+    /// Calculates the right-hand side of the curve equation, `𝑥³ + 𝑎𝑥 + 𝑏 mod 𝑃`.
+    fn curve_rhs(x: &[u8; 32]) -> [u8; 32] {
+        let x_3 = Self::mul_mod_p(&Self::mul_mod_p(x, x), x);
+        Self::add_mod_p(&Self::add_mod_p(&x_3, &Self::mul_mod_p(&Self::A, x)), &Self::B)
+    }
+
+    /// ### Is On Curve
+    ///
+    /// Checks that both coordinates are canonical field elements satisfying the curve equation.
+    fn is_on_curve(point: &[u8; 64]) -> bool {
+        let (x, y) = arithmetic::split(point);
+        x < Self::P && y < Self::P && Self::mul_mod_p(&y, &y) == Self::curve_rhs(&x)
+    }
 
-    fn tweak(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error>;
-}
\ No newline at end of file
+    /// ### Decompress SEC1
+    ///
+    /// Decompresses a 33-byte SEC1 point, checking its prefix and that it lies on the curve.
+    fn decompress_sec1(point: &[u8; 33]) -> Result<[u8; 64], Secp256k1Error> {
+        if point[0] != 0x02 && point[0] != 0x03 {
+            return Err(Secp256k1Error::InvalidPublicKey);
+        }
+        let x: [u8; 32] = point[1..].try_into().expect("32 bytes");
+        if x >= Self::P {
+            return Err(Secp256k1Error::InvalidPublicKey);
+        }
+        let mut y = Self::sqrt_mod_p(&Self::curve_rhs(&x)).ok_or(Secp256k1Error::InvalidYCoordinate)?;
+        if y[31] & 1 != point[0] & 1 {
+            y = Self::negate_p(&y);
+        }
+        Ok(arithmetic::concat(&x, &y))
+    }
+}
@@ -0,0 +1,118 @@
+//! # Transcript
+//!
+//! A Fiat-Shamir transcript in the style of Merlin, built on the `sol_sha256` or
+//! `sol_keccak256` syscall rather than STROBE. Every proof in this crate derives its challenges
+//! from a `Transcript`, so each one is bound to a domain separator and every labelled value
+//! that precedes it.
+//!
+//! The hash function is any `challenge::ChallengeHasher`, or a `TranscriptHash` chosen at
+//! runtime. The transcript is a single 32-byte chaining value. Appending a message hashes
+//! `state ‖ len(label) ‖ label ‖ len(message) ‖ message` into a new state, with lengths as
+//! little-endian `u32` and `u64`, so one append costs one hash syscall and no two sequences of
+//! appends can collide. Points are appended in compressed SEC1 form, and scalars as 32
+//! big-endian bytes.
+
+use crate::{
+    arithmetic,
+    challenge::{ChallengeHasher, Keccak256, Sha256},
+    Secp256k1Point,
+};
+
+/// ### Transcript Hash
+///
+/// The hash function a transcript is built on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptHash {
+    Sha256,
+    Keccak256,
+}
+
+impl ChallengeHasher for TranscriptHash {
+    fn hash<const M: usize>(&self, vals: &[&[u8]; M]) -> [u8; 32] {
+        match self {
+            TranscriptHash::Sha256 => Sha256.hash(vals),
+            TranscriptHash::Keccak256 => Keccak256.hash(vals),
+        }
+    }
+}
+
+/// ### Transcript
+///
+/// A running Fiat-Shamir transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transcript<H: ChallengeHasher = TranscriptHash> {
+    state: [u8; 32],
+    hash: H,
+}
+
+impl Transcript {
+    /// ### New
+    ///
+    /// Starts a SHA-256 transcript separated by `domain`, eg: the name and version of a protocol.
+    pub fn new(domain: &[u8]) -> Self {
+        Self::with_hash(domain, TranscriptHash::Sha256)
+    }
+}
+
+impl<H: ChallengeHasher> Transcript<H> {
+    /// ### With Hash
+    ///
+    /// Starts a transcript on the given hash function. Transcripts on different hash functions
+    /// are unrelated.
+    pub fn with_hash(domain: &[u8], hash: H) -> Self {
+        let mut transcript = Self { state: [0u8; 32], hash };
+        transcript.append_message(b"dom-sep", domain);
+        transcript
+    }
+
+    /// ### Append Message
+    ///
+    /// Appends a labelled message.
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.state = self.hash(&[
+            &self.state,
+            &(label.len() as u32).to_le_bytes(),
+            label,
+            &(message.len() as u64).to_le_bytes(),
+            message,
+        ]);
+    }
+
+    /// ### Append U64
+    ///
+    /// Appends a labelled integer as 8 little-endian bytes.
+    pub fn append_u64(&mut self, label: &[u8], value: u64) {
+        self.append_message(label, &value.to_le_bytes());
+    }
+
+    /// ### Append Point
+    ///
+    /// Appends a labelled point in compressed SEC1 form, so a point hashes the same whichever
+    /// type holds it.
+    pub fn append_point<T: Secp256k1Point>(&mut self, label: &[u8], point: &T) {
+        self.append_message(label, &point.compress().0);
+    }
+
+    /// ### Append Scalar
+    ///
+    /// Appends a labelled big-endian scalar.
+    pub fn append_scalar(&mut self, label: &[u8], scalar: &[u8; 32]) {
+        self.append_message(label, scalar);
+    }
+
+    /// ### Challenge Scalar
+    ///
+    /// Derives a labelled challenge scalar modulo 𝑁 from everything appended so far, and
+    /// ratchets the transcript past it. The challenge is reduced from 64 bytes of output, so it
+    /// is unbiased.
+    pub fn challenge_scalar(&mut self, label: &[u8]) -> [u8; 32] {
+        self.append_message(b"challenge", label);
+        let wide = arithmetic::concat(&self.hash(&[&self.state, &[0]]), &self.hash(&[&self.state, &[1]]));
+        self.state = self.hash(&[&self.state, &[2]]);
+        arithmetic::to_be_bytes(&arithmetic::from_be_slice(&wide, &arithmetic::N))
+    }
+
+    fn hash<const M: usize>(&self, vals: &[&[u8]; M]) -> [u8; 32] {
+        self.hash.hash(vals)
+    }
+}
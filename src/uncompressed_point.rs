@@ -1,17 +1,12 @@
-use core::{fmt::{Debug, Formatter}, ops::Add};
+use core::{fmt::{Debug, Formatter}, ops::{Add, Mul}};
 
-use dashu::integer::UBig;
-use solana_nostd_secp256k1_recover::secp256k1_recover;
-
-#[cfg(feature="big-mod-exp")]
-use solana_nostd_big_mod_exp::big_mod_exp;
-
-
-use crate::{CompressedPoint, Curve, Secp256k1Error, Secp256k1Point};
+use crate::{arithmetic, backend, hex, CompressedPoint, Curve, Secp256k1Error, Secp256k1Point, WeierstrassCurve};
 
 pub const SEC1_OCTET_UNCOMPRESSED: u8 = 0x04;
 
 #[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize), rkyv(compare(PartialEq), derive(Debug)))]
+#[repr(transparent)]
 pub struct UncompressedPoint(pub [u8; Self::SIZE]);
 
 impl Secp256k1Point for UncompressedPoint {
@@ -25,228 +20,148 @@ impl Secp256k1Point for UncompressedPoint {
         self.0[63] & 1 != 1
     }
 
-    fn x(&self) -> [u8; 32] {
-        [
-            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5], self.0[6], self.0[7],
-            self.0[8], self.0[9], self.0[10], self.0[11], self.0[12], self.0[13], self.0[14],
-            self.0[15], self.0[16], self.0[17], self.0[18], self.0[19], self.0[20], self.0[21],
-            self.0[22], self.0[23], self.0[24], self.0[25], self.0[26], self.0[27], self.0[28],
-            self.0[29], self.0[30], self.0[31],
-        ]
+    fn x_ref(&self) -> &[u8; 32] {
+        self.0[..32].try_into().expect("64 byte point")
     }
 
     fn y(&self) -> [u8; 32] {
-        [
-            self.0[32], self.0[33], self.0[34], self.0[35], self.0[36], self.0[37], self.0[38],
-            self.0[39], self.0[40], self.0[41], self.0[42], self.0[43], self.0[44], self.0[45],
-            self.0[46], self.0[47], self.0[48], self.0[49], self.0[50], self.0[51], self.0[52],
-            self.0[53], self.0[54], self.0[55], self.0[56], self.0[57], self.0[58], self.0[59],
-            self.0[60], self.0[61], self.0[62], self.0[63],
-        ]
+        *self.y_ref()
     }
 
-    #[cfg(feature="big-mod-exp")]
-    fn lift_x(x: &[u8; 32]) -> Result<Self, Secp256k1Error> {
-        // y^2 = x^3 + 7 mod P
-        let x_3 = (&UBig::from_be_bytes(x).pow(3) + UBig::from_word(7)) % &UBig::from_be_bytes(&Curve::P);
-        // Use big_mod_exp for cheap cubed root
-        let y = big_mod_exp(&x_3.to_be_bytes(), &Curve::P_1_4, &Curve::P);
-        if (&UBig::from_be_bytes(&y).pow(2) % &UBig::from_be_bytes(&Curve::P)) != x_3 {
-            return Err(Secp256k1Error::InvalidYCoordinate);
-        }
-        let mut x_y = [0u8; 64];
-        x_y[..32].clone_from_slice(x);
-        x_y[32..].clone_from_slice(&y);
-        Ok(Self(x_y))
-    }
-
-    #[cfg(feature="big-mod-exp")]
-    fn lift_x_unchecked(x: &[u8; 32]) -> Self {
-        // We first compute y^2 = x^3 + 7 mod P
-        let x_3 = (&UBig::from_be_bytes(x).pow(3) + UBig::from_word(7)) % &UBig::from_be_bytes(&Curve::P);
-        // Use big_mod_exp for cheap cubed root
-        let y = big_mod_exp(&x_3.to_be_bytes(), &Curve::P_1_4, &Curve::P);
-        let mut x_y = [0u8; 64];
-        x_y[..32].clone_from_slice(x);
-        x_y[32..].clone_from_slice(&y);
-        Self(x_y)
-    }
-
-    #[cfg(not(feature="big-mod-exp"))]
     fn lift_x(x: &[u8; 32]) -> Result<Self, Secp256k1Error> {
         Curve::lift_x(x)
     }
 
-    #[cfg(not(feature="big-mod-exp"))]
     fn lift_x_unchecked(x: &[u8; 32]) -> Self {
         Curve::lift_x_unchecked(x)
     }
 
     fn invert(&mut self) {
-        let y = (UBig::from_be_bytes(&Curve::P) - UBig::from_be_bytes(&self.y())).to_be_bytes();
-        self.0[32..64].clone_from_slice(&[0u8;32]);
-        self.0[64-y.len()..].clone_from_slice(&y);
+        let y = Curve::negate_p(self.y_ref());
+        self.0[32..].copy_from_slice(&y);
     }
     
     fn compress(&self) -> CompressedPoint {
         CompressedPoint::from(*self)
     }
     
-    fn decompress(&self) -> UncompressedPoint {
+    fn decompress(&self) -> Result<UncompressedPoint, Secp256k1Error> {
+        Ok(*self)
+    }
+
+    fn decompress_unchecked(&self) -> UncompressedPoint {
         *self
     }
 
-    fn tweak(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error> {
-        // Compute z = (-r * k) mod N
-        let z_scalar = ((UBig::from_be_bytes(&Curve::negate_n(&self.x())) * UBig::from_be_bytes(&tweak)) % UBig::from_be_bytes(&Curve::N)).to_be_bytes();
-               
-        // Ensure z and s are 32 bytes
-        let mut z = [0u8; 32];
-        z[32 - z_scalar.len()..].copy_from_slice(&z_scalar);
-
-        let s: [u8; 64] = [
-            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5], self.0[6], self.0[7],
-            self.0[8], self.0[9], self.0[10], self.0[11], self.0[12], self.0[13], self.0[14], self.0[15],
-            self.0[16], self.0[17], self.0[18], self.0[19], self.0[20], self.0[21], self.0[22], self.0[23],
-            self.0[24], self.0[25], self.0[26], self.0[27], self.0[28], self.0[29], self.0[30], self.0[31],
-            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5], self.0[6], self.0[7],
-            self.0[8], self.0[9], self.0[10], self.0[11], self.0[12], self.0[13], self.0[14], self.0[15],
-            self.0[16], self.0[17], self.0[18], self.0[19], self.0[20], self.0[21], self.0[22], self.0[23],
-            self.0[24], self.0[25], self.0[26], self.0[27], self.0[28], self.0[29], self.0[30], self.0[31],
-        ];
-
-        // Use ecrecover with negated z to perform ECAdd
-        Ok(UncompressedPoint(secp256k1_recover(&z, self.is_odd(), &s)?))
+    fn tweak_unchecked(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error> {
+        Ok(UncompressedPoint(backend::tweak(self.x_ref(), self.is_odd(), &tweak)?))
+    }
+
+    fn tweak_mul_unchecked(&self, tweak: [u8; 32]) -> Result<Self, Secp256k1Error> {
+        Ok(UncompressedPoint(backend::ecmul(self.x_ref(), self.is_odd(), &tweak)?))
     }
 }
 
 impl Add<UncompressedPoint> for UncompressedPoint {
-    type Output = UncompressedPoint;
+    type Output = Result<UncompressedPoint, Secp256k1Error>;
 
+    /// Adds two points in affine coordinates, returning `IdentityPoint` if they are inverses of
+    /// one another.
     fn add(self, rhs: UncompressedPoint) -> Self::Output {
-        let rhs: UncompressedPoint = rhs.decompress();
-        let p = UBig::from_be_bytes(&Curve::P); // The modulus
+        let p = arithmetic::point_from_be_bytes(&self.0);
+        let q = arithmetic::point_from_be_bytes(&rhs.0);
+        arithmetic::affine_add_checked(&p, &q)
+            .map(|r| UncompressedPoint(arithmetic::point_to_be_bytes(&r)))
+            .ok_or(Secp256k1Error::IdentityPoint)
+    }
+}
 
-        // Convert [u8; 32] to UBig
-        let x_p = UBig::from_be_bytes(&self.x());
-        let y_p = UBig::from_be_bytes(&self.y());
-        let x_q = UBig::from_be_bytes(&rhs.x());
-        let y_q = UBig::from_be_bytes(&rhs.y());
+impl Add<CompressedPoint> for UncompressedPoint {
+    type Output = Result<UncompressedPoint, Secp256k1Error>;
 
-        // Calculate modular inverse using big_mod_exp
-        let inv = Curve::mod_inv_p(&(&x_q - &x_p).to_be_bytes()).expect("This shouldn't fail");
-        let inv = UBig::from_be_bytes(&inv);
+    fn add(self, rhs: CompressedPoint) -> Self::Output {
+        self.add(rhs.decompress()?)
+    }
+}
 
-        // m = (y_q - y_p) * modinv(x_q - x_p, p)
-        let m = (&y_q + &p - &y_p) * inv % &p;
+impl Mul<&[u8;32]> for UncompressedPoint {
+    type Output = Result<UncompressedPoint, Secp256k1Error>;
 
-        // xr = m^2 - x_p - x_q
-        let xr = (&m * &m + &p - &x_p - &x_q) % &p;
+    fn mul(self, k: &[u8;32]) -> Self::Output {
+        Curve::ecmul(&self, k)
+    }
+}
 
-        // yr = m * (x_p - xr) - y_p
-        let yr = (&m * (&x_p + &p - &xr) + &p - &y_p) % &p;
+impl Debug for UncompressedPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(hex::encode_upper_to_slice(&self.0, &mut [0u8; 128]).expect("128 hex digits"))
+    }
+}
 
-        // Convert results back to [u8; 32]
-        let mut result_x = [0u8; 32];
-        let mut result_y = [0u8; 32];
-        result_x.copy_from_slice(&xr.to_be_bytes());
-        result_y.copy_from_slice(&yr.to_be_bytes());
+impl UncompressedPoint {
+    /// ### From SEC1
+    /// 
+    /// Parses a 65-byte SEC1 uncompressed point, checking its prefix and that it is on curve.
+    pub fn from_sec1(p: &[u8; 65]) -> Result<Self, Secp256k1Error> {
+        if p[0] != SEC1_OCTET_UNCOMPRESSED {
+            return Err(Secp256k1Error::InvalidPublicKey);
+        }
+        let point = Self::from_sec1_unchecked(p);
+        if !point.is_on_curve() {
+            return Err(Secp256k1Error::InvalidPublicKey);
+        }
+        Ok(point)
+    }
 
-        // Construct the result as a new UncompressedPoint
-        let mut result = [0u8; 64];
-        result[..32].copy_from_slice(&result_x);
-        result[32..].copy_from_slice(&result_y);
+    /// ### From SEC1 Unchecked
+    /// 
+    /// Strips the prefix from a 65-byte SEC1 uncompressed point without any checks. Only use
+    /// this when you know you are dealing with a valid point.
+    pub fn from_sec1_unchecked(p: &[u8; 65]) -> Self {
+        let mut s = [0u8; 64];
+        s.copy_from_slice(&p[1..]);
+        UncompressedPoint(s)
+    }
 
-        UncompressedPoint(result)
+    /// ### From Eth Pubkey
+    /// 
+    /// Parses a raw 64-byte `𝑋‖𝑌` public key, the unprefixed layout Ethereum tooling passes
+    /// around, checking that it is on curve.
+    pub fn from_eth_pubkey(p: [u8; 64]) -> Result<Self, Secp256k1Error> {
+        let point = UncompressedPoint(p);
+        if !point.is_on_curve() {
+            return Err(Secp256k1Error::InvalidPublicKey);
+        }
+        Ok(point)
     }
-}
 
-impl Add<CompressedPoint> for UncompressedPoint {
-    type Output = UncompressedPoint;
+    /// ### To Eth Pubkey
+    /// 
+    /// Returns the raw 64-byte `𝑋‖𝑌` public key, without a SEC1 prefix, as Ethereum tooling
+    /// expects it. Use `EthAddress::from` for the address of the key.
+    pub const fn to_eth_pubkey(&self) -> [u8; 64] {
+        self.0
+    }
 
-    fn add(self, rhs: CompressedPoint) -> Self::Output {
-        self.add(rhs.decompress())
+    /// ### Is On Curve
+    /// 
+    /// Checks that both coordinates are less than 𝑃 and satisfy `𝑦² = 𝑥³ + 7`.
+    pub fn is_on_curve(&self) -> bool {
+        <Curve as WeierstrassCurve>::is_on_curve(&self.0)
     }
-}
 
-impl Debug for UncompressedPoint {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for byte in &self.0 {
-            write!(f, "{:02X}", byte)?;
-        }
-        Ok(())
+    /// ### 𝑌 Ref
+    /// 
+    /// Borrows the 𝑌 coordinate of the point without copying it. Only uncompressed points store
+    /// their 𝑌 coordinate, so this is not part of `Secp256k1Point`.
+    pub fn y_ref(&self) -> &[u8; 32] {
+        self.0[32..].try_into().expect("64 byte point")
     }
-}
 
-impl UncompressedPoint {
     pub fn to_sec1_bytes(&self) -> [u8; 65] {
-        [
-            SEC1_OCTET_UNCOMPRESSED,
-            self.0[0],
-            self.0[1],
-            self.0[2],
-            self.0[3],
-            self.0[4],
-            self.0[5],
-            self.0[6],
-            self.0[7],
-            self.0[8],
-            self.0[9],
-            self.0[10],
-            self.0[11],
-            self.0[12],
-            self.0[13],
-            self.0[14],
-            self.0[15],
-            self.0[16],
-            self.0[17],
-            self.0[18],
-            self.0[19],
-            self.0[20],
-            self.0[21],
-            self.0[22],
-            self.0[23],
-            self.0[24],
-            self.0[25],
-            self.0[26],
-            self.0[27],
-            self.0[28],
-            self.0[29],
-            self.0[30],
-            self.0[31],
-            self.0[32],
-            self.0[33],
-            self.0[34],
-            self.0[35],
-            self.0[36],
-            self.0[37],
-            self.0[38],
-            self.0[39],
-            self.0[40],
-            self.0[41],
-            self.0[42],
-            self.0[43],
-            self.0[44],
-            self.0[45],
-            self.0[46],
-            self.0[47],
-            self.0[48],
-            self.0[49],
-            self.0[50],
-            self.0[51],
-            self.0[52],
-            self.0[53],
-            self.0[54],
-            self.0[55],
-            self.0[56],
-            self.0[57],
-            self.0[58],
-            self.0[59],
-            self.0[60],
-            self.0[61],
-            self.0[62],
-            self.0[63],
-        ]
+        let mut p = [SEC1_OCTET_UNCOMPRESSED; 65];
+        p[1..].copy_from_slice(&self.0);
+        p
     }
 }
 
@@ -254,19 +169,16 @@ impl TryFrom<CompressedPoint> for UncompressedPoint {
     type Error = Secp256k1Error;
 
     fn try_from(x: CompressedPoint) -> Result<Self, Secp256k1Error> {
-        let mut point = UncompressedPoint::lift_x(&x.x())?;
-        if point.is_odd() != x.is_odd() {
-            point.invert();
-        }
-        Ok(point)
+        let (even, odd) = Curve::lift_x_both(x.x_ref())?;
+        Ok(if x.is_odd() { odd } else { even })
     }
 }
 
-impl From<[u8; 65]> for UncompressedPoint {
-    fn from(p: [u8; 65]) -> Self {
-        let mut s = [0u8; 64];
-        s.clone_from_slice(&p[1..]);
-        UncompressedPoint(s)
+impl TryFrom<[u8; 65]> for UncompressedPoint {
+    type Error = Secp256k1Error;
+
+    fn try_from(p: [u8; 65]) -> Result<Self, Secp256k1Error> {
+        Self::from_sec1(&p)
     }
 }
 
@@ -277,3 +189,29 @@ impl TryFrom<[u8; 32]> for UncompressedPoint {
         Curve::mul_g(&scalar)
     }
 }
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for UncompressedPoint {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        writer.write_all(&self.0)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for UncompressedPoint {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let mut p = [0u8; Self::SIZE];
+        reader.read_exact(&mut p)?;
+        let p = UncompressedPoint(p);
+        if !p.is_on_curve() {
+            return Err(borsh::io::Error::new(borsh::io::ErrorKind::InvalidData, "Point off curve"));
+        }
+        Ok(p)
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for UncompressedPoint {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for UncompressedPoint {}
@@ -0,0 +1,35 @@
+//! Measures the compute units of every verifier case on the SBF build, printing a table against
+//! `cu_estimate` and the limb arithmetic allowances the measurements imply.
+
+use solana_secp256k1::cu::cu_estimate;
+use solana_secp256k1_test_utils::{fixture, mollusk::Harness};
+
+fn main() {
+    let harness = Harness::new();
+    let baseline = harness.baseline();
+    let mut measured = Vec::new();
+
+    println!("| Case | Operation | Measured CUs | `cu_estimate` | Headroom |");
+    println!("|------|-----------|-------------:|--------------:|---------:|");
+    for case in fixture::cases() {
+        let execution = harness.run(&case.data);
+        assert!(execution.success, "{} failed", case.name);
+        // The entrypoint's own cost isn't part of the operation
+        let units = execution.compute_units.saturating_sub(baseline);
+        let estimate = cu_estimate(case.op);
+        let headroom = (estimate as f64 - units as f64) / estimate as f64 * 100.0;
+        println!("| `{}` | `{:?}` | {units} | {estimate} | {headroom:.1}% |", case.name, case.op);
+        measured.push((case.name, units));
+    }
+    println!("\nEntrypoint baseline: {baseline} CUs");
+
+    // `ModInv` costs `290·FIELD_MUL + OVERHEAD`, and each point of `EcaddMany` `12·FIELD_MUL + OVERHEAD`
+    let units = |name: &str| measured.iter().find(|(case, _)| *case == name).map(|(_, units)| *units as f64).expect("case");
+    let per_point = (units("ecadd_many_16") - units("ecadd_many_1")) / 15.0;
+    let field_mul = (units("mod_inv") - per_point) / 278.0;
+    let overhead = per_point - 12.0 * field_mul;
+    println!("Implied FIELD_MUL: {field_mul:.0} CUs, OVERHEAD: {overhead:.0} CUs");
+    // `DoubleMul` costs `ECMUL + 3·FIELD_MUL`
+    let double_mul = units("double_mul") - units("ecmul");
+    println!("DoubleMul over Ecmul: {double_mul:.0} CUs, {:.1}·FIELD_MUL", double_mul / field_mul);
+}
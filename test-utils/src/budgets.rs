@@ -0,0 +1,46 @@
+//! # Budgets
+//!
+//! The compute unit budgets checked in at `budgets.txt`, one `name units` pair per line, with
+//! `#` comments. Budgets are measured in Mollusk and exclude the entrypoint's baseline, as the
+//! compute unit bench reports them. A case costing more than its budget is a regression, and a
+//! case without one has not been recorded yet.
+
+use std::{collections::BTreeMap, fs};
+
+/// The path of the checked-in budgets.
+pub const PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/budgets.txt");
+
+const HEADER: &str = "\
+# Compute unit budgets of each verifier case in `fixture::cases`, less the entrypoint baseline,
+# checked by `cargo test --features mollusk`. Rerun it with `UPDATE_BUDGETS=1` to record new
+# measurements.
+";
+
+/// ### Parse
+///
+/// Parses budgets, panicking on a malformed line.
+pub fn parse(text: &str) -> BTreeMap<String, u64> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (name, units) = line.split_once(' ').unwrap_or_else(|| panic!("malformed budget `{line}`"));
+            (name.to_owned(), units.trim().parse().unwrap_or_else(|_| panic!("malformed budget `{line}`")))
+        })
+        .collect()
+}
+
+/// ### Load
+///
+/// Reads the checked-in budgets.
+pub fn load() -> BTreeMap<String, u64> {
+    parse(&fs::read_to_string(PATH).unwrap_or_else(|e| panic!("reading {PATH}: {e}")))
+}
+
+/// ### Store
+///
+/// Rewrites the checked-in budgets with freshly recorded ones.
+pub fn store(budgets: &BTreeMap<String, u64>) {
+    let body: String = budgets.iter().map(|(name, units)| format!("{name} {units}\n")).collect();
+    fs::write(PATH, String::from(HEADER) + &body).unwrap_or_else(|e| panic!("writing {PATH}: {e}"));
+}
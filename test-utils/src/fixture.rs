@@ -0,0 +1,212 @@
+//! # Fixture
+//!
+//! Instruction data for every instruction of `programs/verifier`, and the cases the harnesses
+//! run, each paired with the `cu::Operation` it exercises.
+
+use std::{env, fs, path::PathBuf};
+
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use solana_secp256k1::{bip340, cu::Operation, eth::RecoverableSignature, CompressedPoint, Curve, Secp256k1Point, SecretKey, UncompressedPoint};
+
+/// The address the verifier is deployed at.
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(*b"solana-secp256k1-verifier-fixtur");
+
+/// The name of the verifier's shared object, without the `.so` extension.
+pub const PROGRAM_NAME: &str = "solana_secp256k1_verifier";
+
+/// ### Program Path
+///
+/// The path of the verifier's ELF, in `SBF_OUT_DIR` if set and `target/deploy` at the root of
+/// the repository otherwise.
+pub fn program_path() -> PathBuf {
+    env::var_os("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../target/deploy"))
+        .join(format!("{PROGRAM_NAME}.so"))
+}
+
+/// ### Program ELF
+///
+/// Reads the verifier's ELF, panicking with the build command if it is missing.
+pub fn program_elf() -> Vec<u8> {
+    let path = program_path();
+    fs::read(&path).unwrap_or_else(|e| {
+        panic!("reading {}: {e}, build it with `cargo build-sbf --manifest-path programs/verifier/Cargo.toml`", path.display())
+    })
+}
+
+/// ### Instruction
+///
+/// An instruction to the verifier, which takes no accounts.
+pub fn instruction(data: &[u8]) -> Instruction {
+    Instruction::new_with_bytes(PROGRAM_ID, data, vec![])
+}
+
+/// `VerifyEcdsa`: recovers the signer of `hash` and compares it to `pubkey`.
+pub fn verify_ecdsa(hash: &[u8; 32], signature: &RecoverableSignature, pubkey: &UncompressedPoint) -> Vec<u8> {
+    [&[0u8][..], hash, &signature.r, &signature.s, &[signature.recovery_id], &pubkey.0].concat()
+}
+
+/// `VerifySchnorr`: BIP-340 spelled out by hand in the program.
+pub fn verify_schnorr(pubkey_x: &[u8; 32], message: &[u8; 32], signature: &[u8; 64]) -> Vec<u8> {
+    [&[1u8][..], pubkey_x, message, signature].concat()
+}
+
+/// `Ecmul`: `𝒌·𝑃`.
+pub fn ecmul(point: &CompressedPoint, k: &[u8; 32]) -> Vec<u8> {
+    [&[2u8][..], &point.0, k].concat()
+}
+
+/// `Tweak`: `𝑃 + 𝒕·𝐺`.
+pub fn tweak(point: &CompressedPoint, tweak: &[u8; 32]) -> Vec<u8> {
+    [&[3u8][..], &point.0, tweak].concat()
+}
+
+/// `MulG`: `𝒌·𝐺`.
+pub fn mul_g(k: &[u8; 32]) -> Vec<u8> {
+    [&[4u8][..], k].concat()
+}
+
+/// `Decompress`.
+pub fn decompress(point: &CompressedPoint) -> Vec<u8> {
+    [&[5u8][..], &point.0].concat()
+}
+
+/// `DoubleMul`: `𝒂·𝐺 + 𝒃·𝑃`.
+pub fn double_mul(a: &[u8; 32], point: &CompressedPoint, b: &[u8; 32]) -> Vec<u8> {
+    [&[6u8][..], a, &point.0, b].concat()
+}
+
+/// `ModInv`: `𝒌⁻¹ mod 𝑁`.
+pub fn mod_inv(k: &[u8; 32]) -> Vec<u8> {
+    [&[7u8][..], k].concat()
+}
+
+/// `PointAdd`: `𝑃 + 𝑄`.
+pub fn point_add(p: &UncompressedPoint, q: &UncompressedPoint) -> Vec<u8> {
+    [&[8u8][..], &p.0, &q.0].concat()
+}
+
+/// `EcaddMany`: `Σ 𝑃ᵢ`.
+pub fn ecadd_many(points: &[UncompressedPoint]) -> Vec<u8> {
+    let mut data = vec![9u8];
+    for point in points {
+        data.extend_from_slice(&point.0);
+    }
+    data
+}
+
+/// `VerifyBip340`: `bip340::verify`.
+pub fn verify_bip340(pubkey_x: &[u8; 32], message: &[u8; 32], signature: &[u8; 64]) -> Vec<u8> {
+    [&[10u8][..], pubkey_x, message, signature].concat()
+}
+
+/// ### Baseline
+///
+/// Instruction data with an unknown tag, which the program rejects straight away, so its cost
+/// is that of the entrypoint alone.
+pub const BASELINE: &[u8] = &[0xff];
+
+/// ### Case
+///
+/// An instruction exercising one operation, with the return data it must produce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    /// The name the case's budget is recorded under.
+    pub name: &'static str,
+    pub op: Operation,
+    pub data: Vec<u8>,
+    pub expected: Vec<u8>,
+}
+
+/// ### Cases
+///
+/// One case per operation the verifier exposes, with `EcaddMany` at a few sizes so its cost
+/// per point can be told apart from its final inversion.
+pub fn cases() -> Vec<Case> {
+    let key = SecretKey::from_bytes([0x17; 32]).expect("valid key");
+    let pubkey = key.public_key().expect("valid key");
+    let point = Curve::mul_g(&[0x29; 32]).expect("valid scalar");
+    let k = [0x3b; 32];
+    let hash = [0x42; 32];
+    let signature = key.sign_prehash(&hash).expect("valid signature");
+    let schnorr = bip340::sign(&key, &hash, &[0u8; 32]).expect("valid signature");
+    let points: Vec<_> = (1..=16u8).map(|i| Curve::mul_g(&[i; 32]).expect("valid scalar")).collect();
+    let sum = |n: usize| Curve::ecadd_many(&points[..n]).expect("non-zero sum").0.to_vec();
+
+    vec![
+        Case {
+            name: "verify_ecdsa",
+            op: Operation::VerifyEcdsa,
+            data: verify_ecdsa(&hash, &signature, &pubkey),
+            expected: vec![],
+        },
+        Case {
+            name: "verify_bip340",
+            op: Operation::VerifySchnorr,
+            data: verify_bip340(&pubkey.x(), &hash, &schnorr),
+            expected: vec![],
+        },
+        Case {
+            name: "ecmul",
+            op: Operation::Ecmul,
+            data: ecmul(&point.compress(), &k),
+            expected: Curve::ecmul(&point, &k).expect("valid scalar").0.to_vec(),
+        },
+        Case {
+            name: "tweak",
+            op: Operation::Tweak,
+            data: tweak(&point.compress(), &k),
+            expected: point.tweak(k).expect("valid tweak").0.to_vec(),
+        },
+        Case {
+            name: "mul_g",
+            op: Operation::MulG,
+            data: mul_g(&k),
+            expected: Curve::mul_g(&k).expect("valid scalar").0.to_vec(),
+        },
+        Case {
+            name: "decompress",
+            op: Operation::Decompress,
+            data: decompress(&point.compress()),
+            expected: point.0.to_vec(),
+        },
+        Case {
+            name: "double_mul",
+            op: Operation::DoubleMul,
+            data: double_mul(&hash, &point.compress(), &k),
+            expected: Curve::double_mul(&hash, &point, &k).expect("valid scalars").0.to_vec(),
+        },
+        Case {
+            name: "mod_inv",
+            op: Operation::ModInv,
+            data: mod_inv(&k),
+            expected: Curve::mod_inv_n(&k).expect("non-zero scalar").to_vec(),
+        },
+        Case {
+            name: "point_add",
+            op: Operation::PointAdd,
+            data: point_add(&point, &pubkey),
+            expected: (point + pubkey).expect("distinct points").0.to_vec(),
+        },
+        Case {
+            name: "ecadd_many_1",
+            op: Operation::EcaddMany { count: 1, compressed: false },
+            data: ecadd_many(&points[..1]),
+            expected: sum(1),
+        },
+        Case {
+            name: "ecadd_many_4",
+            op: Operation::EcaddMany { count: 4, compressed: false },
+            data: ecadd_many(&points[..4]),
+            expected: sum(4),
+        },
+        Case {
+            name: "ecadd_many_16",
+            op: Operation::EcaddMany { count: 16, compressed: false },
+            data: ecadd_many(&points),
+            expected: sum(16),
+        },
+    ]
+}
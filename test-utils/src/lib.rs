@@ -0,0 +1,22 @@
+//! # solana-secp256k1 test utils
+//!
+//! Test rigs running `solana-secp256k1` on-chain through the `programs/verifier` fixture
+//! program, so compute costs are measured on the SBF build rather than estimated. Build the
+//! program first with `cargo build-sbf --manifest-path programs/verifier/Cargo.toml`; its ELF is
+//! read from `SBF_OUT_DIR`, or `target/deploy` at the root of the repository.
+//!
+//! - `fixture`: instruction data for every verifier instruction, and the cases the rigs run.
+//! - `budgets`: the compute unit budgets checked in at `budgets.txt`.
+//! - `mollusk` (feature `mollusk`): runs instructions in Mollusk and reports their compute units.
+//!   `cargo test --features mollusk` fails when any case costs more than its budget, or has none.
+//! - `litesvm` (feature `litesvm`): deploys the verifier to LiteSVM, with helpers sending
+//!   `ecmul`, `tweak`, `mul_g` and signature verification as transactions.
+
+pub mod budgets;
+pub mod fixture;
+
+#[cfg(feature = "mollusk")]
+pub mod mollusk;
+
+#[cfg(feature = "litesvm")]
+pub mod litesvm;
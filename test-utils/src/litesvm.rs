@@ -0,0 +1,105 @@
+//! # LiteSVM
+//!
+//! An end-to-end rig deploying the verifier to LiteSVM, with helpers sending each operation as
+//! a signed transaction and decoding its return data. Downstream crates can reuse it to check
+//! their own inputs against the SBF build.
+
+use litesvm::{types::TransactionMetadata, LiteSVM};
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_keypair::Keypair;
+use solana_secp256k1::{eth::RecoverableSignature, CompressedPoint, UncompressedPoint};
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+
+use crate::fixture::{self, PROGRAM_ID};
+
+/// The largest compute unit limit a transaction can request.
+const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// ### Rig
+///
+/// A LiteSVM instance with the verifier deployed at `fixture::PROGRAM_ID` and a funded payer.
+pub struct Rig {
+    pub svm: LiteSVM,
+    pub payer: Keypair,
+}
+
+impl Rig {
+    /// ### New
+    ///
+    /// Deploys the verifier from `fixture::program_path` and funds a fresh payer.
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(PROGRAM_ID, fixture::program_path())
+            .unwrap_or_else(|e| panic!("deploying {}: {e:?}", fixture::program_path().display()));
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).expect("airdrop");
+        Self { svm, payer }
+    }
+
+    /// ### Send
+    ///
+    /// Sends a transaction with a single verifier instruction, returning its metadata, which
+    /// holds the compute units consumed and the return data. The transaction requests the
+    /// maximum compute unit limit, and the blockhash is expired after each one, so the same
+    /// instruction can be sent again.
+    pub fn send(&mut self, data: &[u8]) -> Result<TransactionMetadata, TransactionError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNITS), fixture::instruction(data)],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx).map_err(|failed| failed.err);
+        self.svm.expire_blockhash();
+        result
+    }
+
+    /// ### Ecmul
+    ///
+    /// Calculates `𝒌·𝑃` on-chain.
+    pub fn ecmul(&mut self, point: &CompressedPoint, k: &[u8; 32]) -> Result<UncompressedPoint, TransactionError> {
+        self.point(&fixture::ecmul(point, k))
+    }
+
+    /// ### Tweak
+    ///
+    /// Calculates `𝑃 + 𝒕·𝐺` on-chain.
+    pub fn tweak(&mut self, point: &CompressedPoint, tweak: &[u8; 32]) -> Result<UncompressedPoint, TransactionError> {
+        self.point(&fixture::tweak(point, tweak))
+    }
+
+    /// ### Mul G
+    ///
+    /// Calculates `𝒌·𝐺` on-chain.
+    pub fn mul_g(&mut self, k: &[u8; 32]) -> Result<UncompressedPoint, TransactionError> {
+        self.point(&fixture::mul_g(k))
+    }
+
+    /// ### Verify ECDSA
+    ///
+    /// Verifies a recoverable ECDSA signature over `hash` by `pubkey` on-chain.
+    pub fn verify_ecdsa(&mut self, hash: &[u8; 32], signature: &RecoverableSignature, pubkey: &UncompressedPoint) -> Result<(), TransactionError> {
+        self.send(&fixture::verify_ecdsa(hash, signature, pubkey)).map(|_| ())
+    }
+
+    /// ### Verify BIP-340
+    ///
+    /// Verifies a BIP-340 signature over `message` by the x-only `pubkey_x` on-chain.
+    pub fn verify_bip340(&mut self, pubkey_x: &[u8; 32], message: &[u8; 32], signature: &[u8; 64]) -> Result<(), TransactionError> {
+        self.send(&fixture::verify_bip340(pubkey_x, message, signature)).map(|_| ())
+    }
+
+    fn point(&mut self, data: &[u8]) -> Result<UncompressedPoint, TransactionError> {
+        let meta = self.send(data)?;
+        let point: [u8; 64] = meta.return_data.data.as_slice().try_into().expect("64 bytes of return data");
+        Ok(UncompressedPoint(point))
+    }
+}
+
+impl Default for Rig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,62 @@
+//! # Mollusk
+//!
+//! Runs verifier instructions in Mollusk, which executes the SBF build of the program with the
+//! default compute budget and reports the compute units each instruction consumed.
+
+use mollusk_svm::{program::loader_keys, result::ProgramResult, Mollusk};
+
+use crate::fixture::{self, PROGRAM_ID};
+
+/// ### Execution
+///
+/// The outcome of a single instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Execution {
+    pub success: bool,
+    pub compute_units: u64,
+    pub return_data: Vec<u8>,
+}
+
+/// ### Harness
+///
+/// A Mollusk instance with the verifier deployed at `fixture::PROGRAM_ID`.
+pub struct Harness {
+    pub mollusk: Mollusk,
+}
+
+impl Harness {
+    /// ### New
+    ///
+    /// Deploys the verifier from `fixture::program_path`.
+    pub fn new() -> Self {
+        let mut mollusk = Mollusk::default();
+        mollusk.add_program_with_elf_and_loader(&PROGRAM_ID, &fixture::program_elf(), &loader_keys::LOADER_V3);
+        Self { mollusk }
+    }
+
+    /// ### Run
+    ///
+    /// Executes an instruction with the given data.
+    pub fn run(&self, data: &[u8]) -> Execution {
+        let result = self.mollusk.process_instruction(&fixture::instruction(data), &[]);
+        Execution {
+            success: matches!(result.program_result, ProgramResult::Success),
+            compute_units: result.compute_units_consumed,
+            return_data: result.return_data,
+        }
+    }
+
+    /// ### Baseline
+    ///
+    /// The compute units of the entrypoint alone, to subtract from a measurement for the cost of
+    /// the operation it wraps.
+    pub fn baseline(&self) -> u64 {
+        self.run(fixture::BASELINE).compute_units
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,84 @@
+use solana_secp256k1_test_utils::{budgets, fixture};
+
+#[test]
+fn budgets_name_cases() {
+    let names: Vec<_> = fixture::cases().iter().map(|case| case.name).collect();
+    for name in budgets::load().keys() {
+        assert!(names.contains(&name.as_str()), "budget for unknown case {name}");
+    }
+    assert_eq!(budgets::parse("# comment\n\nmul_g 27000\n")["mul_g"], 27000);
+}
+
+#[test]
+fn cases_match_native() {
+    // The program's logic run natively, so fixtures are checked without an SVM
+    for case in fixture::cases() {
+        let output = solana_secp256k1_verifier::process_instruction(&case.data).unwrap_or_else(|e| panic!("{}: {e:?}", case.name));
+        assert_eq!(output.as_ref().map_or(&[][..], |data| data.as_bytes()), case.expected, "{}", case.name);
+    }
+    assert!(solana_secp256k1_verifier::process_instruction(fixture::BASELINE).is_err());
+}
+
+#[cfg(feature = "mollusk")]
+#[test]
+fn within_budgets() {
+    use std::{collections::BTreeMap, env};
+
+    use solana_secp256k1_test_utils::mollusk::Harness;
+
+    let harness = Harness::new();
+    let baseline = harness.baseline();
+    let budgets = budgets::load();
+    let mut measured = BTreeMap::new();
+    let mut over = Vec::new();
+    for case in fixture::cases() {
+        let execution = harness.run(&case.data);
+        assert!(execution.success, "{} failed", case.name);
+        assert_eq!(execution.return_data, case.expected, "{} returned the wrong data", case.name);
+        // Measured as `estimates_cover_measurements` and the bench do, without the entrypoint
+        let units = execution.compute_units.saturating_sub(baseline);
+        match budgets.get(case.name) {
+            None => over.push(format!("{}: {units} CUs, no budget recorded", case.name)),
+            Some(budget) if units > *budget => over.push(format!("{}: {units} CUs, budget {budget}", case.name)),
+            Some(_) => {}
+        }
+        measured.insert(case.name.to_owned(), units);
+    }
+
+    if env::var_os("UPDATE_BUDGETS").is_some() {
+        budgets::store(&measured);
+        return;
+    }
+    assert!(over.is_empty(), "over budget, rerun with UPDATE_BUDGETS=1 if intended:\n{}", over.join("\n"));
+}
+
+#[cfg(feature = "mollusk")]
+#[test]
+fn estimates_cover_measurements() {
+    use solana_secp256k1::cu::cu_estimate;
+    use solana_secp256k1_test_utils::mollusk::Harness;
+
+    // Programs budget against `cu_estimate`, eg: `msm::AddCursor::step` stopping short of the
+    // compute limit, so every operation should cost at most its estimate
+    let harness = Harness::new();
+    let baseline = harness.baseline();
+    let under: Vec<_> = fixture::cases()
+        .into_iter()
+        .filter_map(|case| {
+            let units = harness.run(&case.data).compute_units.saturating_sub(baseline);
+            (units > cu_estimate(case.op)).then(|| format!("{}: {units} CUs, estimate {}", case.name, cu_estimate(case.op)))
+        })
+        .collect();
+    assert!(under.is_empty(), "cu_estimate below the measured cost:\n{}", under.join("\n"));
+}
+
+#[cfg(feature = "mollusk")]
+#[test]
+fn rejects_bad_input() {
+    let harness = solana_secp256k1_test_utils::mollusk::Harness::new();
+    assert!(!harness.run(fixture::BASELINE).success);
+    let mut data = fixture::mod_inv(&[0u8; 32]);
+    assert!(!harness.run(&data).success);
+    data.pop();
+    assert!(!harness.run(&data).success);
+}
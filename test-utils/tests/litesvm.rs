@@ -0,0 +1,45 @@
+#![cfg(feature = "litesvm")]
+
+use solana_secp256k1::{bip340, Curve, Secp256k1Point, SecretKey};
+use solana_secp256k1_test_utils::{fixture, litesvm::Rig};
+
+#[test]
+fn point_operations() {
+    let mut rig = Rig::new();
+    let point = Curve::mul_g(&[0x29; 32]).unwrap();
+    let k = [0x3b; 32];
+    assert_eq!(rig.ecmul(&point.compress(), &k), Ok(Curve::ecmul(&point, &k).unwrap()));
+    assert_eq!(rig.tweak(&point.compress(), &k), Ok(point.tweak(k).unwrap()));
+    assert_eq!(rig.mul_g(&k), Ok(Curve::mul_g(&k).unwrap()));
+    // The same instruction twice, and scalars the program must reject
+    assert_eq!(rig.mul_g(&k), Ok(Curve::mul_g(&k).unwrap()));
+    assert!(rig.mul_g(&[0u8; 32]).is_err());
+    assert!(rig.mul_g(&Curve::N).is_err());
+}
+
+#[test]
+fn verification() {
+    let mut rig = Rig::new();
+    let key = SecretKey::from_bytes([0x17; 32]).unwrap();
+    let pubkey = key.public_key().unwrap();
+    let hash = [0x42; 32];
+
+    let signature = key.sign_prehash(&hash).unwrap();
+    assert_eq!(rig.verify_ecdsa(&hash, &signature, &pubkey), Ok(()));
+    assert!(rig.verify_ecdsa(&[0x43; 32], &signature, &pubkey).is_err());
+
+    let schnorr = bip340::sign(&key, &hash, &[0u8; 32]).unwrap();
+    assert_eq!(rig.verify_bip340(&pubkey.x(), &hash, &schnorr), Ok(()));
+    assert!(rig.verify_bip340(&pubkey.x(), &[0x43; 32], &schnorr).is_err());
+}
+
+#[test]
+fn every_case() {
+    let mut rig = Rig::new();
+    // `EcaddMany` over 16 points is too large for a transaction, so only runs in Mollusk
+    for case in fixture::cases().into_iter().filter(|case| case.data.len() < 1_000) {
+        let meta = rig.send(&case.data).unwrap_or_else(|e| panic!("{}: {e:?}", case.name));
+        assert_eq!(meta.return_data.data, case.expected, "{}", case.name);
+        assert!(meta.compute_units_consumed > 0);
+    }
+}
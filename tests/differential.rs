@@ -0,0 +1,230 @@
+//! # Differential
+//!
+//! Checks `mul_g`, `ecmul`, point addition, `ecadd`, `tweak`, `tweak_mul`, `double_mul`,
+//! `lift_x`, `lift_x_both`, decompression and ECDSA verification and recovery byte for byte
+//! against `k256` over pseudorandom inputs, plus the edge cases the recover tricks are most likely to get wrong:
+//! scalars next to 0 and 𝑁, scalars with leading zero bytes, and both parities of every point.
+//!
+//! The native backend computes most operations with `k256` directly, so run these with
+//! `--features test-stubs` as well, which routes them through the same `secp256k1_recover`
+//! constructions as the SBF build.
+
+use k256::{
+    ecdsa::{signature::hazmat::PrehashVerifier, RecoveryId, Signature, SigningKey, VerifyingKey},
+    elliptic_curve::{
+        ops::Reduce,
+        sec1::{FromEncodedPoint, ToEncodedPoint},
+        PrimeField,
+    },
+    AffinePoint, EncodedPoint, ProjectivePoint, Scalar, U256,
+};
+use sha2::{Digest, Sha256};
+use solana_secp256k1::{
+    eth::RecoverableSignature, CompressedPoint, Curve, Secp256k1Error, Secp256k1Point, SecretKey,
+    UncompressedPoint,
+};
+
+const SEED: &[u8] = b"solana-secp256k1 differential";
+const COUNT: u32 = 64;
+
+/// 𝑁 - 1
+const N_MINUS_1: [u8; 32] = [
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE, 0xBA, 0xAE,
+    0xDC, 0xE6, 0xAF, 0x48, 0xA0, 0x3B, 0xBF, 0xD2, 0x5E, 0x8C, 0xD0, 0x36, 0x41, 0x40,
+];
+
+/// Derives the `i`th pseudorandom 32 bytes of a kind.
+fn bytes(kind: &str, i: u32) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(SEED)
+        .chain_update(kind)
+        .chain_update(i.to_le_bytes())
+        .finalize()
+        .into()
+}
+
+fn small(k: u64) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[24..].copy_from_slice(&k.to_be_bytes());
+    out
+}
+
+/// Pseudorandom scalars in `1..𝑁`, followed by the edge cases.
+fn scalars(kind: &str) -> Vec<[u8; 32]> {
+    let mut scalars: Vec<[u8; 32]> = (0..COUNT).map(|i| Curve::add_mod_n(&bytes(kind, i), &[0u8; 32])).collect();
+    // Leading zero bytes, so short big-endian encodings have to be padded correctly
+    scalars.extend((0..COUNT / 4).map(|i| {
+        let mut k = bytes(kind, COUNT + i);
+        k[..(i as usize % 31) + 1].fill(0);
+        k
+    }));
+    scalars.extend([small(1), small(2), small(3), N_MINUS_1, Curve::N_DIV_2]);
+    scalars.extend([2, 3].map(|k| Curve::negate_n(&small(k))));
+    scalars.retain(|k| *k != [0u8; 32]);
+    scalars
+}
+
+/// Pseudorandom points of both parities, and 𝐺, -𝐺 and their small multiples.
+fn points(kind: &str) -> Vec<UncompressedPoint> {
+    scalars(kind).iter().map(|k| Curve::mul_g(k).expect("nonzero scalar")).collect()
+}
+
+fn k256_scalar(k: &[u8; 32]) -> Scalar {
+    <Scalar as Reduce<U256>>::reduce_bytes(k.into())
+}
+
+fn k256_point(p: &UncompressedPoint) -> ProjectivePoint {
+    let encoded = EncodedPoint::from_bytes(p.to_sec1_bytes()).expect("valid encoding");
+    Option::<AffinePoint>::from(AffinePoint::from_encoded_point(&encoded)).expect("point on curve").into()
+}
+
+/// Encodes a `k256` point as this crate would, or `IdentityPoint` at infinity.
+fn expected(p: ProjectivePoint) -> Result<UncompressedPoint, Secp256k1Error> {
+    let encoded = p.to_affine().to_encoded_point(false);
+    match encoded.as_bytes() {
+        [0x04, rest @ ..] => Ok(UncompressedPoint(rest.try_into().expect("64 byte point"))),
+        _ => Err(Secp256k1Error::IdentityPoint),
+    }
+}
+
+#[test]
+fn mul_g() {
+    for k in scalars("mul_g") {
+        assert_eq!(Curve::mul_g(&k), expected(ProjectivePoint::GENERATOR * k256_scalar(&k)), "k = {}", hex::encode(k));
+    }
+}
+
+#[test]
+fn ecmul() {
+    for (p, k) in points("ecmul.point").into_iter().zip(scalars("ecmul.k")) {
+        let want = expected(k256_point(&p) * k256_scalar(&k));
+        assert_eq!(Curve::ecmul(&p, &k), want, "p = {p:?}, k = {}", hex::encode(k));
+        assert_eq!(Curve::ecmul(&p.compress(), &k), want, "p = {p:?}, k = {}", hex::encode(k));
+        assert_eq!(p.tweak_mul(k), want, "p = {p:?}, k = {}", hex::encode(k));
+        assert_eq!(p.compress().tweak_mul(k), want.map(|p| p.compress()), "p = {p:?}, k = {}", hex::encode(k));
+    }
+    // 𝒌 ≡ 0 gives the point at infinity, however the recover trick sees it
+    let p = Curve::mul_g(&small(7)).expect("nonzero scalar");
+    assert_eq!(Curve::ecmul_unchecked(&p, &Curve::N), expected(k256_point(&p) * k256_scalar(&Curve::N)));
+    assert_eq!(Curve::mul_g_unchecked(&Curve::N), Err(Secp256k1Error::IdentityPoint));
+}
+
+#[test]
+fn add() {
+    for (p, q) in points("add.p").into_iter().zip(points("add.q")) {
+        let want = expected(k256_point(&p) + k256_point(&q)).expect("distinct points");
+        assert_eq!(p + q, Ok(want), "p = {p:?}, q = {q:?}");
+        assert_eq!(p + q.compress(), Ok(want), "p = {p:?}, q = {q:?}");
+        assert_eq!(Curve::ecadd(&p, &q), Ok(want), "p = {p:?}, q = {q:?}");
+        assert_eq!(Curve::ecadd(&p.compress(), &q.compress()), Ok(want), "p = {p:?}, q = {q:?}");
+        // Doubling takes a separate branch
+        assert_eq!(p + p, expected(k256_point(&p).double()), "p = {p:?}");
+        assert_eq!(Curve::ecadd(&p, &p), expected(k256_point(&p).double()), "p = {p:?}");
+        assert_eq!(Curve::ecadd(&p, &Curve::G), expected(k256_point(&p) + ProjectivePoint::GENERATOR), "p = {p:?}");
+    }
+}
+
+#[test]
+fn tweak() {
+    for (p, t) in points("tweak.point").into_iter().zip(scalars("tweak.t")) {
+        let want = expected(k256_point(&p) + ProjectivePoint::GENERATOR * k256_scalar(&t));
+        assert_eq!(p.tweak(t), want, "p = {p:?}, t = {}", hex::encode(t));
+        assert_eq!(p.compress().tweak(t), want.map(|p| p.compress()), "p = {p:?}, t = {}", hex::encode(t));
+    }
+    // 𝑃 + (-𝒌)·𝐺 for 𝑃 = 𝒌·𝐺 is the point at infinity
+    for k in scalars("tweak.identity") {
+        let p = Curve::mul_g(&k).expect("nonzero scalar");
+        assert_eq!(p.tweak(Curve::negate_n(&k)), Err(Secp256k1Error::IdentityPoint), "k = {}", hex::encode(k));
+    }
+}
+
+#[test]
+fn double_mul() {
+    for ((p, a), b) in points("double_mul.point").into_iter().zip(scalars("double_mul.a")).zip(scalars("double_mul.b")) {
+        let want = expected(ProjectivePoint::GENERATOR * k256_scalar(&a) + k256_point(&p) * k256_scalar(&b));
+        assert_eq!(Curve::double_mul(&a, &p, &b), want, "p = {p:?}, a = {}, b = {}", hex::encode(a), hex::encode(b));
+        assert_eq!(Curve::double_mul(&a, &p.compress(), &b), want, "p = {p:?}, a = {}, b = {}", hex::encode(a), hex::encode(b));
+    }
+}
+
+#[test]
+fn lift_x() {
+    for i in 0..COUNT {
+        // Half are 𝑋-coordinates of points, and the rest are arbitrary, so about half of those are
+        // off curve
+        let x = if i % 2 == 0 { Curve::mul_g(&bytes("lift_x.k", i)).expect("nonzero scalar").x() } else { bytes("lift_x.x", i) };
+        let compressed = |prefix: u8| {
+            let mut sec1 = [prefix; 33];
+            sec1[1..].copy_from_slice(&x);
+            sec1
+        };
+        let even = Option::<AffinePoint>::from(AffinePoint::from_encoded_point(
+            &EncodedPoint::from_bytes(compressed(0x02)).expect("valid encoding"),
+        ));
+        match (UncompressedPoint::lift_x(&x), even) {
+            (Ok(lifted), Some(even)) => {
+                // `lift_x` returns the principal square root, which may have either parity
+                let want = expected(even.into()).expect("finite point");
+                assert_eq!(Curve::lift_x_both(&x), Ok((want, want.negate())), "x = {}", hex::encode(x));
+                assert!(lifted == want || lifted == want.negate(), "x = {}", hex::encode(x));
+                for prefix in [0x02, 0x03] {
+                    let want = expected(
+                        Option::<AffinePoint>::from(AffinePoint::from_encoded_point(
+                            &EncodedPoint::from_bytes(compressed(prefix)).expect("valid encoding"),
+                        ))
+                        .expect("on curve")
+                        .into(),
+                    );
+                    assert_eq!(CompressedPoint(compressed(prefix)).decompress(), want, "x = {}", hex::encode(x));
+                }
+            }
+            (Err(_), None) => {
+                assert_eq!(Curve::lift_x_both(&x), Err(Secp256k1Error::InvalidYCoordinate), "x = {}", hex::encode(x));
+                assert!(CompressedPoint(compressed(0x02)).decompress().is_err(), "x = {}", hex::encode(x));
+            }
+            (lifted, even) => panic!("x = {}: lift_x gave {lifted:?}, k256 gave {even:?}", hex::encode(x)),
+        }
+    }
+}
+
+#[test]
+fn ecdsa() {
+    for (key, hash) in scalars("ecdsa.key").into_iter().zip(scalars("ecdsa.hash")) {
+        let key = SecretKey::from_bytes(key).expect("scalar in range");
+        let signer = SigningKey::from_bytes(key.as_bytes().into()).expect("scalar in range");
+        let pubkey = key.public_key().expect("valid key");
+        assert_eq!(pubkey, expected(signer.verifying_key().as_affine().into()).expect("finite point"));
+
+        let (signature, recovery_id): (Signature, RecoveryId) =
+            signer.sign_prehash_recoverable(&hash).expect("valid prehash");
+        let bytes: [u8; 64] = signature.to_bytes().into();
+        assert_eq!(Curve::verify_ecdsa(&hash, &bytes, &pubkey.compress()), Ok(()));
+
+        let recoverable = RecoverableSignature {
+            r: bytes[..32].try_into().expect("32 bytes"),
+            s: bytes[32..].try_into().expect("32 bytes"),
+            recovery_id: recovery_id.to_byte(),
+        };
+        let want = VerifyingKey::recover_from_prehash(&hash, &signature, recovery_id).expect("recoverable");
+        assert_eq!(recoverable.recover(&hash), expected(want.as_affine().into()), "hash = {}", hex::encode(hash));
+
+        // Both reject a signature over a different hash
+        let other = Curve::add_mod_n(&hash, &small(1));
+        assert_eq!(
+            Curve::verify_ecdsa(&other, &bytes, &pubkey.compress()).is_ok(),
+            want.verify_prehash(&other, &signature).is_ok()
+        );
+    }
+}
+
+#[test]
+fn scalar_arithmetic() {
+    for (a, b) in scalars("scalar.a").into_iter().zip(scalars("scalar.b")) {
+        let (ka, kb) = (k256_scalar(&a), k256_scalar(&b));
+        let bytes = |k: Scalar| -> [u8; 32] { k.to_repr().into() };
+        assert_eq!(Curve::add_mod_n(&a, &b), bytes(ka + kb));
+        assert_eq!(Curve::mul_mod_n(&a, &b), bytes(ka * kb));
+        assert_eq!(Curve::negate_n(&a), bytes(-ka));
+        assert_eq!(Curve::mod_inv_n(&a), Ok(bytes(ka.invert().unwrap())));
+    }
+}
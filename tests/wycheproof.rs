@@ -0,0 +1,102 @@
+//! # Wycheproof
+//!
+//! Runs the Wycheproof `ecdsa_secp256k1_sha256_bitcoin` vectors against public key parsing and
+//! the crate's own DER parsers and ECDSA verification, both `der::parse_signature` with
+//! `Curve::verify_ecdsa` and `bitcoin::verify_ecdsa`. The Bitcoin variant rejects high-𝑠
+//! signatures, matching both.
+//!
+//! The ECDSA vectors are vendored from Wycheproof under `tests/data/wycheproof`, along with
+//! their license. The `ecdh_secp256k1` vectors run against `SecretKey::diffie_hellman` once
+//! `ecdh_secp256k1_test.json` is vendored alongside them.
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use solana_secp256k1::{
+    bitcoin, der, CompressedPoint, Curve, Secp256k1Point, SecretKey, UncompressedPoint, WeierstrassCurve,
+};
+
+const VECTORS: &str = include_str!("data/wycheproof/ecdsa_secp256k1_sha256_bitcoin_test.json");
+
+const ECDH_VECTORS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/wycheproof/ecdh_secp256k1_test.json");
+
+fn verify(pubkey: &CompressedPoint, msg: &[u8], der: &[u8]) -> bool {
+    let hash: [u8; 32] = Sha256::digest(msg).into();
+    let verified = der::parse_signature(der).and_then(|signature| Curve::verify_ecdsa(&hash, &signature, pubkey)).is_ok();
+    let mut signature = der.to_vec();
+    signature.push(bitcoin::SIGHASH_ALL);
+    let bitcoin_verified = bitcoin::verify_ecdsa(&signature, pubkey, &hash) == Ok(bitcoin::SIGHASH_ALL);
+    assert_eq!(verified, bitcoin_verified, "bitcoin::verify_ecdsa disagrees on {}", hex::encode(der));
+    verified
+}
+
+#[test]
+fn ecdsa_secp256k1_sha256_bitcoin() {
+    let vectors: Value = serde_json::from_str(VECTORS).unwrap();
+    let mut failures = vec![];
+    let mut count = 0;
+    for group in vectors["testGroups"].as_array().unwrap() {
+        let sec1: [u8; 65] = hex::decode(group["publicKey"]["uncompressed"].as_str().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let pubkey = UncompressedPoint::try_from(sec1).unwrap();
+        let mut compressed = [0u8; 33];
+        compressed[0] = 0x02 | (sec1[64] & 1);
+        compressed[1..].copy_from_slice(&sec1[1..33]);
+        assert_eq!(Curve::decompress_sec1(&compressed), Ok(pubkey.0));
+        assert_eq!(UncompressedPoint::try_from(CompressedPoint(compressed)), Ok(pubkey));
+        assert_eq!(pubkey.compress().0, compressed);
+
+        for test in group["tests"].as_array().unwrap() {
+            count += 1;
+            let msg = hex::decode(test["msg"].as_str().unwrap()).unwrap();
+            let sig = hex::decode(test["sig"].as_str().unwrap()).unwrap();
+            let expected = test["result"].as_str().unwrap() == "valid";
+            if verify(&CompressedPoint(compressed), &msg, &sig) != expected {
+                failures.push((test["tcId"].as_u64().unwrap(), test["comment"].as_str().unwrap().to_owned()));
+            }
+        }
+    }
+    assert_eq!(count, vectors["numberOfTests"].as_u64().unwrap());
+    assert!(failures.is_empty(), "{} failures: {:?}", failures.len(), failures);
+}
+
+/// Parses a Wycheproof private key, an unsigned big-endian integer that may carry a leading
+/// zero byte or be shorter than 32 bytes.
+fn parse_private(private: &[u8]) -> Option<SecretKey> {
+    let start = private.iter().position(|&b| b != 0).unwrap_or(private.len());
+    let private = &private[start..];
+    let mut key = [0u8; 32];
+    key.get_mut(32usize.checked_sub(private.len())?..)?.copy_from_slice(private);
+    SecretKey::from_bytes(key).ok()
+}
+
+#[test]
+#[ignore = "ecdh_secp256k1_test.json is not vendored yet"]
+fn ecdh_secp256k1() {
+    let vectors: Value = serde_json::from_str(&std::fs::read_to_string(ECDH_VECTORS).unwrap()).unwrap();
+    let mut failures = vec![];
+    let mut count = 0;
+    for group in vectors["testGroups"].as_array().unwrap() {
+        assert_eq!(group["curve"], "secp256k1");
+        for test in group["tests"].as_array().unwrap() {
+            count += 1;
+            let public = hex::decode(test["public"].as_str().unwrap()).unwrap();
+            let private = hex::decode(test["private"].as_str().unwrap()).unwrap();
+            let shared = hex::decode(test["shared"].as_str().unwrap()).unwrap();
+            let result = der::parse_spki(&public)
+                .and_then(|pubkey| parse_private(&private).unwrap().diffie_hellman(&pubkey));
+            let passed = match test["result"].as_str().unwrap() {
+                "valid" => result.as_ref().map(|shared| shared.as_slice()) == Ok(shared.as_slice()),
+                "invalid" => result.is_err(),
+                // Compressed keys and other lenient encodings may be accepted or rejected
+                _ => result.map_or(true, |result| result.as_slice() == shared.as_slice()),
+            };
+            if !passed {
+                failures.push((test["tcId"].as_u64().unwrap(), test["comment"].as_str().unwrap().to_owned()));
+            }
+        }
+    }
+    assert_eq!(count, vectors["numberOfTests"].as_u64().unwrap());
+    assert!(failures.is_empty(), "{} failures: {:?}", failures.len(), failures);
+}